@@ -0,0 +1,44 @@
+//! Benchmarks of the layout engines on synthetic graphs, run with `cargo bench`.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+use rs_plode::engines::lattice::Grid;
+use rs_plode::engines::termination::Termination;
+use rs_plode::graph::generators::{grid, scale_free};
+use rs_plode::graph::AdjacencyGraph;
+use rs_plode::Graph;
+
+fn graphs() -> Vec<(String, AdjacencyGraph)> {
+    let mut graphs = Vec::new();
+    for side in [5, 10, 20] {
+        graphs.push((format!("grid-{}x{}", side, side), grid(side, side)));
+    }
+    for nodes in [25, 100, 400] {
+        graphs.push((format!("scale-free-{}", nodes), scale_free(nodes, 2, 0)));
+    }
+    graphs
+}
+
+fn fruchterman_reingold(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fruchterman_reingold");
+    group.sample_size(10);
+    for (name, graph) in graphs() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &graph, |b, graph| {
+            b.iter(|| graph.layout(FruchtermanReingold::new(150., 0).until(Termination::Iterations(50))))
+        });
+    }
+    group.finish();
+}
+
+fn lattice(c: &mut Criterion) {
+    let mut group = c.benchmark_group("grid");
+    for (name, graph) in graphs() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &graph, |b, graph| {
+            b.iter(|| graph.layout(Grid::default()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, fruchterman_reingold, lattice);
+criterion_main!(benches);
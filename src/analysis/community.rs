@@ -0,0 +1,265 @@
+//! Community detection, grouping densely connected nodes, e.g. to color them or to pull them
+//! together in a layout, see [`FruchtermanReingold::communities`].
+//!
+//! [`FruchtermanReingold::communities`]: crate::engines::fruchterman_reingold::FruchtermanReingold::communities
+use std::collections::BTreeMap;
+
+#[cfg(feature = "svg")]
+use crate::render::svg::{Style, PALETTE};
+use crate::{Float, Graph};
+
+/// An assignment of every node to one of `count` communities, numbered consecutively in the order
+/// of their first node.
+///
+/// As a [`Style`](crate::render::svg::Style), it colors the nodes by community and assigns them the
+/// css class `community-<index>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Partition {
+    labels: Vec<usize>,
+    count: usize,
+}
+
+impl Partition {
+    /// Create a partition from arbitrary labels, indexed by node. Nodes with equal labels form a
+    /// community.
+    pub fn new(labels: Vec<usize>) -> Self {
+        let mut numbers = BTreeMap::new();
+        let labels: Vec<usize> = labels
+            .into_iter()
+            .map(|label| {
+                let next = numbers.len();
+                *numbers.entry(label).or_insert(next)
+            })
+            .collect();
+        Self {
+            labels,
+            count: numbers.len(),
+        }
+    }
+
+    /// The community of the given node.
+    pub fn community(&self, node: usize) -> usize {
+        self.labels[node]
+    }
+
+    /// The community of each node.
+    pub fn labels(&self) -> &[usize] {
+        &self.labels
+    }
+
+    /// The number of communities.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The nodes of each community.
+    pub fn members(&self) -> Vec<Vec<usize>> {
+        let mut members = vec![Vec::new(); self.count];
+        for (node, &community) in self.labels.iter().enumerate() {
+            members[community].push(node);
+        }
+        members
+    }
+
+    /// The modularity of the partition of the given graph, i.e. the fraction of the edge weight
+    /// within communities minus the fraction expected if edges were placed at random. Ranges from
+    /// -0.5 to 1, higher values mean more pronounced communities.
+    pub fn modularity(&self, graph: &impl Graph) -> Float {
+        let weights = graph.edge_weights();
+        let mut internal = vec![0.; self.count];
+        let mut total = vec![0.; self.count];
+        let mut sum = 0.;
+        for (i, (u, v)) in graph.edges().enumerate() {
+            let w = weights.as_ref().map_or(1., |w| Float::max(w[i], 0.));
+            let (a, b) = (self.labels[u], self.labels[v]);
+            sum += w;
+            total[a] += w;
+            total[b] += w;
+            if a == b {
+                internal[a] += w;
+            }
+        }
+        if sum <= 0. {
+            return 0.;
+        }
+        internal.iter().zip(&total).map(|(&i, &t)| i / sum - (t / (2. * sum)).powi(2)).sum()
+    }
+}
+
+#[cfg(feature = "svg")]
+impl Style for Partition {
+    fn node_fill(&self, node: usize) -> Option<String> {
+        self.labels.get(node).map(|&c| PALETTE[c % PALETTE.len()].to_string())
+    }
+
+    fn node_class(&self, node: usize) -> Option<String> {
+        self.labels.get(node).map(|c| format!("community-{}", c))
+    }
+}
+
+/// Detect communities by the Louvain method of Blondel et al., maximizing the
+/// [modularity](Partition::modularity).
+///
+/// Nodes repeatedly move to the neighbouring community that increases the modularity the most,
+/// then each community is merged into a single node and the process repeats on the smaller graph.
+/// Edge weights are respected, negative weights count as zero. The result is deterministic.
+///
+/// ```
+/// use rs_plode::analysis::community::louvain;
+/// # #[cfg(feature = "svg")]
+/// use rs_plode::render::svg::RenderOptions;
+///
+/// // two triangles joined by the edge (2, 3)
+/// let graph: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)];
+/// let communities = louvain(&graph);
+/// assert_eq!(communities.labels(), &[0, 0, 0, 1, 1, 1]);
+/// # #[cfg(feature = "svg")]
+/// let options = RenderOptions::default().style(communities);
+/// ```
+pub fn louvain(graph: &impl Graph) -> Partition {
+    let weights = graph.edge_weights();
+    let mut level = Level::new(graph.nodes());
+    for (i, (u, v)) in graph.edges().enumerate() {
+        level.add(u, v, weights.as_ref().map_or(1., |w| Float::max(w[i], 0.)));
+    }
+    let mut labels: Vec<usize> = (0..graph.nodes()).collect();
+    loop {
+        let (communities, count) = level.move_nodes();
+        if count == level.loops.len() {
+            return Partition::new(labels);
+        }
+        for label in labels.iter_mut() {
+            *label = communities[*label];
+        }
+        level = level.aggregate(&communities, count);
+    }
+}
+
+/// A weighted graph of the Louvain method, whose nodes are the communities of the previous level.
+struct Level {
+    /// the neighbours and edge weights of each node, excluding self loops
+    neighbours: Vec<Vec<(usize, Float)>>,
+    /// the total weight of the self loops of each node
+    loops: Vec<Float>,
+}
+
+impl Level {
+    fn new(nodes: usize) -> Self {
+        Self {
+            neighbours: vec![Vec::new(); nodes],
+            loops: vec![0.; nodes],
+        }
+    }
+
+    fn add(&mut self, u: usize, v: usize, w: Float) {
+        if u == v {
+            self.loops[u] += w;
+        } else {
+            self.neighbours[u].push((v, w));
+            self.neighbours[v].push((u, w));
+        }
+    }
+
+    /// Move nodes between communities until the modularity stops increasing, returning the
+    /// community of each node, numbered consecutively, and the number of communities.
+    fn move_nodes(&self) -> (Vec<usize>, usize) {
+        let nodes = self.loops.len();
+        let degree: Vec<Float> = (0..nodes)
+            .map(|n| self.neighbours[n].iter().map(|&(_, w)| w).sum::<Float>() + 2. * self.loops[n])
+            .collect();
+        let twice_total: Float = degree.iter().sum();
+        let mut community: Vec<usize> = (0..nodes).collect();
+        if twice_total > 0. {
+            let mut total = degree.clone();
+            let mut links = vec![0.; nodes];
+            let mut touched = Vec::new();
+            for _ in 0..100 {
+                let mut moved = false;
+                for n in 0..nodes {
+                    let own = community[n];
+                    for &(m, w) in &self.neighbours[n] {
+                        let c = community[m];
+                        if links[c] == 0. {
+                            touched.push(c);
+                        }
+                        links[c] += w;
+                    }
+                    total[own] -= degree[n];
+                    let gain = |c: usize, links: &[Float]| links[c] - total[c] * degree[n] / twice_total;
+                    let mut best = (own, gain(own, &links));
+                    for &c in &touched {
+                        let g = gain(c, &links);
+                        if g > best.1 {
+                            best = (c, g);
+                        }
+                    }
+                    total[best.0] += degree[n];
+                    community[n] = best.0;
+                    moved |= best.0 != own;
+                    for c in touched.drain(..) {
+                        links[c] = 0.;
+                    }
+                }
+                if !moved {
+                    break;
+                }
+            }
+        }
+        let partition = Partition::new(community);
+        let count = partition.count();
+        (partition.labels, count)
+    }
+
+    /// Merge the nodes of each community into a single node.
+    fn aggregate(&self, communities: &[usize], count: usize) -> Self {
+        let mut level = Level::new(count);
+        for (n, neighbours) in self.neighbours.iter().enumerate() {
+            level.loops[communities[n]] += self.loops[n];
+            for &(m, w) in neighbours.iter().filter(|&&(m, _)| m > n) {
+                level.add(communities[n], communities[m], w);
+            }
+        }
+        for neighbours in level.neighbours.iter_mut() {
+            let mut merged: BTreeMap<usize, Float> = BTreeMap::new();
+            for &(m, w) in neighbours.iter() {
+                *merged.entry(m).or_insert(0.) += w;
+            }
+            *neighbours = merged.into_iter().collect();
+        }
+        level
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{louvain, Partition};
+    use crate::graph::AdjacencyGraph;
+    use crate::{Float, Weighted};
+
+    #[test]
+    fn detect_communities() {
+        // two complete graphs of five nodes, joined by a single edge
+        let mut edges = Vec::new();
+        for offset in [0, 5] {
+            for u in 0..5 {
+                edges.extend((u + 1..5).map(|v| (offset + u, offset + v)));
+            }
+        }
+        edges.push((4, 5));
+        let graph = AdjacencyGraph::from_edges(12, edges).unwrap();
+        let communities = louvain(&graph);
+        // the two isolated nodes form communities of their own
+        assert_eq!(communities.labels(), &[0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 2, 3]);
+        assert_eq!(communities.members()[1], vec![5, 6, 7, 8, 9]);
+        assert!((communities.modularity(&graph) - (20. / 21. - 2. * (21. as Float / 42.).powi(2))).abs() < 1e-6);
+        assert!(communities.modularity(&graph) > Partition::new(vec![0; 12]).modularity(&graph));
+
+        // a square, split along its heavy edges
+        let square = Weighted(vec![(0usize, 1usize, 5.), (1, 2, 1.), (2, 3, 5.), (3, 0, 1.)]);
+        assert_eq!(louvain(&square).labels(), &[0, 0, 1, 1]);
+
+        let partition = Partition::new(vec![7, 3, 7, 9]);
+        assert_eq!((partition.labels(), partition.count()), (&[0, 1, 0, 2][..], 3));
+        assert_eq!(louvain(&AdjacencyGraph::from_edges(0, vec![]).unwrap()).count(), 0);
+    }
+}
@@ -0,0 +1,118 @@
+use super::community::Partition;
+use super::incidences;
+use crate::Graph;
+
+/// The connected components of the graph, numbered in the order of their lowest node.
+///
+/// As a [`Partition`], the components can directly color a rendering.
+///
+/// ```
+/// use rs_plode::analysis::connected_components;
+///
+/// let graph: Vec<(usize, usize)> = vec![(0, 2), (1, 3), (4, 4)];
+/// let components = connected_components(&graph);
+/// assert_eq!(components.labels(), &[0, 1, 0, 1, 2]);
+/// ```
+pub fn connected_components(graph: &impl Graph) -> Partition {
+    let incidences = incidences(graph);
+    let mut labels = vec![usize::MAX; graph.nodes()];
+    let mut stack = Vec::new();
+    for root in 0..graph.nodes() {
+        if labels[root] != usize::MAX {
+            continue;
+        }
+        labels[root] = root;
+        stack.push(root);
+        while let Some(n) = stack.pop() {
+            for &(m, _) in &incidences[n] {
+                if labels[m] == usize::MAX {
+                    labels[m] = root;
+                    stack.push(m);
+                }
+            }
+        }
+    }
+    Partition::new(labels)
+}
+
+/// The indices, in the order of `edges()`, of the bridges of the graph, i.e. the edges whose
+/// removal disconnects their end nodes.
+///
+/// Self loops and parallel edges are never bridges.
+///
+/// ```
+/// use rs_plode::analysis::bridges;
+///
+/// // two triangles joined by the edge (2, 3)
+/// let graph: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)];
+/// assert_eq!(bridges(&graph), vec![3]);
+/// ```
+pub fn bridges(graph: &impl Graph) -> Vec<usize> {
+    let incidences = incidences(graph);
+    let nodes = graph.nodes();
+    // the depth first discovery time of each node and the earliest time reachable from its subtree
+    // by at most one back edge
+    let mut discovery = vec![usize::MAX; nodes];
+    let mut low = vec![0; nodes];
+    let mut bridges = Vec::new();
+    let mut time = 0;
+    // (node, edge to its parent, index of the next incidence to visit)
+    let mut stack: Vec<(usize, usize, usize)> = Vec::new();
+    for root in 0..nodes {
+        if discovery[root] != usize::MAX {
+            continue;
+        }
+        discovery[root] = time;
+        low[root] = time;
+        time += 1;
+        stack.push((root, usize::MAX, 0));
+        while let Some(&mut (n, parent_edge, ref mut next)) = stack.last_mut() {
+            if let Some(&(m, edge)) = incidences[n].get(*next) {
+                *next += 1;
+                if edge == parent_edge {
+                    continue;
+                }
+                if discovery[m] == usize::MAX {
+                    discovery[m] = time;
+                    low[m] = time;
+                    time += 1;
+                    stack.push((m, edge, 0));
+                } else {
+                    low[n] = usize::min(low[n], discovery[m]);
+                }
+            } else {
+                stack.pop();
+                if let Some(&(parent, _, _)) = stack.last() {
+                    low[parent] = usize::min(low[parent], low[n]);
+                    if low[n] > discovery[parent] {
+                        bridges.push(parent_edge);
+                    }
+                }
+            }
+        }
+    }
+    bridges.sort_unstable();
+    bridges
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bridges, connected_components};
+    use crate::graph::AdjacencyGraph;
+
+    #[test]
+    fn components_and_bridges() {
+        // a triangle with a pendant path, a doubled edge and an isolated node
+        let graph = AdjacencyGraph::from_edges(8, vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (5, 6), (6, 5)]).unwrap();
+        let components = connected_components(&graph);
+        assert_eq!(components.labels(), &[0, 0, 0, 0, 0, 1, 1, 2]);
+        assert_eq!(components.members()[1], vec![5, 6]);
+        assert_eq!(bridges(&graph), vec![3, 4]);
+
+        // every edge of a tree is a bridge, a self loop is not
+        let tree = AdjacencyGraph::from_edges(5, vec![(0, 1), (0, 2), (2, 3), (2, 4), (4, 4)]).unwrap();
+        assert_eq!(bridges(&tree), vec![0, 1, 2, 3]);
+        assert_eq!(connected_components(&tree).count(), 1);
+        assert_eq!(connected_components(&AdjacencyGraph::from_edges(0, vec![]).unwrap()).count(), 0);
+    }
+}
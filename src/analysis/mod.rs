@@ -0,0 +1,48 @@
+//! Structural analysis of graphs, e.g. to color or lay out nodes by their role in the graph.
+//!
+//! All functions ignore the edge direction and treat parallel edges and self loops like any other
+//! edge.
+pub mod community;
+mod components;
+mod traversal;
+
+pub use components::{bridges, connected_components};
+pub use traversal::{bfs_distances, dfs_order, ego_graph};
+
+use crate::Graph;
+
+/// The number of edges incident to each node, counting self loops twice.
+pub fn degrees(graph: &impl Graph) -> Vec<usize> {
+    let mut degrees = vec![0; graph.nodes()];
+    for (u, v) in graph.edges() {
+        degrees[u] += 1;
+        degrees[v] += 1;
+    }
+    degrees
+}
+
+/// The [`degrees`] of all nodes in descending order.
+///
+/// ```
+/// use rs_plode::analysis::degree_sequence;
+///
+/// let star: Vec<(usize, usize)> = vec![(0, 1), (0, 2), (0, 3)];
+/// assert_eq!(degree_sequence(&star), vec![3, 1, 1, 1]);
+/// ```
+pub fn degree_sequence(graph: &impl Graph) -> Vec<usize> {
+    let mut sequence = degrees(graph);
+    sequence.sort_unstable_by(|a, b| b.cmp(a));
+    sequence
+}
+
+/// The neighbours of each node, together with the index of the connecting edge.
+fn incidences(graph: &impl Graph) -> Vec<Vec<(usize, usize)>> {
+    let mut incidences = vec![Vec::new(); graph.nodes()];
+    for (i, (u, v)) in graph.edges().enumerate() {
+        incidences[u].push((v, i));
+        if u != v {
+            incidences[v].push((u, i));
+        }
+    }
+    incidences
+}
@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+
+use super::incidences;
+use crate::graph::SubGraphView;
+use crate::Graph;
+
+/// The number of edges on a shortest path from the source to each node, by breadth first search.
+/// Nodes unreachable from the source have no distance.
+///
+/// ```
+/// use rs_plode::analysis::bfs_distances;
+///
+/// let graph: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 3), (3, 0), (4, 4)];
+/// assert_eq!(bfs_distances(&graph, 0), vec![Some(0), Some(1), Some(2), Some(1), None]);
+/// ```
+pub fn bfs_distances(graph: &impl Graph, source: usize) -> Vec<Option<usize>> {
+    let incidences = incidences(graph);
+    let mut distances = vec![None; graph.nodes()];
+    let mut queue = VecDeque::new();
+    if source < graph.nodes() {
+        distances[source] = Some(0);
+        queue.push_back(source);
+    }
+    while let Some(n) = queue.pop_front() {
+        let next = distances[n].map(|d| d + 1);
+        for &(m, _) in &incidences[n] {
+            if distances[m].is_none() {
+                distances[m] = next;
+                queue.push_back(m);
+            }
+        }
+    }
+    distances
+}
+
+/// The nodes reachable from the root in depth first preorder, visiting neighbours in the order of
+/// their edges, together with the depth of each node in the search tree. Nodes unreachable from
+/// the root have no depth.
+///
+/// ```
+/// use rs_plode::analysis::dfs_order;
+///
+/// let graph: Vec<(usize, usize)> = vec![(0, 1), (0, 2), (1, 3), (2, 3)];
+/// let (order, depths) = dfs_order(&graph, 0);
+/// assert_eq!(order, vec![0, 1, 3, 2]);
+/// assert_eq!(depths, vec![Some(0), Some(1), Some(3), Some(2)]);
+/// ```
+pub fn dfs_order(graph: &impl Graph, root: usize) -> (Vec<usize>, Vec<Option<usize>>) {
+    let incidences = incidences(graph);
+    let mut depths = vec![None; graph.nodes()];
+    let mut order = Vec::new();
+    if root >= graph.nodes() {
+        return (order, depths);
+    }
+    depths[root] = Some(0);
+    order.push(root);
+    // (node, index of the next incidence to visit)
+    let mut stack = vec![(root, 0)];
+    while let Some((n, next)) = stack.last_mut() {
+        let Some(&(m, _)) = incidences[*n].get(*next) else {
+            stack.pop();
+            continue;
+        };
+        *next += 1;
+        if depths[m].is_none() {
+            depths[m] = Some(stack.len());
+            order.push(m);
+            stack.push((m, 0));
+        }
+    }
+    (order, depths)
+}
+
+/// The subgraph induced by all nodes within the given number of hops from the center, e.g. to lay
+/// out or render its local neighbourhood. Nodes are numbered in breadth first order, hence the
+/// center is node 0 of the subgraph; [`SubGraphView::original`] maps them back.
+///
+/// ```
+/// use rs_plode::analysis::ego_graph;
+/// use rs_plode::Graph;
+///
+/// let path: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 3), (3, 4)];
+/// let ego = ego_graph(&path, 2, 1).unwrap();
+/// assert_eq!(ego.original_nodes(), &[2, 1, 3]);
+/// assert_eq!(ego.edges().count(), 2);
+/// ```
+pub fn ego_graph(graph: &impl Graph, center: usize, hops: usize) -> Result<SubGraphView, String> {
+    if center >= graph.nodes() {
+        return Err(format!("Node {} does not exist, the graph has {} nodes", center, graph.nodes()));
+    }
+    let incidences = incidences(graph);
+    let mut distances = vec![None; graph.nodes()];
+    distances[center] = Some(0);
+    let mut order = vec![center];
+    let mut next = 0;
+    while let Some(&n) = order.get(next) {
+        next += 1;
+        let d = distances[n].unwrap_or(0);
+        if d == hops {
+            continue;
+        }
+        for &(m, _) in &incidences[n] {
+            if distances[m].is_none() {
+                distances[m] = Some(d + 1);
+                order.push(m);
+            }
+        }
+    }
+    SubGraphView::induced(graph, &order)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bfs_distances, dfs_order, ego_graph};
+    use crate::analysis::{degree_sequence, degrees};
+    use crate::graph::AdjacencyGraph;
+    use crate::Graph;
+
+    #[test]
+    fn traverse_from_root() {
+        // a path 0 - 1 - 2 - 3 with a shortcut from 0 to 2 and an isolated node
+        let graph = AdjacencyGraph::from_edges(5, vec![(0, 1), (1, 2), (2, 3), (2, 0)]).unwrap();
+        assert_eq!(bfs_distances(&graph, 3), vec![Some(2), Some(2), Some(1), Some(0), None]);
+        assert_eq!(bfs_distances(&graph, 4), vec![None, None, None, None, Some(0)]);
+        assert_eq!(bfs_distances(&graph, 5), vec![None; 5]);
+
+        let ego = ego_graph(&graph, 3, 1).unwrap();
+        assert_eq!(ego.original_nodes(), &[3, 2]);
+        assert_eq!(ego_graph(&graph, 3, 2).unwrap().original_nodes(), &[3, 2, 1, 0]);
+        assert_eq!(ego_graph(&graph, 3, 2).unwrap().edges().count(), 4);
+        assert_eq!(ego_graph(&graph, 4, 9).unwrap().original_nodes(), &[4]);
+        assert_eq!(ego_graph(&graph, 0, 0).unwrap().edges().count(), 0);
+        assert!(ego_graph(&graph, 5, 1).is_err());
+
+        let (order, depths) = dfs_order(&graph, 0);
+        assert_eq!(order, vec![0, 1, 2, 3]);
+        assert_eq!(depths, vec![Some(0), Some(1), Some(2), Some(3), None]);
+
+        assert_eq!(degrees(&graph), vec![2, 2, 3, 1, 0]);
+        assert_eq!(degree_sequence(&graph), vec![3, 2, 2, 1, 0]);
+    }
+}
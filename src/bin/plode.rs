@@ -0,0 +1,296 @@
+//! Command line interface of rs-plode, built with the `cli` feature.
+//!
+//! ```text
+//! rs-plode bench [--engine fr|grid|spiral|hexagonal] [--nodes 10,100,...] [--degree 3]
+//!                [--iterations 200] [--seed 0] [--format json|tsv]
+//! rs-plode run <pipeline.toml|pipeline.yaml>
+//! rs-plode layout <graph.csv|graph.dot|graph.graphml> --output out.svg [--output out.png ...]
+//!                 [--engine fr|grid|spiral|hexagonal] [--iterations 200] [--seed 0] [--size 800]
+//! ```
+//!
+//! The `bench` subcommand lays out random graphs of the given sizes and prints the time spent in
+//! each phase (init, repulsion, attraction, integrate, render) in milliseconds, one run per line.
+//! The force computations are separate, never inlined functions, such that profilers and
+//! flamegraphs attribute time to them.
+//!
+//! The `run` subcommand (requires the `config` feature) runs a layout pipeline read from a
+//! configuration file, see `rs_plode::pipeline::Pipeline`, and prints the written paths.
+//!
+//! The `layout` subcommand lays out a single edge list, DOT (requires the `dot` feature) or
+//! GraphML (requires the `graphml` feature) file and writes each output in the format given by its
+//! extension: `svg`, `png` (requires the `raster` feature) or `json`. The size is the width and
+//! height of the images in pixels.
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use rs_plode::engines::fruchterman_reingold::{FruchtermanReingold, PhaseTimings};
+use rs_plode::engines::lattice::{Grid, Hexagonal, Spiral};
+use rs_plode::engines::termination::Termination;
+use rs_plode::graph::AdjacencyGraph;
+use rs_plode::layout::scatter::ScatterLayout;
+use rs_plode::render::scene::RenderScene;
+use rs_plode::Graph;
+
+const USAGE: &str = "usage: rs-plode bench [--engine fr|grid|spiral|hexagonal] [--nodes 10,100,...] [--degree 3] [--iterations 200] [--seed 0] [--format json|tsv]
+       rs-plode run <pipeline.toml|pipeline.yaml>
+       rs-plode layout <graph.csv|graph.dot|graph.graphml> --output out.svg [--output out.png ...] [--engine fr|grid|spiral|hexagonal] [--iterations 200] [--seed 0] [--size 800]";
+
+struct Bench {
+    engine: String,
+    nodes: Vec<usize>,
+    degree: usize,
+    iterations: usize,
+    seed: u64,
+    format: String,
+}
+
+impl Bench {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut bench = Self {
+            engine: "fr".to_string(),
+            nodes: vec![10, 50, 100],
+            degree: 3,
+            iterations: 200,
+            seed: 0,
+            format: "json".to_string(),
+        };
+        while let Some(flag) = args.next() {
+            let value = args.next().ok_or_else(|| format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--engine" => bench.engine = value,
+                "--nodes" => bench.nodes = value.split(',').map(|v| number(v, &flag)).collect::<Result<_, _>>()?,
+                "--degree" => bench.degree = number(&value, &flag)?,
+                "--iterations" => bench.iterations = number(&value, &flag)?,
+                "--seed" => bench.seed = number(&value, &flag)?,
+                "--format" => bench.format = value,
+                _ => return Err(format!("Unknown option {}", flag)),
+            }
+        }
+        if !["fr", "grid", "spiral", "hexagonal"].contains(&bench.engine.as_str()) {
+            return Err(format!("Unknown engine {}", bench.engine));
+        }
+        if !["json", "tsv"].contains(&bench.format.as_str()) {
+            return Err(format!("Unknown format {}", bench.format));
+        }
+        Ok(bench)
+    }
+
+    /// A random graph with the given number of nodes and about `degree` edges per node.
+    fn graph(&self, nodes: usize) -> AdjacencyGraph {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let edges = (0..nodes * self.degree / 2)
+            .map(|_| (rng.gen_range(0..nodes), rng.gen_range(0..nodes)))
+            .filter(|(u, v)| u != v)
+            .collect::<Vec<_>>();
+        AdjacencyGraph::from_edges(nodes, edges).unwrap()
+    }
+
+    fn run(&self) {
+        if self.format == "tsv" {
+            println!("engine\tnodes\tedges\tinit\trepulsion\tattraction\tintegrate\trender");
+        }
+        for &nodes in &self.nodes {
+            let graph = self.graph(nodes);
+            let edges = graph.edges().count();
+            let (layout, timings) = layout(&self.engine, &graph, self.iterations, self.seed);
+            let render = render(&layout);
+            let mut phases = timings.phases().to_vec();
+            phases.push(("render", render));
+            let ms = |d: Duration| d.as_secs_f64() * 1000.;
+            match self.format.as_str() {
+                "tsv" => {
+                    let columns: Vec<String> = phases.iter().map(|(_, d)| ms(*d).to_string()).collect();
+                    println!("{}\t{}\t{}\t{}", self.engine, nodes, edges, columns.join("\t"));
+                }
+                _ => {
+                    let fields: Vec<String> = phases.iter().map(|(name, d)| format!("\"{}\": {}", name, ms(*d))).collect();
+                    println!(
+                        "{{\"engine\": \"{}\", \"nodes\": {}, \"edges\": {}, \"phases_ms\": {{{}}}}}",
+                        self.engine,
+                        nodes,
+                        edges,
+                        fields.join(", ")
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn layout<'a>(engine: &str, graph: &'a AdjacencyGraph, iterations: usize, seed: u64) -> (ScatterLayout<&'a AdjacencyGraph>, PhaseTimings) {
+    let placed = |layout: fn(&'a AdjacencyGraph) -> ScatterLayout<&'a AdjacencyGraph>| {
+        let start = Instant::now();
+        let layout = layout(graph);
+        let init = start.elapsed();
+        (layout, PhaseTimings { init, ..PhaseTimings::default() })
+    };
+    match engine {
+        "grid" => placed(|g| g.layout(Grid::default())),
+        "spiral" => placed(|g| g.layout(Spiral::default())),
+        "hexagonal" => placed(|g| g.layout(Hexagonal::default())),
+        _ => FruchtermanReingold::new(150., seed)
+            .until(Termination::Iterations(iterations))
+            .compute_timed(graph),
+    }
+}
+
+#[cfg(feature = "svg")]
+fn render(layout: &ScatterLayout<&AdjacencyGraph>) -> Duration {
+    use rs_plode::render::svg::{RenderOptions, RenderSVG};
+
+    let start = Instant::now();
+    let document = layout.clone().to_document(&RenderOptions::default()).unwrap();
+    std::hint::black_box(document.to_string());
+    start.elapsed()
+}
+
+#[cfg(not(feature = "svg"))]
+fn render(layout: &ScatterLayout<&AdjacencyGraph>) -> Duration {
+    use rs_plode::render::terminal::{BrailleCanvas, RenderTerminal};
+
+    let start = Instant::now();
+    let mut canvas = BrailleCanvas::new(80, 40);
+    layout.draw(&mut canvas).unwrap();
+    std::hint::black_box(canvas.to_string());
+    start.elapsed()
+}
+
+struct Layout {
+    input: PathBuf,
+    outputs: Vec<PathBuf>,
+    engine: String,
+    iterations: usize,
+    seed: u64,
+    size: u32,
+}
+
+impl Layout {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut layout = Self {
+            input: args.next().map(PathBuf::from).ok_or_else(|| USAGE.to_string())?,
+            outputs: Vec::new(),
+            engine: "fr".to_string(),
+            iterations: 200,
+            seed: 0,
+            size: 800,
+        };
+        while let Some(flag) = args.next() {
+            let value = args.next().ok_or_else(|| format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--output" | "-o" => layout.outputs.push(PathBuf::from(value)),
+                "--engine" => layout.engine = value,
+                "--iterations" => layout.iterations = number(&value, &flag)?,
+                "--seed" => layout.seed = number(&value, &flag)?,
+                "--size" => layout.size = number::<NonZeroU32>(&value, &flag)?.get(),
+                _ => return Err(format!("Unknown option {}", flag)),
+            }
+        }
+        if !["fr", "grid", "spiral", "hexagonal"].contains(&layout.engine.as_str()) {
+            return Err(format!("Unknown engine {}", layout.engine));
+        }
+        if layout.outputs.is_empty() {
+            return Err("Missing --output".to_string());
+        }
+        let formats = ["json", #[cfg(feature = "svg")] "svg", #[cfg(feature = "raster")] "png"];
+        if let Some(path) = layout.outputs.iter().find(|p| !formats.contains(&extension(p))) {
+            return Err(format!("Unsupported output format of {}, expected one of {}", path.display(), formats.join(", ")));
+        }
+        Ok(layout)
+    }
+
+    fn run(&self) -> Result<(), String> {
+        match extension(&self.input) {
+            #[cfg(feature = "dot")]
+            "dot" | "gv" => self.write(rs_plode::io::dot::DotGraph::read(&self.input)?),
+            #[cfg(feature = "graphml")]
+            "graphml" => self.write(rs_plode::io::graphml::GraphMLGraph::read(&self.input)?),
+            _ => self.write(rs_plode::io::edgelist::EdgeListGraph::read(&self.input)?),
+        }
+    }
+
+    fn write<G: Graph + Clone>(&self, graph: G) -> Result<(), String> {
+        let layout = match self.engine.as_str() {
+            "grid" => graph.layout(Grid::default()),
+            "spiral" => graph.layout(Spiral::default()),
+            "hexagonal" => graph.layout(Hexagonal::default()),
+            _ => graph.layout(FruchtermanReingold::new(150., self.seed).until(Termination::Iterations(self.iterations))),
+        };
+        for path in &self.outputs {
+            let failed = |e: std::io::Error| format!("Failed to write {}: {}", path.display(), e);
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent).map_err(failed)?;
+            }
+            match extension(path) {
+                "json" => std::fs::write(path, layout.to_json()?).map_err(failed)?,
+                #[cfg(feature = "svg")]
+                "svg" => {
+                    use rs_plode::render::svg::{RenderOptions, RenderSVG};
+
+                    let size = format!("{}px", self.size);
+                    let document = layout.clone().to_document(&RenderOptions::default().size(size.clone(), size))?;
+                    std::fs::write(path, document.to_string()).map_err(failed)?
+                }
+                #[cfg(feature = "raster")]
+                "png" => {
+                    use rs_plode::render::png::{RasterOptions, RenderPNG};
+
+                    let image = layout.clone().render_png(&RasterOptions::default().resolution(self.size, self.size))?;
+                    image.save_png(path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?
+                }
+                _ => unreachable!("formats are validated when parsing"),
+            }
+            println!("{}", path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Parse the value of the flag, rejecting values out of the range of the number type.
+fn number<T: FromStr>(value: &str, flag: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid number '{}' for {}", value, flag))
+}
+
+fn extension(path: &Path) -> &str {
+    path.extension().and_then(|e| e.to_str()).unwrap_or("")
+}
+
+#[cfg(feature = "config")]
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let config = args.next().ok_or_else(|| USAGE.to_string())?;
+    for path in rs_plode::pipeline::Pipeline::from_config(config)?.run()? {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "config"))]
+fn run(_: impl Iterator<Item = String>) -> Result<(), String> {
+    Err("rs-plode run requires the config feature".to_string())
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("bench") => Bench::parse(args).map(|bench| bench.run()),
+        Some("run") => run(args),
+        Some("layout") => Layout::parse(args).and_then(|layout| layout.run()),
+        Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
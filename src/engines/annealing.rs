@@ -0,0 +1,317 @@
+//! Layouts by simulated annealing, for small graphs where quality matters more than speed.
+//!
+//! Original paper: Davidson and Harel, Drawing Graphs Nicely Using Simulated Annealing,
+//! https://doi.org/10.1145/234535.234538
+use ndarray::{Array2, ArrayView2};
+use rand::{Rng, SeedableRng};
+
+use crate::graph::GraphCache;
+use crate::layout::scatter::{ScatterLayout, ScatterLayoutSequence};
+use crate::metrics::cross;
+use crate::{Engine, Float, Graph};
+
+use super::observer::{Control, Observer};
+use super::telemetry::Telemetry;
+use super::termination::{IterationState, Termination};
+use super::{EngineRng, Seeded};
+
+/// Implements the simulated annealing layout by Davidson and Harel.
+///
+/// Each stage (iteration) tries to move every node a number of times to a random point within a
+/// radius, which shrinks from stage to stage. Moves lowering the cost are always accepted, others
+/// with a probability of `exp(-increase / temperature)`. The temperature cools down geometrically.
+///
+/// The cost of a layout combines
+/// - the node distribution, `sum of k² / d²` over all pairs of nodes,
+/// - the edge lengths, `sum of d² / k²` over all edges,
+/// - and the edge crossings, see [`metrics::crossings`](crate::metrics::crossings),
+///
+/// each scaled by its weight. Nodes stay within a square frame of edge length `2 * sqrt(|V|) * k`.
+///
+/// ```
+/// use rs_plode::engines::annealing::DavidsonHarel;
+/// use rs_plode::metrics::crossings;
+/// use rs_plode::Graph;
+///
+/// let cube = vec![(0, 1), (1, 2), (2, 3), (3, 0), (4, 5), (5, 6), (6, 7), (7, 4), (0, 4), (1, 5), (2, 6), (3, 7)];
+/// let layout = cube.clone().layout(DavidsonHarel::new(100., 7));
+/// assert_eq!(crossings(&cube, layout.view()), 0);
+/// ```
+#[derive(Clone)]
+pub struct DavidsonHarel {
+    k: Float,
+    rng: EngineRng,
+    termination: Termination,
+    temperature: Float,
+    cooling: Float,
+    moves: usize,
+    distribution: Float,
+    edge_length: Float,
+    crossings: Float,
+}
+
+impl Default for DavidsonHarel {
+    fn default() -> Self {
+        Self::new(150., 0)
+    }
+}
+
+impl DavidsonHarel {
+    pub fn new(k: Float, seed: u64) -> Self {
+        Self {
+            k,
+            rng: EngineRng::seed_from_u64(seed),
+            termination: Termination::Iterations(30),
+            temperature: 1.,
+            cooling: 0.8,
+            moves: 30,
+            distribution: 1.,
+            edge_length: 1.,
+            crossings: 2.,
+        }
+    }
+
+    /// Set the policy deciding when to stop iterating. Defaults to 30 stages.
+    ///
+    /// The move radius shrinks linearly over the maximum iteration count of the policy, or over 30
+    /// stages if the policy has none. As downhill moves are accepted even when cold, displacement
+    /// and stress based policies may never be met, hence annealing stops after twice that many
+    /// stages regardless of the policy.
+    pub fn until(mut self, termination: Termination) -> Self {
+        self.termination = termination;
+        self
+    }
+
+    /// Set the initial temperature and the factor it is multiplied with after every stage. Defaults
+    /// to 1 and 0.8. The factor is clamped to `[0, 1]`.
+    pub fn temperature(mut self, initial: Float, cooling: Float) -> Self {
+        self.temperature = initial;
+        self.cooling = cooling.clamp(0., 1.);
+        self
+    }
+
+    /// Set the number of moves tried per node and stage. Defaults to 30.
+    pub fn moves(mut self, moves: usize) -> Self {
+        self.moves = moves;
+        self
+    }
+
+    /// Set the weights of the node distribution, edge length and crossing terms of the cost.
+    /// Defaults to 1, 1 and 2.
+    pub fn weights(mut self, distribution: Float, edge_length: Float, crossings: Float) -> Self {
+        self.distribution = distribution;
+        self.edge_length = edge_length;
+        self.crossings = crossings;
+        self
+    }
+
+    /// The cost of the V x 2 shaped positions, which the engine minimizes.
+    pub fn cost(&self, graph: &impl Graph, positions: ArrayView2<Float>) -> Float {
+        let edges: Vec<(usize, usize)> = graph.edges().filter(|&(u, v)| u != v).collect();
+        let mut distribution = 0.;
+        for u in 0..graph.nodes() {
+            for v in u + 1..graph.nodes() {
+                distribution += self.k * self.k / squared_distance(positions, u, v);
+            }
+        }
+        let edge_length: Float = edges.iter().map(|&(u, v)| squared_distance(positions, u, v)).sum::<Float>() / (self.k * self.k);
+        let crossings = crate::metrics::crossings(&edges, positions) as Float;
+        self.distribution * distribution + self.edge_length * edge_length + self.crossings * crossings
+    }
+
+    /// The part of the cost that depends on the position of the given node.
+    fn node_cost(&self, pos: ArrayView2<Float>, edges: &[(usize, usize)], incident: &[usize], node: usize) -> Float {
+        let distribution: Float = (0..pos.nrows())
+            .filter(|&n| n != node)
+            .map(|n| self.k * self.k / squared_distance(pos, node, n))
+            .sum();
+        let mut edge_length = 0.;
+        let mut crossings = 0;
+        for &e in incident {
+            let (u, v) = edges[e];
+            edge_length += squared_distance(pos, u, v) / (self.k * self.k);
+            crossings += edges.iter().filter(|&&f| cross(pos, (u, v), f)).count();
+        }
+        self.distribution * distribution + self.edge_length * edge_length + self.crossings * crossings as Float
+    }
+
+    fn initial_positions(&mut self, graph: &impl Graph) -> Array2<Float> {
+        let half = Float::sqrt(graph.nodes() as Float) * self.k / 2.;
+        Array2::from_shape_simple_fn((graph.nodes(), 2), || self.rng.gen_range(-half..=half))
+    }
+
+    /// A uniformly distributed point in the unit disk, by rejection sampling to avoid trigonometric
+    /// functions, whose precision depends on the platform.
+    fn random_offset(&mut self) -> (Float, Float) {
+        loop {
+            let (x, y) = (self.rng.gen_range::<Float, _>(-1. ..1.), self.rng.gen_range::<Float, _>(-1. ..1.));
+            if x * x + y * y <= 1. {
+                return (x, y);
+            }
+        }
+    }
+
+    /// Anneal the positions until the termination policy or the observer says stop.
+    fn run(&mut self, graph: &GraphCache, mut pos: Array2<Float>, mut observer: impl Observer) -> Array2<Float> {
+        let nodes = graph.nodes();
+        let edges: Vec<(usize, usize)> = graph.edges().filter(|&(u, v)| u != v).collect();
+        let mut incident: Vec<Vec<usize>> = vec![Vec::new(); nodes];
+        for (e, &(u, v)) in edges.iter().enumerate() {
+            incident[u].push(e);
+            incident[v].push(e);
+        }
+        let frame = Float::sqrt(nodes as Float) * self.k;
+        let horizon = self.termination.max_iterations().unwrap_or(30).max(1);
+        let mut temperature = self.temperature;
+        let mut previous_stress = None;
+        for stage in 1..=2 * horizon {
+            // shrink from half the initial square down to a tenth of the edge length
+            let progress = usize::min(stage - 1, horizon) as Float / horizon as Float;
+            let radius = frame / 2. + (self.k / 10. - frame / 2.) * progress;
+            let start = pos.clone();
+            for _ in 0..self.moves {
+                for node in 0..nodes {
+                    let old = (pos[[node, 0]], pos[[node, 1]]);
+                    let before = self.node_cost(pos.view(), &edges, &incident[node], node);
+                    let (dx, dy) = self.random_offset();
+                    pos[[node, 0]] = (old.0 + radius * dx).clamp(-frame, frame);
+                    pos[[node, 1]] = (old.1 + radius * dy).clamp(-frame, frame);
+                    let increase = self.node_cost(pos.view(), &edges, &incident[node], node) - before;
+                    if increase > 0. && self.rng.gen::<Float>() >= exp(-increase / temperature) {
+                        pos[[node, 0]] = old.0;
+                        pos[[node, 1]] = old.1;
+                    }
+                }
+            }
+
+            let max_displacement = (0..nodes)
+                .map(|n| squared_distance_between(start.view(), pos.view(), n).sqrt())
+                .fold(0., Float::max);
+            let stress = self.cost(graph, pos.view());
+            let state = IterationState {
+                iteration: stage,
+                temperature,
+                max_displacement,
+                stress,
+                previous_stress,
+            };
+            previous_stress = Some(stress);
+            temperature *= self.cooling;
+            if observer.observe(pos.view(), &state) == Control::Stop || self.termination.should_stop(&state) {
+                break;
+            }
+        }
+        pos
+    }
+}
+
+/// `exp(x)` for `x <= 0` by repeated squaring, as the precision of `Float::exp` depends on the
+/// platform.
+fn exp(x: Float) -> Float {
+    let mut y = Float::max(1. + x / 65536., 0.);
+    for _ in 0..16 {
+        y *= y;
+    }
+    y
+}
+
+/// The squared distance of two nodes, at least a tiny bit above 0 to keep the cost finite.
+fn squared_distance(pos: ArrayView2<Float>, u: usize, v: usize) -> Float {
+    let (dx, dy) = (pos[[u, 0]] - pos[[v, 0]], pos[[u, 1]] - pos[[v, 1]]);
+    Float::max(dx * dx + dy * dy, 1e-6)
+}
+
+/// The squared distance of a node between two sets of positions.
+fn squared_distance_between(a: ArrayView2<Float>, b: ArrayView2<Float>, n: usize) -> Float {
+    (a[[n, 0]] - b[[n, 0]]).powi(2) + (a[[n, 1]] - b[[n, 1]]).powi(2)
+}
+
+impl Seeded for DavidsonHarel {
+    fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = EngineRng::seed_from_u64(seed);
+        self
+    }
+}
+
+impl Engine for DavidsonHarel {
+    type Layout<G: Graph> = ScatterLayout<G>;
+    type LayoutSequence<G: Graph> = ScatterLayoutSequence<G>;
+
+    fn compute<G: Graph>(self, graph: G) -> Self::Layout<G> {
+        self.compute_observed(graph, |_: ArrayView2<Float>, _: &IterationState| Control::Continue)
+    }
+
+    fn compute_observed<G: Graph, O: Observer>(mut self, graph: G, observer: O) -> Self::Layout<G> {
+        let cache = GraphCache::for_layout(&graph);
+        let pos = self.initial_positions(&cache);
+        let pos = self.run(&cache, pos, observer);
+        ScatterLayout::new(graph, pos).unwrap()
+    }
+
+    fn animate<G: Graph>(mut self, graph: G) -> Self::LayoutSequence<G> {
+        let cache = GraphCache::for_layout(&graph);
+        let pos = self.initial_positions(&cache);
+        let mut sequence = vec![pos.clone()];
+        let mut telemetry = Telemetry::default();
+        self.run(&cache, pos, |frame: ArrayView2<Float>, state: &IterationState| {
+            sequence.push(frame.to_owned());
+            telemetry.push(*state);
+            Control::Continue
+        });
+        ScatterLayoutSequence::new(graph, sequence).unwrap().with_telemetry(telemetry)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::{arr2, ArrayView2};
+
+    use super::DavidsonHarel;
+    use crate::engines::observer::Control;
+    use crate::engines::termination::{IterationState, Termination};
+    use crate::graph::generators::cycle;
+    use crate::metrics::crossings;
+    use crate::{Engine, Float, Graph};
+
+    #[test]
+    fn cost_terms() {
+        let engine = DavidsonHarel::new(1., 0);
+        let square = vec![(0usize, 1usize), (1, 2), (2, 3), (3, 0)];
+        let pos = arr2(&[[0., 0.], [1., 0.], [1., 1.], [0., 1.]]);
+        // four sides and two diagonals, four unit edges
+        assert!((engine.cost(&square, pos.view()) - (4. + 2. * 0.5 + 4.)).abs() < 1e-5);
+        // swapping two corners makes two edges cross
+        let twisted = arr2(&[[0., 0.], [1., 1.], [1., 0.], [0., 1.]]);
+        let lengths = 2. * 2. + 2. * 1.;
+        assert!((engine.cost(&square, twisted.view()) - (4. + 2. * 0.5 + lengths + 2.)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn anneal_cycle() {
+        let graph = cycle(8);
+        let mut stages = Vec::new();
+        let layout = DavidsonHarel::new(50., 3).until(Termination::Iterations(20)).compute_observed(
+            graph.clone(),
+            |_: ArrayView2<Float>, state: &IterationState| {
+                stages.push(state.stress);
+                Control::Continue
+            },
+        );
+        assert_eq!(stages.len(), 20);
+        assert!(stages.last().unwrap() < &stages[0]);
+        assert_eq!(crossings(&graph, layout.view()), 0);
+        assert!(layout.view().iter().all(|p| p.is_finite()));
+
+        let sequence = graph.clone().animate(DavidsonHarel::new(50., 3).until(Termination::Iterations(5)));
+        assert_eq!(sequence.frames(), 6);
+        assert_eq!(graph.clone().layout(DavidsonHarel::new(50., 3)).view(), graph.layout(DavidsonHarel::new(50., 3)).view());
+
+        // policies that are never met stop after twice the default 30 stages
+        let mut stages = 0;
+        cycle(4).layout_observed(DavidsonHarel::new(50., 3).until(Termination::MaxDisplacement(-1.)), |_: ArrayView2<Float>, _: &IterationState| {
+            stages += 1;
+            Control::Continue
+        });
+        assert_eq!(stages, 60);
+    }
+}
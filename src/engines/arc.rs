@@ -0,0 +1,192 @@
+//! Arc diagrams, placing all nodes on a horizontal line.
+//!
+//! Render them with [`RenderOptions::arc_edges`](crate::render::svg::RenderOptions::arc_edges) to
+//! draw the edges as semicircles instead of straight lines along the axis.
+use std::collections::VecDeque;
+
+use ndarray::Array2;
+
+use crate::{Float, Graph};
+
+use super::Placement;
+
+/// The order of the nodes along the line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Order {
+    /// By node index.
+    Index,
+    /// By breadth first search from a node of lowest degree per connected component, visiting
+    /// neighbours by increasing degree (Cuthill–McKee), which keeps neighbours close together.
+    BreadthFirst,
+    /// Starting from the breadth first order, repeatedly sort the nodes by the mean position of
+    /// their neighbours, keeping the order with the shortest total arc length.
+    #[default]
+    Barycenter,
+}
+
+/// Places the nodes on the x axis, `k` apart, in the given [`Order`].
+///
+/// This is a deterministic [`Placement`].
+///
+/// ```
+/// use rs_plode::engines::arc::{ArcDiagram, Order};
+/// # #[cfg(feature = "svg")]
+/// use rs_plode::render::svg::RenderOptions;
+/// use rs_plode::Graph;
+///
+/// let graph: Vec<(usize, usize)> = vec![(0, 3), (3, 1), (1, 4), (4, 2)];
+/// let layout = graph.layout(ArcDiagram::new().spacing(10.).order(Order::BreadthFirst));
+/// // the path is laid out in sequence
+/// assert_eq!(layout.coord(0).x(), 0.);
+/// assert_eq!(layout.coord(3).x(), 10.);
+/// assert_eq!(layout.coord(2).x(), 40.);
+/// # #[cfg(feature = "svg")]
+/// let options = RenderOptions::default().arc_edges(true);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArcDiagram {
+    k: Float,
+    order: Order,
+    sweeps: usize,
+}
+
+impl Default for ArcDiagram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArcDiagram {
+    pub fn new() -> Self {
+        Self {
+            k: 150.,
+            order: Order::default(),
+            sweeps: 8,
+        }
+    }
+
+    /// Set the distance between adjacent nodes.
+    pub fn spacing(mut self, k: Float) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// Set the order of the nodes. Defaults to [`Order::Barycenter`].
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Set the number of sweeps of the [`Order::Barycenter`] heuristic.
+    pub fn sweeps(mut self, sweeps: usize) -> Self {
+        self.sweeps = sweeps;
+        self
+    }
+
+    /// The nodes in the order along the line.
+    fn sequence(&self, neighbours: &[Vec<usize>]) -> Vec<usize> {
+        match self.order {
+            Order::Index => (0..neighbours.len()).collect(),
+            Order::BreadthFirst => breadth_first(neighbours),
+            Order::Barycenter => {
+                let mut sequence = breadth_first(neighbours);
+                let mut index = positions(&sequence);
+                let mut best = (arc_length(neighbours, &index), sequence.clone());
+                for _ in 0..self.sweeps {
+                    let key = |n: usize| match neighbours[n].len() {
+                        0 => index[n] as Float,
+                        degree => neighbours[n].iter().map(|&m| index[m] as Float).sum::<Float>() / degree as Float,
+                    };
+                    let mut keyed: Vec<(Float, usize)> = sequence.iter().map(|&n| (key(n), n)).collect();
+                    keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(index[a.1].cmp(&index[b.1])));
+                    sequence = keyed.into_iter().map(|(_, n)| n).collect();
+                    index = positions(&sequence);
+                    let length = arc_length(neighbours, &index);
+                    if length < best.0 {
+                        best = (length, sequence.clone());
+                    }
+                }
+                best.1
+            }
+        }
+    }
+}
+
+/// The Cuthill–McKee order, see [`Order::BreadthFirst`].
+fn breadth_first(neighbours: &[Vec<usize>]) -> Vec<usize> {
+    let mut roots: Vec<usize> = (0..neighbours.len()).collect();
+    roots.sort_by_key(|&n| neighbours[n].len());
+    let mut visited = vec![false; neighbours.len()];
+    let mut sequence = Vec::with_capacity(neighbours.len());
+    let mut queue = VecDeque::new();
+    for root in roots {
+        if std::mem::replace(&mut visited[root], true) {
+            continue;
+        }
+        queue.push_back(root);
+        while let Some(n) = queue.pop_front() {
+            sequence.push(n);
+            let mut next: Vec<usize> = neighbours[n].iter().copied().filter(|&m| !visited[m]).collect();
+            next.sort_by_key(|&m| neighbours[m].len());
+            for m in next {
+                if !std::mem::replace(&mut visited[m], true) {
+                    queue.push_back(m);
+                }
+            }
+        }
+    }
+    sequence
+}
+
+/// The position of each node in the sequence.
+fn positions(sequence: &[usize]) -> Vec<usize> {
+    let mut index = vec![0; sequence.len()];
+    for (i, &n) in sequence.iter().enumerate() {
+        index[n] = i;
+    }
+    index
+}
+
+/// The total distance of all neighbours along the line, counting every edge twice.
+fn arc_length(neighbours: &[Vec<usize>], index: &[usize]) -> usize {
+    (0..neighbours.len()).flat_map(|n| neighbours[n].iter().map(move |&m| index[n].abs_diff(index[m]))).sum()
+}
+
+impl Placement for ArcDiagram {
+    fn place(&self, graph: &impl Graph) -> Array2<Float> {
+        let mut neighbours = vec![Vec::new(); graph.nodes()];
+        for (u, v) in graph.edges().filter(|&(u, v)| u != v) {
+            neighbours[u].push(v);
+            neighbours[v].push(u);
+        }
+        let index = positions(&self.sequence(&neighbours));
+        Array2::from_shape_fn((graph.nodes(), 2), |(n, d)| if d == 0 { index[n] as Float * self.k } else { 0. })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{arc_length, positions, ArcDiagram, Order};
+    use crate::engines::Placement;
+    use crate::graph::AdjacencyGraph;
+    use crate::{Float, Graph};
+
+    #[test]
+    fn orders_shorten_arcs() {
+        // a path, labeled in a scrambled order, plus an isolated node
+        let graph = AdjacencyGraph::from_edges(7, vec![(0, 4), (4, 2), (2, 5), (5, 1), (1, 3)]).unwrap();
+        let length = |order: Order| {
+            let pos = ArcDiagram::new().spacing(1.).order(order).place(&graph);
+            assert!(pos.column(1).iter().all(|&y| y == 0.));
+            graph.edges().map(|(u, v)| (pos[[u, 0]] - pos[[v, 0]]).abs()).sum::<Float>()
+        };
+        assert_eq!(length(Order::Index), 2. + 4. + 3. + 4. + 2.);
+        assert_eq!(length(Order::BreadthFirst), 5.);
+        assert_eq!(length(Order::Barycenter), 5.);
+
+        // a path 0 - 1 - 2 - 3
+        let neighbours = vec![vec![1], vec![0, 2], vec![1, 3], vec![2]];
+        assert_eq!(arc_length(&neighbours, &positions(&[0, 1, 2, 3])), 6);
+        assert_eq!(arc_length(&neighbours, &positions(&[0, 2, 1, 3])), 10);
+    }
+}
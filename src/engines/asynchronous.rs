@@ -0,0 +1,264 @@
+//! Computing layouts without blocking an async runtime.
+//!
+//! The engine runs on a pool of worker threads, while the returned [`LayoutTask`] is a future that
+//! any executor can await, e.g. in a web service that must not block its runtime for the duration
+//! of a layout. Only the standard library is used, hence no executor is required or preferred.
+//!
+//! The pool has one worker per available core, spawned on first use and reused afterwards.
+//! Layouts requested while all workers are busy wait for a free one, such that a burst of requests
+//! neither spawns a thread per request nor oversubscribes the cores.
+//!
+//! The module is not available on `wasm32-unknown-unknown`, which has no threads; compute layouts
+//! there with [`Engine::compute`] or step by step, e.g. with
+//! [`FruchtermanReingold::simulation`](super::fruchterman_reingold::FruchtermanReingold::simulation).
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use ndarray::ArrayView2;
+
+use super::observer::{CancellationToken, Control, Observer};
+use super::termination::IterationState;
+use crate::{Engine, Float, Graph};
+
+/// A layout computation queued for the worker threads.
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The queue of the worker threads, which are spawned on first use.
+fn workers() -> &'static Sender<Job> {
+    static QUEUE: OnceLock<Sender<Job>> = OnceLock::new();
+    QUEUE.get_or_init(|| pool(thread::available_parallelism().map_or(1, NonZeroUsize::get)))
+}
+
+/// Spawn the given number of worker threads, taking jobs from the returned queue until it is
+/// dropped.
+fn pool(workers: usize) -> Sender<Job> {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    for n in 0..workers {
+        let receiver = receiver.clone();
+        thread::Builder::new()
+            .name(format!("rs-plode-worker-{}", n))
+            .spawn(move || loop {
+                // the lock is released before running the job, such that idle workers can take
+                // the next one
+                let job = receiver.lock().unwrap().recv();
+                let Ok(job) = job else { break };
+                job();
+            })
+            .expect("Failed to spawn a layout worker thread");
+    }
+    sender
+}
+
+/// The state shared between a [`LayoutTask`] and the worker computing it.
+struct Shared<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// A layout being computed on a worker thread, which resolves to the layout once done.
+///
+/// Cancelling the task, or dropping it before it completed, stops the engine after its current
+/// iteration, such that the worker does not keep computing a layout nobody awaits. A cancelled
+/// task resolves to the layout reached so far. Panics of the engine are resumed by the awaiting
+/// task.
+pub struct LayoutTask<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+    token: CancellationToken,
+}
+
+impl<T> LayoutTask<T> {
+    /// Stop the engine after its current iteration.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// A token cancelling the task, e.g. to be handed to another task.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Whether the layout is done, such that awaiting it does not suspend.
+    pub fn is_finished(&self) -> bool {
+        self.shared.lock().unwrap().result.is_some()
+    }
+}
+
+impl<T> Future for LayoutTask<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.result.take() {
+            Some(Ok(layout)) => Poll::Ready(layout),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for LayoutTask<T> {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+/// Engines that compute layouts on the worker threads, implemented for all engines that can be
+/// sent there.
+///
+/// ```
+/// use rs_plode::engines::asynchronous::AsyncEngine;
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+///
+/// async fn handle_request(edges: Vec<(usize, usize)>) -> String {
+///     let layout = FruchtermanReingold::default().compute_async(edges).await;
+///     format!("{:?}", layout.bbox())
+/// }
+/// ```
+pub trait AsyncEngine: Engine + Send + 'static {
+    /// Compute the layout on a worker thread.
+    fn compute_async<G>(self, graph: G) -> LayoutTask<Self::Layout<G>>
+    where
+        G: Graph + Send + 'static,
+        Self::Layout<G>: Send + 'static,
+    {
+        self.compute_async_observed(graph, |_: ArrayView2<Float>, _: &IterationState| Control::Continue)
+    }
+
+    /// Compute the layout on a worker thread, notifying the observer about every iteration there.
+    fn compute_async_observed<G, O>(self, graph: G, mut observer: O) -> LayoutTask<Self::Layout<G>>
+    where
+        G: Graph + Send + 'static,
+        O: Observer + Send + 'static,
+        Self::Layout<G>: Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(Shared { result: None, waker: None }));
+        let token = CancellationToken::new();
+        let (done, mut cancelled) = (shared.clone(), token.clone());
+        let job: Job = Box::new(move || {
+            let observer = |positions: ArrayView2<Float>, state: &IterationState| {
+                match observer.observe(positions, state) {
+                    Control::Stop => Control::Stop,
+                    Control::Continue => cancelled.observe(positions, state),
+                }
+            };
+            let result = panic::catch_unwind(AssertUnwindSafe(|| self.compute_observed(graph, observer)));
+            let mut shared = done.lock().unwrap();
+            shared.result = Some(result);
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        });
+        workers().send(job).expect("The layout workers are gone");
+        LayoutTask { shared, token }
+    }
+}
+
+impl<E: Engine + Send + 'static> AsyncEngine for E {}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::future::Future;
+    use std::sync::mpsc::channel;
+    use std::sync::{Arc, Barrier};
+    use std::task::{Context, Poll, Wake};
+    use std::thread::{self, Thread};
+    use std::time::Duration;
+
+    use ndarray::ArrayView2;
+
+    use super::{pool, AsyncEngine};
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::observer::Control;
+    use crate::engines::termination::{IterationState, Termination};
+    use crate::graph::generators::grid;
+    use crate::{Float, Graph};
+
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// A minimal executor, parking the thread until the future is woken.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Arc::new(Unpark(thread::current())).into();
+        let mut context = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn await_or_cancel_layouts() {
+        let engine = || FruchtermanReingold::default().until(Termination::Iterations(20));
+        let expected = grid(3, 3).layout(engine());
+        let layout = block_on(engine().compute_async(grid(3, 3)));
+        assert_eq!(layout.view(), expected.view());
+
+        // cancelled while the observer holds the engine after its first iteration
+        let (sender, receiver) = channel();
+        let (resume, paused) = channel::<()>();
+        let task = engine().compute_async_observed(grid(3, 3), move |_: ArrayView2<Float>, state: &IterationState| {
+            sender.send(state.iteration).unwrap();
+            paused.recv().unwrap();
+            Control::Continue
+        });
+        assert_eq!(receiver.recv(), Ok(1));
+        assert!(!task.is_finished());
+        task.cancel();
+        resume.send(()).unwrap();
+        let layout = block_on(task);
+        assert_eq!(layout.graph().nodes(), 9);
+        assert_eq!(receiver.iter().count(), 0);
+    }
+
+    #[test]
+    fn reuse_bounded_workers() {
+        let (sender, receiver) = channel();
+        let tasks: Vec<_> = (0..20)
+            .map(|_| {
+                let sender = sender.clone();
+                let engine = FruchtermanReingold::default().until(Termination::Iterations(2));
+                engine.compute_async_observed(grid(2, 2), move |_: ArrayView2<Float>, _: &IterationState| {
+                    sender.send(thread::current().id()).unwrap();
+                    Control::Continue
+                })
+            })
+            .collect();
+        drop(sender);
+        tasks.into_iter().for_each(|task| drop(block_on(task)));
+        let workers: HashSet<_> = receiver.iter().collect();
+        assert!(workers.len() <= thread::available_parallelism().map_or(1, |n| n.get()));
+    }
+
+    #[test]
+    fn run_jobs_concurrently() {
+        // both jobs wait for each other, which only returns if the workers run them at once
+        let workers = pool(2);
+        let barrier = Arc::new(Barrier::new(2));
+        let (sender, receiver) = channel();
+        for _ in 0..2 {
+            let (barrier, sender) = (barrier.clone(), sender.clone());
+            workers.send(Box::new(move || sender.send(barrier.wait().is_leader()).unwrap())).unwrap();
+        }
+        let met = (0..2).map(|_| receiver.recv_timeout(Duration::from_secs(10)));
+        assert_eq!(met.filter(Result::is_ok).count(), 2);
+    }
+}
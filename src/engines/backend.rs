@@ -0,0 +1,77 @@
+use ndarray::{Array2, ArrayView2};
+
+use super::repulsion::{blocked, dense};
+use crate::Float;
+
+/// How force directed engines compute the repulsion between all pairs of nodes, which dominates
+/// their run time.
+///
+/// All CPU backends compute bit-identical forces, hence the same seed yields the same layout with
+/// every one of them, and layouts do not depend on the CPU backend they were computed with. The
+/// GPU backend computes in single precision on the device instead, such that its layouts differ
+/// slightly from the others and between devices.
+///
+/// ```
+/// use rs_plode::engines::backend::Backend;
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::graph::generators::grid;
+/// use rs_plode::Graph;
+///
+/// let blocked = grid(4, 4).layout(FruchtermanReingold::default().backend(Backend::Blocked));
+/// let dense = grid(4, 4).layout(FruchtermanReingold::default().backend(Backend::Dense));
+/// assert_eq!(blocked.view(), dense.view());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Backend {
+    /// The blocked kernel for graphs of up to [`Backend::BLOCKED_NODES`] nodes, the dense one
+    /// beyond.
+    #[default]
+    Auto,
+    /// Whole array operations per node, allocating temporary arrays of all nodes.
+    Dense,
+    /// Walk the positions in cache sized tiles without allocating temporary arrays. Only 2D and 3D
+    /// layouts have a blocked kernel, others use the dense one.
+    Blocked,
+    /// Compute the forces in a compute shader on the GPU, for layouts of up to 4 dimensions.
+    /// Without a GPU adapter, or for layouts of more dimensions, it falls back to
+    /// [`Backend::Auto`]. Requires the `gpu` feature.
+    #[cfg(feature = "gpu")]
+    Gpu,
+}
+
+impl Backend {
+    /// The largest graphs for which [`Backend::Auto`] chooses the blocked kernel.
+    pub const BLOCKED_NODES: usize = 5000;
+
+    /// Whether [`Backend::Gpu`] found a GPU adapter to compute the forces with, instead of falling
+    /// back to [`Backend::Auto`].
+    #[cfg(feature = "gpu")]
+    pub fn gpu_available() -> bool {
+        super::gpu::is_available()
+    }
+
+    /// The V x D shaped repulsive displacements of the nodes at the given positions, optionally
+    /// scaled by the masses of the nodes.
+    pub(crate) fn repulsive_force(&self, positions: ArrayView2<Float>, k: Float, masses: Option<&[Float]>) -> Array2<Float> {
+        #[cfg(feature = "gpu")]
+        if let Backend::Gpu = self {
+            if let Some(forces) = super::gpu::repulsion(positions, k, masses) {
+                return forces;
+            }
+        }
+        let (nodes, dimensions) = positions.dim();
+        let blocks = match self {
+            Backend::Auto => nodes <= Self::BLOCKED_NODES,
+            #[cfg(feature = "gpu")]
+            Backend::Gpu => nodes <= Self::BLOCKED_NODES,
+            Backend::Dense => false,
+            Backend::Blocked => true,
+        };
+        match dimensions {
+            2 if blocks => blocked::<2>(positions, k, masses),
+            3 if blocks => blocked::<3>(positions, k, masses),
+            _ => dense(positions, k, masses),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Two-layer layouts of bipartite graphs, e.g. users and the items they rated.
+use std::collections::VecDeque;
+
+use ndarray::Array2;
+
+use crate::{Float, Graph};
+
+use super::Placement;
+
+/// How the order of a row is derived from the positions of the neighbours in the other row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Heuristic {
+    /// Sort by the mean position of the neighbours.
+    Barycenter,
+    /// Sort by the median position of the neighbours, which is less sensitive to outliers.
+    Median,
+}
+
+/// Split the nodes into two sides, such that every edge connects both sides. The first node of
+/// each connected component goes to the top side (`false`).
+///
+/// Returns `None` if the graph is not bipartite, i.e. contains a cycle of odd length.
+pub fn bipartition(graph: &impl Graph) -> Option<Vec<bool>> {
+    let (sides, bipartite) = breadth_first_sides(graph);
+    bipartite.then_some(sides)
+}
+
+fn neighbours(graph: &impl Graph) -> Vec<Vec<usize>> {
+    let mut neighbours = vec![Vec::new(); graph.nodes()];
+    for (u, v) in graph.edges() {
+        neighbours[u].push(v);
+        neighbours[v].push(u);
+    }
+    neighbours
+}
+
+/// Places the nodes in two parallel rows and orders both rows to reduce the number of edge
+/// crossings between them.
+///
+/// The rows are found automatically with [`bipartition`], unless given explicitly with
+/// [`partition`](Bipartite::partition). Graphs that are not bipartite are split by a breadth first
+/// search nonetheless, edges within a row are then drawn along the row and ignored by the ordering.
+///
+/// The rows are ordered by alternating sweeps of the [`Heuristic`], keeping the order with the
+/// fewest crossings. This is a deterministic [`Placement`].
+///
+/// ```
+/// use rs_plode::engines::bipartite::Bipartite;
+/// use rs_plode::metrics::crossings;
+/// use rs_plode::Graph;
+///
+/// // users 0, 1 and 2 rated items 3, 4 and 5
+/// let ratings: Vec<(usize, usize)> = vec![(0, 5), (1, 4), (2, 3), (0, 4)];
+/// let layout = ratings.clone().layout(Bipartite::new());
+/// assert_eq!(crossings(&ratings, layout.view()), 0);
+/// assert_eq!(layout.coord(0).y(), layout.coord(1).y());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Bipartite {
+    top: Option<Vec<usize>>,
+    heuristic: Heuristic,
+    k: Float,
+    separation: Float,
+    sweeps: usize,
+}
+
+impl Default for Bipartite {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bipartite {
+    /// Create a layout detecting the rows automatically.
+    pub fn new() -> Self {
+        Self {
+            top: None,
+            heuristic: Heuristic::Barycenter,
+            k: 150.,
+            separation: 300.,
+            sweeps: 4,
+        }
+    }
+
+    /// Place the given nodes in the top row, and all others in the bottom row.
+    pub fn partition(mut self, top: Vec<usize>) -> Self {
+        self.top = Some(top);
+        self
+    }
+
+    /// Set the heuristic ordering the rows. Defaults to [`Heuristic::Barycenter`].
+    pub fn heuristic(mut self, heuristic: Heuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// Set the distance between adjacent nodes within a row.
+    pub fn spacing(mut self, k: Float) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// Set the distance between the two rows.
+    pub fn separation(mut self, separation: Float) -> Self {
+        self.separation = separation;
+        self
+    }
+
+    /// Set the number of ordering sweeps, each reordering both rows. Zero keeps the node order.
+    pub fn sweeps(mut self, sweeps: usize) -> Self {
+        self.sweeps = sweeps;
+        self
+    }
+
+    /// The side of each node, `true` for the bottom row.
+    fn sides(&self, graph: &impl Graph) -> Vec<bool> {
+        match &self.top {
+            Some(top) => {
+                let mut bottom = vec![true; graph.nodes()];
+                for &n in top.iter().filter(|&&n| n < graph.nodes()) {
+                    bottom[n] = false;
+                }
+                bottom
+            }
+            None => breadth_first_sides(graph).0,
+        }
+    }
+
+    /// The sort key of a node given the indices of the nodes in the other row.
+    fn key(&self, neighbours: &[usize], index: &[usize], current: usize) -> Float {
+        let mut positions: Vec<Float> = neighbours.iter().map(|&m| index[m] as Float).collect();
+        if positions.is_empty() {
+            return current as Float;
+        }
+        match self.heuristic {
+            Heuristic::Barycenter => positions.iter().sum::<Float>() / positions.len() as Float,
+            Heuristic::Median => {
+                positions.sort_by(Float::total_cmp);
+                let middle = positions.len() / 2;
+                match positions.len() % 2 {
+                    1 => positions[middle],
+                    _ => (positions[middle - 1] + positions[middle]) / 2.,
+                }
+            }
+        }
+    }
+}
+
+/// Alternate the sides along a breadth first search, together with whether no edge connects nodes
+/// of the same side.
+fn breadth_first_sides(graph: &impl Graph) -> (Vec<bool>, bool) {
+    let neighbours = neighbours(graph);
+    let mut side: Vec<Option<bool>> = vec![None; graph.nodes()];
+    let mut bipartite = true;
+    let mut queue = VecDeque::new();
+    for root in 0..graph.nodes() {
+        if side[root].is_some() {
+            continue;
+        }
+        side[root] = Some(false);
+        queue.push_back(root);
+        while let Some(n) = queue.pop_front() {
+            let bottom = side[n] == Some(true);
+            for &m in &neighbours[n] {
+                match side[m] {
+                    None => {
+                        side[m] = Some(!bottom);
+                        queue.push_back(m);
+                    }
+                    Some(s) => bipartite &= s != bottom,
+                }
+            }
+        }
+    }
+    (side.into_iter().map(Option::unwrap).collect(), bipartite)
+}
+
+/// Count the crossings of the edges between both rows, given the index of each node in its row.
+fn crossings(edges: &[(usize, usize)], index: &[usize]) -> usize {
+    let mut count = 0;
+    for (i, &(a, b)) in edges.iter().enumerate() {
+        for &(c, d) in &edges[i + 1..] {
+            let (top, bottom) = (index[a] as isize - index[c] as isize, index[b] as isize - index[d] as isize);
+            if top.signum() * bottom.signum() < 0 {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+impl Placement for Bipartite {
+    fn place(&self, graph: &impl Graph) -> Array2<Float> {
+        let nodes = graph.nodes();
+        let bottom = self.sides(graph);
+        // edges between both rows, from top to bottom
+        let edges: Vec<(usize, usize)> = graph
+            .edges()
+            .filter(|&(u, v)| bottom[u] != bottom[v])
+            .map(|(u, v)| if bottom[u] { (v, u) } else { (u, v) })
+            .collect();
+        let mut neighbours = vec![Vec::new(); nodes];
+        for &(u, v) in &edges {
+            neighbours[u].push(v);
+            neighbours[v].push(u);
+        }
+
+        let mut rows: [Vec<usize>; 2] = [(0..nodes).filter(|&n| !bottom[n]).collect(), (0..nodes).filter(|&n| bottom[n]).collect()];
+        let mut index = vec![0; nodes];
+        let assign = |rows: &[Vec<usize>; 2], index: &mut Vec<usize>| {
+            for row in rows {
+                for (i, &n) in row.iter().enumerate() {
+                    index[n] = i;
+                }
+            }
+        };
+        assign(&rows, &mut index);
+        let mut best = (crossings(&edges, &index), rows.clone());
+        for _ in 0..self.sweeps {
+            for r in [1, 0] {
+                let mut keyed: Vec<(Float, usize)> = rows[r].iter().map(|&n| (self.key(&neighbours[n], &index, index[n]), n)).collect();
+                keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(index[a.1].cmp(&index[b.1])));
+                rows[r] = keyed.into_iter().map(|(_, n)| n).collect();
+                assign(&rows, &mut index);
+            }
+            let count = crossings(&edges, &index);
+            if count < best.0 {
+                best = (count, rows.clone());
+            }
+        }
+
+        let mut pos = Array2::<Float>::zeros((nodes, 2));
+        for (r, row) in best.1.iter().enumerate() {
+            let offset = (row.len() as Float - 1.) / 2.;
+            for (i, &n) in row.iter().enumerate() {
+                pos[[n, 0]] = (i as Float - offset) * self.k;
+                pos[[n, 1]] = r as Float * self.separation;
+            }
+        }
+        pos
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bipartition, Bipartite, Heuristic};
+    use crate::engines::Placement;
+    use crate::graph::generators::{cycle, grid};
+    use crate::metrics::crossings;
+    use crate::Float;
+
+    #[test]
+    fn detect_bipartition() {
+        let sides = bipartition(&grid(3, 3)).unwrap();
+        // a checkerboard coloring
+        assert_eq!(sides, vec![false, true, false, true, false, true, false, true, false]);
+        assert!(bipartition(&cycle(4)).is_some());
+        assert!(bipartition(&cycle(5)).is_none());
+        // isolated nodes and further components start on top
+        let graph: Vec<(usize, usize)> = vec![(0, 1), (3, 2)];
+        assert_eq!(bipartition(&graph).unwrap(), vec![false, true, false, true]);
+    }
+
+    #[test]
+    fn rows_with_fewer_crossings() {
+        // every pair of edges crosses in index order
+        let graph: Vec<(usize, usize)> = vec![(0, 7), (1, 6), (2, 5), (3, 4), (0, 6)];
+        let unordered = Bipartite::new().partition(vec![0, 1, 2, 3]).sweeps(0).place(&graph);
+        assert!(crossings(&graph, unordered.view()) > 0);
+        for heuristic in [Heuristic::Barycenter, Heuristic::Median] {
+            let pos = Bipartite::new().heuristic(heuristic).spacing(10.).separation(50.).place(&graph);
+            assert_eq!(crossings(&graph, pos.view()), 0);
+            assert!((0..4).all(|n| pos[[n, 1]] == 0.) && (4..8).all(|n| pos[[n, 1]] == 50.));
+            // rows are centered
+            assert_eq!((0..4).map(|n| pos[[n, 0]]).sum::<Float>(), 0.);
+        }
+
+        // explicit partitions may put neighbours into the same row
+        let pos = Bipartite::new().partition(vec![0, 1]).place(&cycle(3));
+        assert_eq!((pos[[0, 1]], pos[[1, 1]], pos[[2, 1]]), (0., 0., 300.));
+        // odd cycles still get two rows
+        let pos = Bipartite::new().place(&cycle(5));
+        assert!((0..5).all(|n| pos[[n, 1]] == 0. || pos[[n, 1]] == 300.));
+    }
+}
@@ -0,0 +1,159 @@
+use ndarray::Array2;
+
+use crate::layout::{BoundingBox, Point};
+use crate::Float;
+
+/// A region of the plane that constrains the node positions of an engine.
+///
+/// Nodes that would leave the region during an iteration are projected back onto its boundary, i.e.
+/// moved to the closest point of the region, while all other nodes move freely. Layouts thus fill
+/// the region without being rescaled. 3D layouts are constrained in their x and y coordinates only.
+///
+/// ```
+/// use rs_plode::engines::bounds::Bounds;
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::layout::Point;
+/// use rs_plode::Graph;
+///
+/// let badge = Bounds::Circle { center: Point([0., 0.]), radius: 200. };
+/// let layout = vec![(0usize, 1usize), (1, 2), (2, 3), (3, 0)].layout(FruchtermanReingold::default().bounds(badge.clone()));
+/// assert!((0..4).all(|n| badge.contains(layout.coord(n))));
+/// ```
+#[derive(Clone, Debug)]
+pub enum Bounds {
+    /// An axis aligned rectangle.
+    Rectangle(BoundingBox),
+    /// A circle with given center and radius.
+    Circle { center: Point, radius: Float },
+    /// A simple polygon given by its vertices in order, which may be concave. Polygons with less than
+    /// three vertices do not constrain the positions.
+    Polygon(Vec<Point>),
+}
+
+impl Bounds {
+    /// Whether the point lies within the region, including its boundary. Points further outside
+    /// than a millionth of the size of the region, i.e. points projected onto the boundary up to
+    /// rounding errors, do not.
+    pub fn contains(&self, p: Point) -> bool {
+        let tolerance = self.bbox().map_or(0., |bbox| 1e-6 * Float::max(bbox.width(), bbox.height()));
+        squared_distance(self.project(p), p) <= tolerance * tolerance
+    }
+
+    /// The closest point of the region, i.e. the point itself if it lies within.
+    pub fn project(&self, p: Point) -> Point {
+        match self {
+            Bounds::Rectangle(bbox) => Point(std::array::from_fn(|d| {
+                p.get(d).clamp(bbox.lower_left().get(d), Float::max(bbox.lower_left().get(d), bbox.upper_right().get(d)))
+            })),
+            Bounds::Circle { center, radius } => {
+                let distance = squared_distance(p, *center).sqrt();
+                if distance <= *radius {
+                    return p;
+                }
+                let scale = Float::max(*radius, 0.) / distance;
+                Point([center.x() + (p.x() - center.x()) * scale, center.y() + (p.y() - center.y()) * scale])
+            }
+            Bounds::Polygon(vertices) => {
+                if vertices.len() < 3 || inside_polygon(vertices, p) {
+                    return p;
+                }
+                let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+                edges
+                    .map(|(&a, &b)| closest_on_segment(a, b, p))
+                    .min_by(|&a, &b| squared_distance(a, p).total_cmp(&squared_distance(b, p)))
+                    .unwrap()
+            }
+        }
+    }
+
+    /// The smallest axis aligned rectangle containing the region, if it is constrained at all.
+    pub fn bbox(&self) -> Option<BoundingBox> {
+        match self {
+            Bounds::Rectangle(bbox) => Some(*bbox),
+            Bounds::Circle { center, radius } => Some(BoundingBox(
+                Point([center.x() - radius, center.y() - radius]),
+                Point([center.x() + radius, center.y() + radius]),
+            )),
+            Bounds::Polygon(vertices) if vertices.len() >= 3 => {
+                let fold = |d: usize, f: fn(Float, Float) -> Float, init: Float| vertices.iter().map(|v| v.get(d)).fold(init, f);
+                Some(BoundingBox(
+                    Point([fold(0, Float::min, Float::INFINITY), fold(1, Float::min, Float::INFINITY)]),
+                    Point([fold(0, Float::max, Float::NEG_INFINITY), fold(1, Float::max, Float::NEG_INFINITY)]),
+                ))
+            }
+            Bounds::Polygon(_) => None,
+        }
+    }
+
+    /// Project the x and y coordinates of the V x D shaped positions into the region.
+    pub(crate) fn constrain(&self, positions: &mut Array2<Float>) {
+        for mut row in positions.rows_mut() {
+            let projected = self.project(Point([row[0], row[1]]));
+            row[0] = projected.x();
+            row[1] = projected.y();
+        }
+    }
+}
+
+fn squared_distance(a: Point, b: Point) -> Float {
+    (a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2)
+}
+
+/// Whether the point lies inside the polygon, by the even-odd rule.
+fn inside_polygon(vertices: &[Point], p: Point) -> bool {
+    let mut inside = false;
+    for (a, b) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+        if (a.y() > p.y()) != (b.y() > p.y()) {
+            let x = a.x() + (p.y() - a.y()) / (b.y() - a.y()) * (b.x() - a.x());
+            if p.x() < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// The point of the segment from `a` to `b` closest to `p`.
+fn closest_on_segment(a: Point, b: Point, p: Point) -> Point {
+    let (dx, dy) = (b.x() - a.x(), b.y() - a.y());
+    let length = dx * dx + dy * dy;
+    if length == 0. {
+        return a;
+    }
+    let t = (((p.x() - a.x()) * dx + (p.y() - a.y()) * dy) / length).clamp(0., 1.);
+    Point([a.x() + t * dx, a.y() + t * dy])
+}
+
+#[cfg(test)]
+mod test {
+    use super::Bounds;
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::graph::generators::grid;
+    use crate::layout::{BoundingBox, Point};
+    use crate::Graph;
+
+    #[test]
+    fn project_into_shapes() {
+        let rectangle = Bounds::Rectangle(BoundingBox(Point([0., 0.]), Point([4., 2.])));
+        assert_eq!(rectangle.project(Point([5., -1.])), Point([4., 0.]));
+        assert_eq!(rectangle.project(Point([1., 1.])), Point([1., 1.]));
+
+        let circle = Bounds::Circle { center: Point([1., 1.]), radius: 5. };
+        assert_eq!(circle.project(Point([7., 9.])), Point([4., 5.]));
+        assert!(circle.contains(Point([4., 5.])) && !circle.contains(Point([4., 6.])));
+
+        // an L shape, concave at (1, 1)
+        let l = Bounds::Polygon(vec![Point([0., 0.]), Point([2., 0.]), Point([2., 1.]), Point([1., 1.]), Point([1., 2.]), Point([0., 2.])]);
+        assert!(l.contains(Point([0.5, 1.5])) && !l.contains(Point([1.5, 1.5])));
+        assert_eq!(l.project(Point([1.5, 1.2])), Point([1.5, 1.]));
+        assert_eq!(l.project(Point([3., -1.])), Point([2., 0.]));
+        assert_eq!(l.bbox().unwrap().upper_right(), Point([2., 2.]));
+        assert_eq!(Bounds::Polygon(vec![]).project(Point([9., 9.])), Point([9., 9.]));
+
+        // the layout fills the region instead of being rescaled
+        let triangle = Bounds::Polygon(vec![Point([0., 0.]), Point([600., 0.]), Point([300., 500.])]);
+        let layout = grid(4, 4).layout(FruchtermanReingold::default().bounds(triangle.clone()));
+        assert!((0..16).all(|n| triangle.contains(layout.coord(n))));
+        assert!(layout.bbox().width() > 300.);
+    }
+}
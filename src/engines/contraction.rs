@@ -0,0 +1,279 @@
+use ndarray::Array2;
+
+use crate::layout::scatter::{ScatterLayout, ScatterLayoutSequence};
+use crate::layout::Point;
+use crate::{EdgeIter, Engine, Float, Graph};
+
+use super::observer::Observer;
+use super::Seeded;
+
+/// A graph where chains of degree-2 nodes have been replaced by single weighted edges.
+///
+/// The weight of a contracted edge is the inverse of the number of original edges it replaces,
+/// such that force directed engines keep long chains long.
+#[derive(Clone, Debug)]
+pub struct ContractedGraph {
+    nodes: usize,
+    edges: Vec<(usize, usize)>,
+    weights: Vec<Float>,
+}
+
+impl Graph for ContractedGraph {
+    type Edges<'a> = EdgeIter<'a>;
+
+    fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.edges.iter().copied()
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edges.len())
+    }
+
+    fn edge_weights(&self) -> Option<Vec<Float>> {
+        Some(self.weights.clone())
+    }
+}
+
+/// The mapping between an original graph and its contraction.
+struct Contraction {
+    graph: ContractedGraph,
+    /// the original node id of each contracted node
+    kept: Vec<usize>,
+    /// for each contracted edge, the original interior nodes in order from source to target
+    chains: Vec<Vec<usize>>,
+    nodes: usize,
+}
+
+impl Contraction {
+    fn new(graph: &impl Graph) -> Self {
+        let nodes = graph.nodes();
+        let edges: Vec<(usize, usize)> = graph.edges().collect();
+        let mut incident = vec![Vec::new(); nodes];
+        for (i, &(u, v)) in edges.iter().enumerate() {
+            incident[u].push(i);
+            if u != v {
+                incident[v].push(i);
+            }
+        }
+        let other = |e: usize, n: usize| if edges[e].0 == n { edges[e].1 } else { edges[e].0 };
+        // nodes with self loops are kept, as the loop is listed once and would mask a pendant node
+        let is_chain = |n: usize| {
+            incident[n].len() == 2
+                && edges[incident[n][0]] != edges[incident[n][1]]
+                && incident[n].iter().all(|&e| edges[e].0 != edges[e].1)
+        };
+
+        let mut keep: Vec<bool> = (0..nodes).map(|n| !is_chain(n)).collect();
+        let mut visited = vec![false; edges.len()];
+        let mut chains = Vec::new();
+        let mut contracted = Vec::new();
+
+        let mut walk = |start: usize, keep: &[bool], visited: &mut Vec<bool>| {
+            for &first in &incident[start] {
+                if visited[first] {
+                    continue;
+                }
+                visited[first] = true;
+                let (mut edge, mut node, mut interior) = (first, other(first, start), Vec::new());
+                while !keep[node] {
+                    interior.push(node);
+                    edge = *incident[node].iter().find(|&&e| e != edge).unwrap();
+                    visited[edge] = true;
+                    node = other(edge, node);
+                }
+                contracted.push((start, node, 1. / (interior.len() + 1) as Float));
+                chains.push(interior);
+            }
+        };
+
+        for n in 0..nodes {
+            if keep[n] {
+                walk(n, &keep, &mut visited);
+            }
+        }
+        // cycles made of chain nodes only are broken up by keeping one of their nodes
+        for n in 0..nodes {
+            if !keep[n] && incident[n].iter().any(|&e| !visited[e]) {
+                keep[n] = true;
+                walk(n, &keep, &mut visited);
+            }
+        }
+
+        let kept: Vec<usize> = (0..nodes).filter(|&n| keep[n]).collect();
+        let mut index = vec![usize::MAX; nodes];
+        for (i, &n) in kept.iter().enumerate() {
+            index[n] = i;
+        }
+        Self {
+            graph: ContractedGraph {
+                nodes: kept.len(),
+                edges: contracted.iter().map(|&(u, v, _)| (index[u], index[v])).collect(),
+                weights: contracted.iter().map(|&(_, _, w)| w).collect(),
+            },
+            kept,
+            chains,
+            nodes,
+        }
+    }
+
+    /// Compute positions for all original nodes by placing chain nodes evenly along the contracted
+    /// edges. Chains that start and end at the same node are placed on a circle through it.
+    fn expand(&self, coord: impl Fn(usize) -> Point) -> Array2<Float> {
+        let mut pos = Array2::<Float>::zeros((self.nodes, 2));
+        for (i, &n) in self.kept.iter().enumerate() {
+            let p = coord(i);
+            pos[[n, 0]] = p.x();
+            pos[[n, 1]] = p.y();
+        }
+        let mean_length = {
+            let lengths: Vec<Float> = self
+                .graph
+                .edges
+                .iter()
+                .filter(|(u, v)| u != v)
+                .map(|&(u, v)| ((coord(u).x() - coord(v).x()).powi(2) + (coord(u).y() - coord(v).y()).powi(2)).sqrt())
+                .collect();
+            if lengths.is_empty() { 1. } else { lengths.iter().sum::<Float>() / lengths.len() as Float }
+        };
+        for (&(u, v), chain) in self.graph.edges.iter().zip(&self.chains) {
+            let (a, b) = (coord(u), coord(v));
+            let steps = (chain.len() + 1) as Float;
+            for (j, &n) in chain.iter().enumerate() {
+                let f = (j + 1) as Float / steps;
+                let (x, y) = if u == v {
+                    // around a square standing on its corner at the node, which unlike a circle
+                    // needs no trigonometry that differs between platforms
+                    let r = mean_length / 2.;
+                    let corners = [(0., 0.), (r, r), (2. * r, 0.), (r, -r), (0., 0.)];
+                    let side = usize::min((4. * f) as usize, 3);
+                    let (t, (x0, y0), (x1, y1)) = (4. * f - side as Float, corners[side], corners[side + 1]);
+                    (a.x() + x0 + t * (x1 - x0), a.y() + y0 + t * (y1 - y0))
+                } else {
+                    (a.x() + f * (b.x() - a.x()), a.y() + f * (b.y() - a.y()))
+                };
+                pos[[n, 0]] = x;
+                pos[[n, 1]] = y;
+            }
+        }
+        pos
+    }
+}
+
+/// Wraps an engine to lay out a simplified graph, where long paths of degree-2 nodes are
+/// contracted into single weighted edges. Afterwards the chain nodes are placed evenly along the
+/// resulting edge geometry.
+///
+/// This is a big quality and speed win for road network like graphs. Observers passed to
+/// `compute_observed` see the positions of the contracted graph.
+///
+/// ```
+/// use rs_plode::engines::contraction::ChainContraction;
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::Graph;
+///
+/// let path: Vec<(usize, usize)> = (0..20).map(|n| (n, n + 1)).collect();
+/// let layout = path.layout(ChainContraction::new(FruchtermanReingold::default()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ChainContraction<E>(E);
+
+impl<E> ChainContraction<E> {
+    pub fn new(engine: E) -> Self {
+        Self(engine)
+    }
+}
+
+impl<E> Engine for ChainContraction<E>
+where
+    E: Engine<
+        Layout<ContractedGraph> = ScatterLayout<ContractedGraph>,
+        LayoutSequence<ContractedGraph> = ScatterLayoutSequence<ContractedGraph>,
+    >,
+{
+    type Layout<G: Graph> = ScatterLayout<G>;
+    type LayoutSequence<G: Graph> = ScatterLayoutSequence<G>;
+
+    fn compute<G: Graph>(self, graph: G) -> Self::Layout<G> {
+        let contraction = Contraction::new(&graph);
+        let layout = self.0.compute(contraction.graph.clone());
+        let pos = contraction.expand(|n| layout.coord(n));
+        ScatterLayout::new(graph, pos).unwrap()
+    }
+
+    fn compute_observed<G: Graph, O: Observer>(self, graph: G, observer: O) -> Self::Layout<G> {
+        let contraction = Contraction::new(&graph);
+        let layout = self.0.compute_observed(contraction.graph.clone(), observer);
+        let pos = contraction.expand(|n| layout.coord(n));
+        ScatterLayout::new(graph, pos).unwrap()
+    }
+
+    fn animate<G: Graph>(self, graph: G) -> Self::LayoutSequence<G> {
+        let contraction = Contraction::new(&graph);
+        let sequence = self.0.animate(contraction.graph.clone());
+        let frames = (0..sequence.frames())
+            .map(|f| contraction.expand(|n| sequence.coord(f, n)))
+            .collect();
+        ScatterLayoutSequence::new(graph, frames).unwrap()
+    }
+}
+
+impl<E> Seeded for ChainContraction<E>
+where
+    E: Seeded
+        + Engine<
+            Layout<ContractedGraph> = ScatterLayout<ContractedGraph>,
+            LayoutSequence<ContractedGraph> = ScatterLayoutSequence<ContractedGraph>,
+        >,
+{
+    fn with_seed(self, seed: u64) -> Self {
+        Self(self.0.with_seed(seed))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChainContraction, Contraction};
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::{Float, Graph};
+
+    #[test]
+    fn contract_chains() {
+        // a triangle 0-1-2 with a chain 2-3-4-5 hanging off node 2 and a separate cycle 6-7-8
+        let graph: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (6, 7), (7, 8), (8, 6)];
+        let contraction = Contraction::new(&graph);
+        // node 2 (degree 3) and node 5 (degree 1) are kept, as well as one node of the cycle
+        assert_eq!(contraction.kept, vec![2, 5, 6]);
+        let mut chains = contraction.chains.clone();
+        chains.sort();
+        assert_eq!(chains, vec![vec![1, 0], vec![3, 4], vec![7, 8]]);
+        // the triangle and the cycle become self loops
+        assert_eq!(contraction.graph.edges, vec![(0, 0), (0, 1), (2, 2)]);
+        assert_eq!(contraction.graph.edge_weights().unwrap(), vec![1. / 3., 1. / 3., 1. / 3.]);
+
+        // the pendant node 2 with a self loop is kept, only node 1 is a chain interior
+        let looped: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 2)];
+        let contraction = Contraction::new(&looped);
+        assert_eq!(contraction.kept, vec![0, 2]);
+        assert_eq!(contraction.chains, vec![vec![1], vec![]]);
+        assert_eq!(contraction.graph.edges, vec![(0, 1), (1, 1)]);
+        let layout = looped.layout(ChainContraction::new(FruchtermanReingold::default()));
+        assert!(layout.view().iter().all(|x| x.is_finite()));
+        assert_ne!((layout.coord(1).x(), layout.coord(1).y()), (layout.coord(2).x(), layout.coord(2).y()));
+    }
+
+    #[test]
+    fn chain_nodes_on_straight_line() {
+        let path: Vec<(usize, usize)> = (0..10).map(|n| (n, n + 1)).collect();
+        let layout = path.layout(ChainContraction::new(FruchtermanReingold::default()));
+        let (a, b) = (layout.coord(0), layout.coord(10));
+        for n in 1..10 {
+            let f = n as Float / 10.;
+            assert!((layout.coord(n).x() - (a.x() + f * (b.x() - a.x()))).abs() < 1e-2);
+            assert!((layout.coord(n).y() - (a.y() + f * (b.y() - a.y()))).abs() < 1e-2);
+        }
+    }
+}
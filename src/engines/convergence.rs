@@ -0,0 +1,173 @@
+use std::collections::VecDeque;
+
+use ndarray::{Array2, ArrayView2, Axis};
+
+use super::observer::{Control, Observer};
+use super::termination::IterationState;
+use crate::Float;
+
+/// Records per-node movement of an iterative engine, to find nodes that did not settle.
+///
+/// Pass a mutable reference as observer and create the report once the engine finished:
+/// ```
+/// use rs_plode::engines::convergence::ConvergenceRecorder;
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::Graph;
+///
+/// let mut recorder = ConvergenceRecorder::default();
+/// let layout = vec![(0usize, 1usize), (1, 2), (2, 0)].layout_observed(FruchtermanReingold::default(), &mut recorder);
+/// let report = recorder.report();
+/// println!("{} of 3 nodes are still oscillating", report.unstable_nodes().len());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConvergenceRecorder {
+    window: usize,
+    tolerance: Float,
+    previous: Option<Array2<Float>>,
+    /// the V x 2 shaped displacements of the most recent iterations
+    history: VecDeque<Array2<Float>>,
+    state: Option<IterationState>,
+}
+
+impl ConvergenceRecorder {
+    /// Judge the convergence by the displacements of the last `window` iterations.
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: usize::max(window, 2),
+            tolerance: 0.5,
+            previous: None,
+            history: VecDeque::new(),
+            state: None,
+        }
+    }
+
+    /// Set the distance a node has to travel within the window to be considered unstable.
+    /// Defaults to 0.5.
+    pub fn tolerance(mut self, tolerance: Float) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Summarize the recorded iterations.
+    pub fn report(&self) -> ConvergenceReport {
+        let nodes = self.previous.as_ref().map_or(0, |p| p.shape()[0]);
+        let mut path: Vec<Float> = vec![0.; nodes];
+        let mut net = Array2::<Float>::zeros((nodes, 2));
+        for displacement in &self.history {
+            let length = (displacement * displacement).sum_axis(Axis(1)).mapv(Float::sqrt);
+            path.iter_mut().zip(length.iter()).for_each(|(p, l)| *p += l);
+            net += displacement;
+        }
+        ConvergenceReport {
+            iterations: self.state.map_or(0, |s| s.iteration),
+            stress: self.state.map(|s| s.stress),
+            tolerance: self.tolerance,
+            path,
+            net: (&net * &net).sum_axis(Axis(1)).mapv(Float::sqrt).to_vec(),
+        }
+    }
+}
+
+impl Default for ConvergenceRecorder {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl Observer for &mut ConvergenceRecorder {
+    fn observe(&mut self, positions: ArrayView2<Float>, state: &IterationState) -> Control {
+        if let Some(previous) = &self.previous {
+            if self.history.len() == self.window {
+                self.history.pop_front();
+            }
+            self.history.push_back(&positions - previous);
+        }
+        self.previous = Some(positions.to_owned());
+        self.state = Some(*state);
+        Control::Continue
+    }
+}
+
+/// Per-node movement during the final iterations of an engine, see [`ConvergenceRecorder`].
+#[derive(Clone, Debug)]
+pub struct ConvergenceReport {
+    iterations: usize,
+    stress: Option<Float>,
+    tolerance: Float,
+    /// the distance travelled by each node within the window
+    path: Vec<Float>,
+    /// the distance between the first and the last position of each node within the window
+    net: Vec<Float>,
+}
+
+impl ConvergenceReport {
+    /// The number of completed iterations.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// The total stress of the last iteration, if any.
+    pub fn stress(&self) -> Option<Float> {
+        self.stress
+    }
+
+    /// The distance travelled by the node during the last iterations.
+    pub fn movement(&self, node: usize) -> Float {
+        self.path[node]
+    }
+
+    /// How much of the nodes movement cancelled out during the last iterations, between 0 for a
+    /// straight motion and 1 for jumping back and forth.
+    pub fn oscillation(&self, node: usize) -> Float {
+        if self.path[node] > 0. {
+            1. - self.net[node] / self.path[node]
+        } else {
+            0.
+        }
+    }
+
+    /// The nodes that are still moving back and forth, i.e. that travelled further than the
+    /// tolerance while mostly returning to where they were.
+    pub fn unstable_nodes(&self) -> Vec<usize> {
+        (0..self.path.len())
+            .filter(|&n| self.path[n] > self.tolerance && self.oscillation(n) > 0.5)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::ConvergenceRecorder;
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::observer::Observer;
+    use crate::engines::termination::IterationState;
+    use crate::{Float, Graph};
+
+    #[test]
+    fn flag_oscillating_nodes() {
+        let mut recorder = ConvergenceRecorder::new(4);
+        for i in 0..10 {
+            // node 0 jumps back and forth, node 1 moves steadily and node 2 rests
+            let x = (i % 2) as Float;
+            let positions = arr2(&[[x, 0.], [i as Float, 0.], [5., 5.]]);
+            let state = IterationState { iteration: i + 1, temperature: 1., max_displacement: 1., stress: 3., previous_stress: None };
+            (&mut recorder).observe(positions.view(), &state);
+        }
+        let report = recorder.report();
+        assert_eq!(report.iterations(), 10);
+        assert_eq!(report.unstable_nodes(), vec![0]);
+        assert_eq!(report.movement(1), 4.);
+        assert_eq!(report.oscillation(2), 0.);
+    }
+
+    #[test]
+    fn record_engine() {
+        let mut recorder = ConvergenceRecorder::default();
+        vec![(0usize, 1usize), (1, 2)].layout_observed(FruchtermanReingold::default(), &mut recorder);
+        let report = recorder.report();
+        assert_eq!(report.iterations(), 200);
+        assert!(report.stress().is_some());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::Float;
+
+/// How the temperature, i.e. the maximal displacement of a node per iteration, evolves over a run.
+///
+/// Every schedule starts at the initial temperature of the engine and drops to zero at the maximum
+/// iteration count of the termination policy, such that nodes eventually stop moving. Fast cooling
+/// converges in fewer iterations, slow cooling leaves more time to untangle the layout.
+///
+/// ```
+/// use rs_plode::engines::cooling::CoolingSchedule;
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+///
+/// let engine = FruchtermanReingold::default().cooling(CoolingSchedule::Exponential(0.95));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CoolingSchedule {
+    /// Decrease by the same amount in every iteration.
+    #[default]
+    Linear,
+    /// Multiply with the given factor in every iteration, e.g. 0.95, which cools down quickly at
+    /// first and slowly towards the end. The factor is clamped to `[0, 1]`.
+    Exponential(Float),
+    /// Divide the initial temperature by the number of iterations so far.
+    Inverse,
+    /// Adapt to the progress of the layout, following Hu (2005): heat up by `1 / 0.9` after five
+    /// iterations in a row that lowered the stress, cool down by `0.9` whenever the stress rises.
+    /// Never exceeds the initial temperature.
+    Adaptive,
+}
+
+impl CoolingSchedule {
+    /// The temperature after `n` completed iterations out of `horizon`, given the current one and
+    /// whether the last iteration lowered the stress. Adaptive schedules count the iterations in a
+    /// row that lowered the stress in `streak`.
+    pub(crate) fn next(&self, t: Float, t0: Float, (n, horizon): (usize, usize), improved: Option<bool>, streak: &mut usize) -> Float {
+        if n >= horizon {
+            return 0.;
+        }
+        match *self {
+            CoolingSchedule::Linear => (1. - n as Float / horizon as Float) * t0,
+            CoolingSchedule::Exponential(factor) => t * factor.clamp(0., 1.),
+            CoolingSchedule::Inverse => t0 / (n + 1) as Float,
+            CoolingSchedule::Adaptive => match improved {
+                Some(true) => {
+                    *streak += 1;
+                    if *streak < 5 {
+                        return t;
+                    }
+                    *streak = 0;
+                    Float::min(t / 0.9, t0)
+                }
+                Some(false) => {
+                    *streak = 0;
+                    t * 0.9
+                }
+                None => t,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CoolingSchedule;
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::termination::Termination;
+    use crate::graph::generators::grid;
+    use crate::{Float, Graph};
+
+    #[test]
+    fn schedules() {
+        let temperatures = |schedule: CoolingSchedule, improved: &[Option<bool>]| {
+            let (mut t, mut streak) = (8., 0);
+            let mut temperatures = Vec::new();
+            for (n, &improved) in improved.iter().enumerate() {
+                t = schedule.next(t, 8., (n, 4), improved, &mut streak);
+                temperatures.push(t);
+            }
+            temperatures
+        };
+        let unknown = [None; 5];
+        assert_eq!(temperatures(CoolingSchedule::Linear, &unknown), vec![8., 6., 4., 2., 0.]);
+        assert_eq!(temperatures(CoolingSchedule::Exponential(0.5), &unknown), vec![4., 2., 1., 0.5, 0.]);
+        assert_eq!(temperatures(CoolingSchedule::Inverse, &unknown), vec![8., 4., 8. / 3., 2., 0.]);
+        let rising = [None, Some(false), Some(false), Some(true)];
+        assert_eq!(temperatures(CoolingSchedule::Adaptive, &rising), vec![8., 8. * 0.9, 8. * 0.9 * 0.9, 8. * 0.9 * 0.9]);
+        let mut streak = 4;
+        assert_eq!(CoolingSchedule::Adaptive.next(7.5, 8., (0, 10), Some(true), &mut streak), 8.);
+        assert_eq!(streak, 0);
+
+        // no schedule exceeds the initial temperature
+        for schedule in [CoolingSchedule::Exponential(0.9), CoolingSchedule::Inverse, CoolingSchedule::Adaptive] {
+            let sequence = grid(4, 4).animate(FruchtermanReingold::default().cooling(schedule).until(Termination::Iterations(30)));
+            let telemetry = sequence.telemetry().unwrap();
+            assert_eq!(telemetry.states().len(), 30);
+            assert_eq!(telemetry.temperatures()[0], telemetry.temperatures().into_iter().fold(0., Float::max));
+        }
+    }
+}
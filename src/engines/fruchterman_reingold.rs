@@ -1,12 +1,28 @@
-use ndarray::{s, stack, Array, Array1, Array2, Axis, Dim};
-use ndarray_rand::rand::rngs::StdRng;
-use ndarray_rand::rand::SeedableRng;
-use ndarray_rand::rand_distr::Uniform;
+use std::time::Duration;
+
+use ndarray::{s, stack, Array1, Array2, ArrayView2, ArrayViewMut2, Axis};
+#[cfg(feature = "ndarray-rand")]
 use ndarray_rand::RandomExt;
-use ndarray_stats::MaybeNanExt;
+use rand::distributions::Uniform;
+use rand::Rng;
+use rand::SeedableRng;
 
-use crate::{layout::scatter::ScatterLayout, Engine, Graph};
+use crate::analysis::community::Partition;
+use crate::graph::GraphCache;
+use crate::{layout::scatter::ScatterLayout, Engine, Float, Graph};
 use crate::layout::scatter::ScatterLayoutSequence;
+use crate::layout::scatter::{ScatterLayout3, ScatterLayoutSequence3};
+
+use super::backend::Backend;
+use super::bounds::Bounds;
+use super::cooling::CoolingSchedule;
+use super::gravity::{community_force, gravitational_force, Gravity};
+use super::instant::Instant;
+use super::integrator::Integrator;
+use super::observer::{Control, Observer};
+use super::telemetry::Telemetry;
+use super::{EngineRng, Refine, Seeded};
+use super::termination::{IterationState, Termination};
 
 /// Implements force directed placement by Fruchterman and Reingold.
 ///
@@ -53,74 +69,166 @@ use crate::layout::scatter::ScatterLayoutSequence;
 ///        t := cool(t)
 ///   end
 /// ```
+#[derive(Clone)]
 pub struct FruchtermanReingold {
-    k: f32,
-    rng: StdRng,
+    k: Float,
+    rng: EngineRng,
+    termination: Termination,
+    cooling: CoolingSchedule,
+    inertia: Float,
+    integrator: Integrator,
+    dimensions: usize,
+    masses: Option<Vec<Float>>,
+    gravity: Option<Gravity>,
+    bounds: Option<Bounds>,
+    communities: Option<(Partition, Float)>,
+    jitter: Option<(Float, Float)>,
+    backend: Backend,
 }
 
 impl FruchtermanReingold {
-    pub fn new(k: f32, seed: u64) -> Self {
+    pub fn new(k: Float, seed: u64) -> Self {
         Self {
             k,
-            rng: StdRng::seed_from_u64(seed),
+            rng: EngineRng::seed_from_u64(seed),
+            termination: Termination::default(),
+            cooling: CoolingSchedule::default(),
+            inertia: 0.,
+            integrator: Integrator::default(),
+            dimensions: 2,
+            masses: None,
+            gravity: None,
+            bounds: None,
+            communities: None,
+            jitter: None,
+            backend: Backend::default(),
         }
     }
 
-    /// Calculate the repulsive displacements for each node from their current positions.
-    fn repulsive_force(&self, positions: &Array2<f32>, k: f32) -> Array2<f32> {
-        // see page 1136 for details. This is actually pretty important, as otherwise
-        // nodes keep getting pushed to the edge of the boundingbox forever.
-        let f_r = |r: f32| -> f32 {
-            if r < 2. * k {
-                k * k / r
-            } else {
-                0.
-            }
-        };
+    /// Set the policy deciding when to stop iterating. Defaults to 200 iterations.
+    ///
+    /// The temperature cools down following the [`CoolingSchedule`] over the maximum iteration count
+    /// of the policy, or over 200 iterations if the policy has none. Once cold, nodes stop moving,
+    /// hence displacement and stress based policies are guaranteed to terminate.
+    pub fn until(mut self, termination: Termination) -> Self {
+        self.termination = termination;
+        self
+    }
 
-        let nodes = positions.shape()[0];
-        // V x 2 shaped displacements for all nodes
-        let mut disp = Array2::<f32>::zeros((nodes, 2));
-
-        // repulsive displacements for each node
-        for j in 0..nodes {
-            // V x D shaped matrix of delta vectors from node j to all other nodes.
-            let delta: Array<f32, Dim<[usize; 2]>> = &positions.slice(s![j, ..]) - positions;
-            // V x 1 shaped matrix holding the absolute distance between v and each other vertex
-            let abs_delta: Array<f32, Dim<[usize; 2]>> = (&delta * &delta)
-                .sum_axis(Axis(1))
-                .map(|x: &f32| f32::sqrt(*x))
-                .insert_axis(Axis(1));
-            disp.slice_mut(s![j, ..]).assign(
-                // V x 2 shaped displacements for node j caused by all other nodes.
-                &((&delta / &abs_delta) * abs_delta.mapv(f_r)).fold_axis_skipnan(
-                    Axis(0),
-                    0.,
-                    |agr, val| agr + val.const_raw(),
-                ),
-            );
-        }
+    /// Set how the temperature, i.e. the maximal displacement per iteration, evolves. Defaults to
+    /// [`CoolingSchedule::Linear`].
+    pub fn cooling(mut self, schedule: CoolingSchedule) -> Self {
+        self.cooling = schedule;
+        self
+    }
 
-        disp
+    /// Damp the movement of nodes by blending each displacement with the one of the previous
+    /// iteration. Defaults to 0, i.e. no smoothing.
+    ///
+    /// Values towards 1 penalize large per-iteration jumps, which gives visually smoother animations
+    /// at the cost of a slower convergence. The value is clamped to `[0, 0.99]`.
+    pub fn smoothing(mut self, inertia: Float) -> Self {
+        self.inertia = inertia.clamp(0., 0.99);
+        self
+    }
+
+    /// Set how forces move the nodes. Defaults to [`Integrator::Displacement`].
+    ///
+    /// [`Integrator::SemiImplicitEuler`] gives nodes momentum, such that the smoothing of
+    /// [`smoothing`](Self::smoothing) does not apply.
+    pub fn integrator(mut self, integrator: Integrator) -> Self {
+        self.integrator = integrator;
+        self
+    }
+
+    /// Assign a mass to each node, indexed by node. Nodes without mass use 1.
+    ///
+    /// The repulsion between two nodes, as well as the distance up to which they repel each other,
+    /// scales with the product of their masses. Heavy nodes, e.g. hubs or otherwise important ones,
+    /// thus get more space around them.
+    pub fn masses(mut self, masses: Vec<Float>) -> Self {
+        self.masses = Some(masses);
+        self
+    }
+
+    /// Pull all nodes towards their centroid, which keeps disconnected parts of the graph together.
+    /// Defaults to no gravity.
+    pub fn gravity(mut self, gravity: Gravity) -> Self {
+        self.gravity = Some(gravity);
+        self
+    }
+
+    /// Pull every node towards the centroid of its community with the given strength relative to
+    /// `k`, which keeps communities visually apart, see [`louvain`]. Nodes without community are
+    /// not pulled.
+    ///
+    /// [`louvain`]: crate::analysis::community::louvain
+    pub fn communities(mut self, partition: Partition, strength: Float) -> Self {
+        self.communities = Some((partition, Float::max(strength, 0.)));
+        self
+    }
+
+    /// Displace every node by uniform random noise of up to `strength * k` per coordinate, which
+    /// shrinks by the factor `decay` in every iteration. Defaults to no noise.
+    ///
+    /// Noise breaks the symmetry of positions that the forces alone never leave, e.g. nodes on a
+    /// line, and shakes the layout out of shallow local minima early on. The decay is clamped to
+    /// `[0, 1]`, values below 1 let the noise fade out, such that the layout still settles. The
+    /// noise stops once the temperature reached zero, such that even without decay the policies of
+    /// [`until`](Self::until) terminate.
+    pub fn with_jitter(mut self, strength: Float, decay: Float) -> Self {
+        self.jitter = Some((Float::max(strength, 0.), decay.clamp(0., 1.)));
+        self
+    }
+
+    /// Choose how the repulsion between all pairs of nodes is computed. Defaults to
+    /// [`Backend::Auto`], all CPU backends yield the same layout.
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Constrain the nodes to the given region, projecting nodes that would leave it back onto its
+    /// boundary. The random initial positions are spread over the region. Defaults to no bounds.
+    pub fn bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Lay out the nodes in 3D space instead of the plane, see [`FruchtermanReingold3`].
+    pub fn in_3d(mut self) -> FruchtermanReingold3 {
+        self.dimensions = 3;
+        FruchtermanReingold3(self)
+    }
+
+    /// Calculate the repulsive displacements for each node from their current positions.
+    #[inline(never)]
+    fn repulsive_force(&self, positions: &Array2<Float>, k: Float) -> Array2<Float> {
+        self.backend.repulsive_force(positions.view(), k, self.masses.as_deref())
     }
 
     /// Calculate the attractive displacement for each node from their current positions and graph connectivity.
-    fn attractive_force(&self, graph: &impl Graph, positions: &Array2<f32>, k: f32) -> Array2<f32> {
+    ///
+    /// The attraction along each edge is scaled by its weight, if the graph provides any.
+    #[inline(never)]
+    fn attractive_force(&self, graph: &GraphCache, positions: &Array2<Float>, k: Float) -> Array2<Float> {
         let nodes = graph.nodes();
-        let f_a = |r: f32| -> f32 { r * r / k };
+        let weights = graph.weights();
+        let f_a = |r: Float| -> Float { r * r / k };
         // note: for sparse connections we have a lot of zero terms in the attractive displacements
         //       however, for small graphs (~100 nodes, ~500 edge) performance is still no issue...
-        let mut disp = Array2::<f32>::zeros((nodes, 2));
-        for (v, u) in graph.edges() {
+        let mut disp = Array2::<Float>::zeros((nodes, positions.shape()[1]));
+        for (i, (v, u)) in graph.edges().enumerate() {
+            let w = weights.map_or(1., |w| w[i]);
             let delta = &positions.slice(s![v, ..]) - &positions.slice(s![u, ..]);
             let abs_delta = (&delta * &delta).sum_axis(Axis(0)).into_scalar().sqrt();
             {
                 let mut slice = disp.slice_mut(s![v, ..]);
-                slice += &(((-1. / f32::max(abs_delta, 1.)) * &delta) * f_a(abs_delta));
+                slice += &(((-1. / Float::max(abs_delta, 1.)) * &delta) * (w * f_a(abs_delta)));
             }
             {
                 let mut slice = disp.slice_mut(s![u, ..]);
-                slice += &(((1. / f32::max(abs_delta, 1.)) * &delta) * f_a(abs_delta));
+                slice += &(((1. / Float::max(abs_delta, 1.)) * &delta) * (w * f_a(abs_delta)));
             }
         }
 
@@ -128,12 +236,377 @@ impl FruchtermanReingold {
     }
 }
 
+/// A column of uniformly distributed random values.
+#[cfg(feature = "ndarray-rand")]
+fn random_column(len: usize, uniform: Uniform<Float>, rng: &mut EngineRng) -> Array1<Float> {
+    Array1::random_using((len,), uniform, rng)
+}
+
+/// A column of uniformly distributed random values, drawn in the same order as by ndarray-rand.
+#[cfg(not(feature = "ndarray-rand"))]
+fn random_column(len: usize, uniform: Uniform<Float>, rng: &mut EngineRng) -> Array1<Float> {
+    Array1::from_shape_simple_fn(len, || rng.sample(uniform))
+}
+
+/// The cube root by Newton's method, as the precision of `Float::cbrt` depends on the platform.
+fn cbrt(x: Float) -> Float {
+    if x <= 0. {
+        return 0.;
+    }
+    let mut y = Float::max(x, 1.);
+    for _ in 0..64 {
+        let next = (2. * y + x / (y * y)) / 3.;
+        if next >= y {
+            break;
+        }
+        y = next;
+    }
+    y
+}
+
 impl Default for FruchtermanReingold {
     fn default() -> Self {
         Self {
             k: 150.,
-            rng: StdRng::seed_from_u64(0),
+            rng: EngineRng::seed_from_u64(0),
+            termination: Termination::default(),
+            cooling: CoolingSchedule::default(),
+            inertia: 0.,
+            integrator: Integrator::default(),
+            dimensions: 2,
+            masses: None,
+            gravity: None,
+            bounds: None,
+            communities: None,
+            jitter: None,
+            backend: Backend::default(),
+        }
+    }
+}
+
+impl FruchtermanReingold {
+    /// The edge length of the square (or cube) the random initial positions are drawn from.
+    fn border_length(&self, graph: &impl Graph) -> Float {
+        let nodes = graph.nodes() as Float;
+        match self.dimensions {
+            2 => Float::sqrt(nodes) * self.k,
+            _ => cbrt(nodes) * self.k,
+        }
+    }
+
+    /// The positions of the nodes, initialized randomly in 2 or 3 dimensions.
+    fn initial_positions(&mut self, graph: &impl Graph) -> Array2<Float> {
+        let border_length = self.border_length(graph);
+        let uniform = Uniform::new(-border_length / 2., border_length / 2.);
+        let columns: Vec<Array1<Float>> = (0..self.dimensions)
+            .map(|_| random_column(graph.nodes(), uniform, &mut self.rng))
+            .collect();
+        let views: Vec<_> = columns.iter().map(Array1::view).collect();
+        let mut pos = stack(Axis(1), &views).unwrap();
+        if let Some((bounds, bbox)) = self.bounds.as_ref().and_then(|b| Some((b, b.bbox()?))) {
+            for d in 0..2 {
+                let (lower, extent) = (bbox.lower_left().get(d), bbox.extent(d));
+                pos.column_mut(d).mapv_inplace(|v| lower + (v / border_length + 0.5) * extent);
+            }
+            bounds.constrain(&mut pos);
         }
+        pos
+    }
+
+    /// Start a new run on the given graph.
+    fn start(&self, cache: GraphCache) -> Progress {
+        let t0 = self.border_length(&cache) / 20.;
+        Progress {
+            t0,
+            t: t0,
+            horizon: self.termination.max_iterations().unwrap_or(200),
+            n: 0,
+            streak: 0,
+            previous_stress: None,
+            gravity: self.gravity.map(|gravity| gravity.magnitudes(&cache, self.k)),
+            velocity: Array2::zeros((cache.nodes(), self.dimensions)),
+            cache,
+            rng: self.rng.clone(),
+            jitter: self.jitter.map_or(0., |(strength, _)| strength * self.k),
+            timings: PhaseTimings::default(),
+        }
+    }
+
+    /// Perform a single iteration, updating the positions in place.
+    fn step(&self, progress: &mut Progress, pos: &mut Array2<Float>) -> IterationState {
+        // V x D shaped
+        let start = Instant::now();
+        let repulsion = self.repulsive_force(pos, self.k);
+        let lap = Instant::now();
+        progress.timings.repulsion += lap - start;
+        let attraction = self.attractive_force(&progress.cache, pos, self.k);
+        let start = Instant::now();
+        progress.timings.attraction += start - lap;
+        let mut force = repulsion + attraction;
+        if let Some(magnitudes) = &progress.gravity {
+            force += &gravitational_force(pos, magnitudes);
+        }
+        if let Some((partition, strength)) = &self.communities {
+            force += &community_force(pos, partition, strength * self.k);
+        }
+        let stress = (&force * &force)
+            .sum_axis(Axis(1))
+            .mapv(|x: Float| Float::max(1., x).sqrt())
+            .sum();
+        progress.velocity = self.integrator.velocity(&progress.velocity, &force, progress.t, self.inertia);
+        let mut displacement = progress.velocity.clone();
+        if let Some((_, decay)) = self.jitter {
+            let amplitude = progress.jitter;
+            if amplitude > 0. && progress.t > 0. {
+                let uniform = Uniform::new_inclusive(-amplitude, amplitude);
+                displacement.mapv_inplace(|d| d + progress.rng.sample(uniform));
+            }
+            // a running product rather than `powi`, which is not exactly rounded on all platforms
+            progress.jitter *= decay;
+        }
+        let max_displacement = (&displacement * &displacement)
+            .sum_axis(Axis(1))
+            .iter()
+            .fold(0., |m: Float, &x| m.max(x.sqrt()));
+        *pos += &displacement;
+        if let Some(bounds) = &self.bounds {
+            bounds.constrain(pos);
+        }
+
+        // original clamping method
+        //            pos = stack![
+        //                Axis(1),
+        //                pos.slice(s![.., 0])
+        //                    .map(|x| x.clamp(-self.width / 2., self.width / 2.)),
+        //                pos.slice(s![.., 1])
+        //                    .map(|x| x.clamp(-self.height / 2., self.height / 2.))
+        //            ];
+        let temperature = progress.t;
+        let improved = progress.previous_stress.map(|previous| stress < previous);
+        progress.t = self.cooling.next(progress.t, progress.t0, (progress.n, progress.horizon), improved, &mut progress.streak);
+        progress.n += 1;
+        progress.timings.integrate += start.elapsed();
+
+        let state = IterationState {
+            iteration: progress.n,
+            temperature,
+            max_displacement,
+            stress,
+            previous_stress: progress.previous_stress,
+        };
+        progress.previous_stress = Some(stress);
+        state
+    }
+
+    /// Iterate from the given positions until the termination policy or the observer says stop.
+    fn run(&self, cache: GraphCache, pos: Array2<Float>, observer: impl Observer) -> (Array2<Float>, PhaseTimings) {
+        self.iterate(self.start(cache), pos, observer)
+    }
+
+    fn iterate(&self, mut progress: Progress, mut pos: Array2<Float>, mut observer: impl Observer) -> (Array2<Float>, PhaseTimings) {
+        loop {
+            let state = self.step(&mut progress, &mut pos);
+            if observer.observe(pos.view(), &state) == Control::Stop
+                || self.termination.should_stop(&state)
+            {
+                break;
+            }
+        }
+        (pos, progress.timings)
+    }
+
+    /// Compute the layout and measure the time spent in each phase, e.g. for benchmarking.
+    pub fn compute_timed<G: Graph>(mut self, graph: G) -> (ScatterLayout<G>, PhaseTimings) {
+        let start = Instant::now();
+        let cache = GraphCache::for_layout(&graph);
+        let pos = self.initial_positions(&cache);
+        let init = start.elapsed();
+        let (pos, timings) = self.run(cache, pos, |_: ArrayView2<Float>, _: &IterationState| Control::Continue);
+        (ScatterLayout::new(graph, pos).unwrap(), PhaseTimings { init, ..timings })
+    }
+
+    /// Lazily compute the layout frames, starting with the random initial positions.
+    ///
+    /// Unlike [`Engine::animate`] only the current positions are kept in memory, the frames borrow
+    /// the graph. Frames fail if the positions are no longer finite, which ends the iteration.
+    ///
+    /// Panics if the graph is malformed, see [`validate`](crate::validate).
+    pub fn frames<G: Graph>(mut self, graph: &G) -> Frames<'_, G> {
+        let cache = GraphCache::for_layout(graph);
+        let pos = self.initial_positions(&cache);
+        let progress = self.start(cache);
+        Frames {
+            engine: self,
+            graph,
+            pos,
+            progress,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Start a simulation on the graph that the caller advances one iteration at a time, e.g. once
+    /// per frame of a GUI, see [`Simulation`].
+    ///
+    /// Fails like [`validate`](crate::validate) if the graph is malformed.
+    pub fn simulation<G: Graph>(mut self, graph: G) -> Result<Simulation<G>, String> {
+        let cache = GraphCache::new(&graph)?;
+        let pos = self.initial_positions(&cache);
+        let progress = self.start(cache);
+        Ok(Simulation {
+            engine: self,
+            graph,
+            pos,
+            progress,
+            state: None,
+        })
+    }
+}
+
+/// Bookkeeping of a running layout computation.
+struct Progress {
+    t0: Float,
+    /// the current temperature
+    t: Float,
+    /// the number of iterations over which the temperature cools down
+    horizon: usize,
+    /// the number of completed iterations
+    n: usize,
+    /// the number of iterations in a row that lowered the stress
+    streak: usize,
+    previous_stress: Option<Float>,
+    /// the structure of the graph, derived once
+    cache: GraphCache,
+    /// the magnitude of the gravitational pull on each node, if any
+    gravity: Option<Array1<Float>>,
+    /// the displacement of the previous iteration, i.e. the velocity of the nodes
+    velocity: Array2<Float>,
+    /// the generator of the jitter, continuing where the initial positions left off
+    rng: EngineRng,
+    /// the amplitude of the jitter, shrinking by its decay in every iteration
+    jitter: Float,
+    timings: PhaseTimings,
+}
+
+/// Time spent in the individual phases of a layout computation, see
+/// [`FruchtermanReingold::compute_timed`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PhaseTimings {
+    /// Creating the random initial positions.
+    pub init: Duration,
+    /// Computing the repulsive forces between all pairs of nodes.
+    pub repulsion: Duration,
+    /// Computing the attractive forces along edges.
+    pub attraction: Duration,
+    /// Limiting and applying the displacements and cooling down.
+    pub integrate: Duration,
+}
+
+impl PhaseTimings {
+    /// The phases as (name, duration) pairs in execution order.
+    pub fn phases(&self) -> [(&'static str, Duration); 4] {
+        [
+            ("init", self.init),
+            ("repulsion", self.repulsion),
+            ("attraction", self.attraction),
+            ("integrate", self.integrate),
+        ]
+    }
+}
+
+/// Iterator over lazily computed layout frames, see [`FruchtermanReingold::frames`].
+pub struct Frames<'a, G: Graph> {
+    engine: FruchtermanReingold,
+    graph: &'a G,
+    pos: Array2<Float>,
+    progress: Progress,
+    started: bool,
+    done: bool,
+}
+
+impl<'a, G: Graph> Iterator for Frames<'a, G> {
+    type Item = Result<ScatterLayout<&'a G>, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+        } else if self.done {
+            return None;
+        } else {
+            let state = self.engine.step(&mut self.progress, &mut self.pos);
+            self.done = self.engine.termination.should_stop(&state);
+        }
+        let frame = ScatterLayout::new(self.graph, self.pos.clone());
+        self.done |= frame.is_err();
+        Some(frame)
+    }
+}
+
+/// A layout computation driven by the caller, created with [`FruchtermanReingold::simulation`].
+///
+/// Every [`tick`](Self::tick) performs one iteration, such that the application decides about the
+/// pacing, e.g. in a game loop. Ticking on after the termination policy was satisfied is possible,
+/// but nodes no longer move once the temperature cooled down; [`reheat`](Self::reheat) restarts
+/// the cooling, e.g. after the user dragged a node.
+///
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+///
+/// let mut sim = FruchtermanReingold::default().simulation(vec![(0usize, 1usize), (1, 2), (2, 0)]).unwrap();
+/// while !sim.is_settled() {
+///     sim.tick();
+/// }
+/// assert_eq!(sim.positions().nrows(), 3);
+/// ```
+pub struct Simulation<G: Graph> {
+    engine: FruchtermanReingold,
+    graph: G,
+    pos: Array2<Float>,
+    progress: Progress,
+    state: Option<IterationState>,
+}
+
+impl<G: Graph> Simulation<G> {
+    /// Perform a single iteration and return the resulting state.
+    pub fn tick(&mut self) -> IterationState {
+        let state = self.engine.step(&mut self.progress, &mut self.pos);
+        self.state = Some(state);
+        state
+    }
+
+    /// The current V x D shaped node positions.
+    pub fn positions(&self) -> ArrayView2<'_, Float> {
+        self.pos.view()
+    }
+
+    /// Mutable access to the node positions, e.g. to move a node the user dragged.
+    pub fn positions_mut(&mut self) -> ArrayViewMut2<'_, Float> {
+        self.pos.view_mut()
+    }
+
+    /// The state after the last tick, if any.
+    pub fn state(&self) -> Option<&IterationState> {
+        self.state.as_ref()
+    }
+
+    /// Whether the termination policy is satisfied by the last tick.
+    pub fn is_settled(&self) -> bool {
+        self.state.as_ref().is_some_and(|state| self.engine.termination.should_stop(state))
+    }
+
+    /// Restart cooling from the initial temperature, keeping the current positions.
+    pub fn reheat(&mut self) {
+        self.progress = self.engine.start(self.progress.cache.clone());
+        self.state = None;
+    }
+
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// Finish the simulation with the current positions.
+    pub fn into_layout(self) -> ScatterLayout<G> {
+        ScatterLayout::new(self.graph, self.pos).unwrap()
     }
 }
 
@@ -142,113 +615,308 @@ impl Engine for FruchtermanReingold {
     type LayoutSequence<G: Graph> = ScatterLayoutSequence<G>;
 
     fn compute<G: Graph>(self, graph: G) -> Self::Layout<G> {
-        let sequence = self.animate(graph);
-        let last = sequence.frame(sequence.frames() - 1).to_owned();
-        ScatterLayout::new(sequence.graph, last).unwrap()
+        self.compute_observed(graph, |_: ArrayView2<Float>, _: &IterationState| Control::Continue)
+    }
+
+    fn compute_observed<G: Graph, O: Observer>(mut self, graph: G, observer: O) -> Self::Layout<G> {
+        let cache = GraphCache::for_layout(&graph);
+        let pos = self.initial_positions(&cache);
+        let (pos, _) = self.run(cache, pos, observer);
+        ScatterLayout::new(graph, pos).unwrap()
     }
 
     fn animate<G: Graph>(mut self, graph: G) -> Self::LayoutSequence<G> {
-        let border_length = f32::sqrt(graph.nodes() as f32) * self.k;
-        let t0 = border_length / 20.;
-        let mut t = t0;
-        const N: i32 = 200;
-        let mut sequence = Vec::new();
-
-        // the positions of the nodes. initialized randomly in 2 dimensions
-        let mut pos = stack![
-            Axis(1),
-            Array1::<f32>::random_using(
-                (graph.nodes(),),
-                Uniform::new(-border_length / 2., border_length / 2.),
-                &mut self.rng,
-            ),
-            Array1::<f32>::random_using(
-                (graph.nodes(),),
-                Uniform::new(-border_length / 2., border_length / 2.),
-                &mut self.rng,
-            )
-        ];
-
-        sequence.push(pos.clone());
-
-        for n in 0..N {
-            // V x D shaped
-            let force =
-                self.repulsive_force(&pos, self.k) + self.attractive_force(&graph, &pos, self.k);
-            let force_norm = (&force * &force)
-                .sum_axis(Axis(1))
-                .mapv(|x: f32| f32::max(1., x).sqrt());
-            let force_scale = force_norm.mapv(|x: f32| f32::min(t, x));
-            let displacement =
-                (&force / &force_norm.insert_axis(Axis(1))) * &force_scale.insert_axis(Axis(1));
-            pos += &displacement;
-
-            // one could add a little noise to help escape local minima
-            //            let mean: f32 = f32::max(k / 20., displacement.mean().unwrap().abs());
-            //            pos += &Array2::<f32>::random_using(
-            //                (graph.node_count(), 2),
-            //                Uniform::new(-mean, mean),
-            //                &mut self.rng,
-            //            );
-
-            // original clamping method
-            //            pos = stack![
-            //                Axis(1),
-            //                pos.slice(s![.., 0])
-            //                    .map(|x| x.clamp(-self.width / 2., self.width / 2.)),
-            //                pos.slice(s![.., 1])
-            //                    .map(|x| x.clamp(-self.height / 2., self.height / 2.))
-            //            ];
-            t = (1. - n as f32 / N as f32) * t0;
-            sequence.push(pos.clone());
-        }
-        ScatterLayoutSequence::new(graph, sequence).unwrap()
+        let cache = GraphCache::for_layout(&graph);
+        let pos = self.initial_positions(&cache);
+        let mut sequence = vec![pos.clone()];
+        let mut telemetry = Telemetry::default();
+        self.run(cache, pos, |frame: ArrayView2<Float>, state: &IterationState| {
+            sequence.push(frame.to_owned());
+            telemetry.push(*state);
+            Control::Continue
+        });
+        ScatterLayoutSequence::new(graph, sequence).unwrap().with_telemetry(telemetry)
+    }
+}
+
+impl Seeded for FruchtermanReingold {
+    fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = EngineRng::seed_from_u64(seed);
+        self
+    }
+}
+
+impl Refine for FruchtermanReingold {
+    fn refine<G: Graph, O: Observer>(self, graph: G, positions: Array2<Float>, observer: O) -> ScatterLayout<G> {
+        let mut progress = self.start(GraphCache::for_layout(&graph));
+        // the positions are roughly right already, hence nodes move by at most one edge length
+        progress.t0 = Float::min(progress.t0, self.k);
+        progress.t = progress.t0;
+        let (pos, _) = self.iterate(progress, positions, observer);
+        ScatterLayout::new(graph, pos).unwrap()
+    }
+}
+
+/// The 3D variant of [`FruchtermanReingold`], created with [`FruchtermanReingold::in_3d`].
+///
+/// The forces are the same as in the plane, nodes start at random positions in a cube of edge
+/// length `cbrt(|V|) * k`.
+///
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::Graph;
+///
+/// let cube = vec![(0, 1), (1, 2), (2, 3), (3, 0), (4, 5), (5, 6), (6, 7), (7, 4), (0, 4), (1, 5), (2, 6), (3, 7)];
+/// let layout = cube.layout(FruchtermanReingold::new(150., 0).in_3d());
+/// assert!(layout.bbox().depth() > 0.);
+/// ```
+pub struct FruchtermanReingold3(FruchtermanReingold);
+
+impl Default for FruchtermanReingold3 {
+    fn default() -> Self {
+        FruchtermanReingold::default().in_3d()
+    }
+}
+
+impl Engine for FruchtermanReingold3 {
+    type Layout<G: Graph> = ScatterLayout3<G>;
+    type LayoutSequence<G: Graph> = ScatterLayoutSequence3<G>;
+
+    fn compute<G: Graph>(self, graph: G) -> Self::Layout<G> {
+        self.compute_observed(graph, |_: ArrayView2<Float>, _: &IterationState| Control::Continue)
+    }
+
+    fn compute_observed<G: Graph, O: Observer>(mut self, graph: G, observer: O) -> Self::Layout<G> {
+        let cache = GraphCache::for_layout(&graph);
+        let pos = self.0.initial_positions(&cache);
+        let (pos, _) = self.0.run(cache, pos, observer);
+        ScatterLayout3::with_positions(graph, pos).unwrap()
+    }
+
+    fn animate<G: Graph>(mut self, graph: G) -> Self::LayoutSequence<G> {
+        let cache = GraphCache::for_layout(&graph);
+        let pos = self.0.initial_positions(&cache);
+        let mut sequence = vec![pos.clone()];
+        let mut telemetry = Telemetry::default();
+        self.0.run(cache, pos, |frame: ArrayView2<Float>, state: &IterationState| {
+            sequence.push(frame.to_owned());
+            telemetry.push(*state);
+            Control::Continue
+        });
+        ScatterLayoutSequence3::with_positions(graph, sequence).unwrap().with_telemetry(telemetry)
+    }
+}
+
+impl Seeded for FruchtermanReingold3 {
+    fn with_seed(self, seed: u64) -> Self {
+        Self(self.0.with_seed(seed))
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::observer::Control;
+    use crate::engines::termination::{IterationState, Termination};
+    use ndarray::{Array2, ArrayView2};
+    use crate::engines::Refine;
+    #[cfg(feature = "svg")]
     use crate::layout::scatter::ScatterLayout;
-    use crate::render::svg::RenderSVG;
+    use crate::metrics::crossings;
+    #[cfg(feature = "svg")]
+    use crate::render::svg::{RenderOptions, RenderSVG};
     use crate::test::{defined_graphs, random_graph};
-    use crate::Graph;
-    use svg::Document;
+    use crate::{Float, Graph, Weighted};
+
+    #[test]
+    fn heavier_edges_are_shorter() {
+        let graph: Vec<(usize, usize, Float)> = vec![(0, 1, 1.), (1, 2, 10.)];
+        let layout = Weighted(&graph).layout(FruchtermanReingold::default());
+        let dist = |u: usize, v: usize| {
+            let (a, b) = (layout.coord(u), layout.coord(v));
+            ((a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2)).sqrt()
+        };
+        assert!(dist(1, 2) < dist(0, 1));
+    }
+
+    #[test]
+    fn observer_stops_early() {
+        let mut iterations = 0;
+        random_graph(10, 20, 31).layout_observed(
+            FruchtermanReingold::default(),
+            |_: ArrayView2<Float>, state: &IterationState| {
+                iterations = state.iteration;
+                if state.iteration < 5 { Control::Continue } else { Control::Stop }
+            },
+        );
+        assert_eq!(iterations, 5);
+    }
+
+    #[test]
+    fn lazy_frames_match_eager_sequence() {
+        let graph = random_graph(10, 20, 31);
+        let engine = || FruchtermanReingold::default().until(Termination::Iterations(20));
+        let sequence = (&graph).animate(engine());
+        let frames: Vec<_> = engine().frames(&graph).collect::<Result<_, _>>().unwrap();
+        assert_eq!(frames.len(), sequence.frames());
+        for (f, frame) in frames.iter().enumerate() {
+            assert_eq!(frame.coord(3).x(), sequence.coord(f, 3).x());
+        }
+    }
+
+    #[test]
+    fn simulation_ticks_like_compute() {
+        let graph = random_graph(10, 20, 31);
+        let engine = || FruchtermanReingold::default().until(Termination::Iterations(20));
+        let mut sim = engine().simulation(&graph).unwrap();
+        assert!(!sim.is_settled());
+        let mut ticks = 0;
+        while !sim.is_settled() {
+            sim.tick();
+            ticks += 1;
+        }
+        assert_eq!(ticks, 20);
+        assert_eq!(sim.positions(), (&graph).layout(engine()).view());
+
+        // cold nodes stay put until reheated
+        sim.tick();
+        sim.positions_mut()[[0, 0]] += 500.;
+        let dragged = sim.positions().to_owned();
+        sim.tick();
+        assert_eq!(sim.positions(), dragged);
+        sim.reheat();
+        sim.tick();
+        assert_ne!(sim.positions(), dragged);
+        assert_eq!(sim.into_layout().graph().nodes(), 10);
+    }
+
+    #[test]
+    fn jitter_escapes_symmetric_minima() {
+        // the planar triangulated triangle with all nodes on a line, which the forces never leave
+        let (_, graph) = defined_graphs().into_iter().find(|(name, _)| *name == "triangulated-triangle").unwrap();
+        let line = Array2::from_shape_fn((graph.nodes(), 2), |(n, d)| if d == 0 { n as Float * 100. } else { 0. });
+        let refine = |engine: FruchtermanReingold| {
+            engine.until(Termination::Iterations(200)).refine(&graph, line.clone(), |_: ArrayView2<Float>, _: &IterationState| Control::Continue)
+        };
+        let stuck = refine(FruchtermanReingold::default());
+        assert_eq!(stuck.bbox().height(), 0.);
+        let escaped = refine(FruchtermanReingold::default().with_jitter(0.1, 0.98));
+        assert!(escaped.bbox().height() > 100.);
+        assert_eq!(crossings(&graph, escaped.view()), 0);
+        assert_eq!(escaped.view(), refine(FruchtermanReingold::default().with_jitter(0.1, 0.98)).view());
+
+        // noise without decay stops once the layout is cold
+        let mut iterations = 0;
+        let engine = FruchtermanReingold::default().with_jitter(0.5, 1.).until(Termination::MaxDisplacement(0.01));
+        (&graph).layout_observed(engine, |_: ArrayView2<Float>, _: &IterationState| {
+            iterations += 1;
+            Control::Continue
+        });
+        assert!(iterations <= 202);
+    }
+
+    #[test]
+    fn measure_phases() {
+        let (layout, timings) = FruchtermanReingold::default()
+            .until(Termination::Iterations(10))
+            .compute_timed(random_graph(10, 20, 31));
+        assert_eq!(layout.graph().nodes(), 10);
+        assert!(timings.repulsion > std::time::Duration::ZERO);
+        assert_eq!(timings.phases().map(|(name, _)| name), ["init", "repulsion", "attraction", "integrate"]);
+    }
+
+    #[test]
+    fn heavy_nodes_get_more_space() {
+        let star: Vec<(usize, usize)> = (1..6).map(|n| (0, n)).collect();
+        let spread = |engine: FruchtermanReingold| {
+            let layout = (&star).layout(engine);
+            let center = layout.coord(0);
+            (1..6)
+                .map(|n| ((layout.coord(n).x() - center.x()).powi(2) + (layout.coord(n).y() - center.y()).powi(2)).sqrt())
+                .sum::<Float>()
+        };
+        assert!(spread(FruchtermanReingold::default().masses(vec![4.])) > spread(FruchtermanReingold::default()));
+    }
+
+    #[test]
+    fn layout_in_3d() {
+        // a path bends into 3D space, with neighbours closer than the ends
+        let path: Vec<(usize, usize)> = (0..5).map(|n| (n, n + 1)).collect();
+        let sequence = (&path).animate(FruchtermanReingold::default().until(Termination::Iterations(30)).in_3d());
+        assert_eq!(sequence.frames(), 31);
+        let layout = path.layout(FruchtermanReingold::default().in_3d());
+        let dist = |u: usize, v: usize| {
+            let (a, b) = (layout.coord(u), layout.coord(v));
+            ((a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2) + (a.z() - b.z()).powi(2)).sqrt()
+        };
+        assert!(layout.bbox().depth() > 0.);
+        assert!(dist(0, 1) < dist(0, 5));
+    }
+
+    #[test]
+    fn terminate_by_policy() {
+        let graph = random_graph(10, 20, 31);
+        let sequence = (&graph).animate(FruchtermanReingold::default().until(Termination::Iterations(20)));
+        assert_eq!(sequence.frames(), 21);
+
+        let sequence = graph.animate(
+            FruchtermanReingold::default()
+                .until(Termination::MaxDisplacement(1.).or(Termination::Iterations(1000))),
+        );
+        assert!(sequence.frames() < 1001);
+    }
+
+    #[test]
+    fn smoothing_reduces_jumps() {
+        let graph = random_graph(15, 30, 31);
+        let largest_jump = |engine: FruchtermanReingold| {
+            let sequence = (&graph).animate(engine.until(Termination::Iterations(50)));
+            (2..sequence.frames())
+                .flat_map(|f| (0..graph.nodes()).map(move |n| (f, n)))
+                .map(|(f, n)| {
+                    let (a, b) = (sequence.coord(f - 1, n), sequence.coord(f, n));
+                    ((a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2)).sqrt()
+                })
+                .fold(0., Float::max)
+        };
+        assert!(largest_jump(FruchtermanReingold::default().smoothing(0.8)) < largest_jump(FruchtermanReingold::default()));
+    }
 
     #[test]
+    #[cfg(feature = "svg")]
     fn fruchterman_reingold_no_panic() {
-        fn create_animation(graph: &impl Graph, name: &str) {
+        fn create_animation(graph: &impl Graph, directory: &std::path::Path, name: &str) {
             println!("Creating animation for {}", name);
 
             let sequence = graph.animate(FruchtermanReingold::default());
             let last: ScatterLayout<_> = ScatterLayout::new(graph, sequence.frame(sequence.frames() - 1).to_owned()).unwrap();
 
-            let document = Document::new()
-                .set("width", "800px")
-                .set("height", "800px")
-                .set("preserveAspectRatio", "none");
+            let options = RenderOptions::default();
 
             svg::save(
-                format!("examples/{}-final.svg", name),
-                &last.render(document.clone()).unwrap(),
+                directory.join(format!("{}-final.svg", name)),
+                &last.to_document(&options).unwrap(),
             )
                 .unwrap();
 
             svg::save(
-                format!("examples/{}.svg", name),
-                &sequence.render(document.clone()).unwrap(),
+                directory.join(format!("{}.svg", name)),
+                &sequence.to_document(&options).unwrap(),
             )
                 .unwrap();
         }
 
+        let directory = std::env::temp_dir().join(format!("rs-plode-animations-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
         for (name, graph) in defined_graphs() {
-            create_animation(&graph, name)
+            create_animation(&graph, &directory, name)
         }
 
         for n in (10..25).step_by(5) {
             for e in (20..50).step_by(5) {
-                create_animation(&random_graph(n, e, 31), &format!("random-{}-{}", n, e));
+                create_animation(&random_graph(n, e, 31), &directory, &format!("random-{}-{}", n, e));
             }
         }
+        std::fs::remove_dir_all(directory).unwrap();
     }
 }
@@ -0,0 +1,210 @@
+//! The repulsive forces of [`FruchtermanReingold`](super::fruchterman_reingold::FruchtermanReingold)
+//! computed by a compute shader, see [`Backend::Gpu`](super::backend::Backend::Gpu).
+//!
+//! The device is requested on first use and shared by all engines of the process. Every iteration
+//! uploads the positions and masses and reads the forces back, in single precision.
+use std::sync::mpsc::channel;
+use std::sync::OnceLock;
+
+use ndarray::{Array2, ArrayView2};
+use wgpu::util::DeviceExt;
+
+use crate::Float;
+
+/// The number of nodes per workgroup of the shader.
+const WORKGROUP_SIZE: usize = 64;
+
+/// The largest number of workgroups per dispatch, which every device supports.
+const MAX_WORKGROUPS: usize = 65535;
+
+/// Sums up the forces acting on one node per invocation, like the CPU kernels. Positions are
+/// padded to four dimensions with zeros, such that 2D and 3D layouts share the shader.
+const SHADER: &str = r#"
+struct Params {
+    nodes: u32,
+    k: f32,
+    // uniform buffers are padded to 16 bytes
+    padding: vec2<u32>,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> positions: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read> masses: array<f32>;
+@group(0) @binding(3) var<storage, read_write> forces: array<vec4<f32>>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let j = id.x;
+    if (j >= params.nodes) {
+        return;
+    }
+    let position = positions[j];
+    var force = vec4<f32>(0.0);
+    for (var i = 0u; i < params.nodes; i++) {
+        let delta = position - positions[i];
+        let r = length(delta);
+        let m = masses[i] * masses[j];
+        // coincident nodes, including the node itself, do not repel each other
+        if (r > 0.0 && r < 2.0 * params.k * sqrt(m)) {
+            force += delta / r * (m * params.k * params.k / r);
+        }
+    }
+    forces[j] = force;
+}
+"#;
+
+/// The device running the shader.
+struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl Gpu {
+    /// The device of the default adapter, if any.
+    async fn request() -> Option<Self> {
+        let instance = wgpu::Instance::default();
+        let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions::default()).await.ok()?;
+        let (device, queue) = adapter.request_device(&wgpu::DeviceDescriptor::default()).await.ok()?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("repulsion"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("repulsion"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        Some(Self { device, queue, pipeline })
+    }
+
+    #[allow(clippy::unnecessary_cast)] // with the f64 feature, `Float` is f64 instead
+    fn repulsion(&self, positions: ArrayView2<Float>, k: Float, masses: Option<&[Float]>) -> Option<Array2<Float>> {
+        let (nodes, dimensions) = positions.dim();
+        let padded: Vec<f32> = (0..nodes * 4)
+            .map(|x| match x % 4 < dimensions {
+                true => positions[[x / 4, x % 4]] as f32,
+                false => 0.,
+            })
+            .collect();
+        let masses: Vec<f32> = (0..nodes)
+            .map(|n| masses.and_then(|m| m.get(n)).copied().unwrap_or(1.) as f32)
+            .collect();
+        let params = [nodes as u32, (k as f32).to_bits(), 0, 0];
+
+        let storage = |label: &str, contents: &[u8], usage: wgpu::BufferUsages| {
+            self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor { label: Some(label), contents, usage })
+        };
+        let params = storage("params", bytemuck::cast_slice(&params), wgpu::BufferUsages::UNIFORM);
+        let positions = storage("positions", bytemuck::cast_slice(&padded), wgpu::BufferUsages::STORAGE);
+        let masses = storage("masses", bytemuck::cast_slice(&masses), wgpu::BufferUsages::STORAGE);
+        let size = (padded.len() * std::mem::size_of::<f32>()) as wgpu::BufferAddress;
+        let forces = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("forces"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("repulsion"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: params.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: positions.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: masses.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 3, resource: forces.as_entire_binding() },
+            ],
+        });
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("repulsion") });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some("repulsion"), timestamp_writes: None });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(nodes.div_ceil(WORKGROUP_SIZE) as u32, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&forces, 0, &readback, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = channel();
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, move |result| sender.send(result).unwrap_or(()));
+        self.device.poll(wgpu::PollType::wait_indefinitely()).ok()?;
+        receiver.recv().ok()?.ok()?;
+        let forces = slice.get_mapped_range().ok()?;
+        let forces: &[f32] = bytemuck::cast_slice(&forces);
+        Some(Array2::from_shape_fn((nodes, dimensions), |(n, d)| forces[n * 4 + d] as Float))
+    }
+}
+
+/// The device shared by all engines, requested on first use.
+fn gpu() -> Option<&'static Gpu> {
+    static GPU: OnceLock<Option<Gpu>> = OnceLock::new();
+    GPU.get_or_init(|| pollster::block_on(Gpu::request())).as_ref()
+}
+
+/// Whether a GPU adapter is available.
+pub(super) fn is_available() -> bool {
+    gpu().is_some()
+}
+
+/// Compute the repulsion on the GPU, or `None` if there is no adapter, the layout has more than 4
+/// dimensions or too many nodes for a single dispatch.
+pub(super) fn repulsion(positions: ArrayView2<Float>, k: Float, masses: Option<&[Float]>) -> Option<Array2<Float>> {
+    let (nodes, dimensions) = positions.dim();
+    if nodes == 0 || dimensions > 4 || nodes > WORKGROUP_SIZE * MAX_WORKGROUPS {
+        return None;
+    }
+    gpu()?.repulsion(positions, k, masses)
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array2;
+    use rand::{Rng, SeedableRng};
+
+    use super::{is_available, repulsion};
+    use crate::engines::backend::Backend;
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::repulsion::dense;
+    use crate::engines::EngineRng;
+    use crate::graph::generators::grid;
+    use crate::{Float, Graph};
+
+    #[test]
+    fn gpu_agrees_with_dense_kernel() {
+        if !is_available() {
+            eprintln!("skipped, no GPU adapter available");
+            return;
+        }
+        let mut rng = EngineRng::seed_from_u64(3);
+        let positions = Array2::from_shape_simple_fn((150, 3), || rng.gen_range(-400. ..400.));
+        let masses: Vec<Float> = (0..100).map(|n| 1. + n as Float / 10.).collect();
+        for masses in [None, Some(masses.as_slice())] {
+            let gpu = repulsion(positions.view(), 150., masses).unwrap();
+            let cpu = dense(positions.view(), 150., masses);
+            let scale = cpu.iter().fold(0., |m: Float, f| m.max(f.abs()));
+            assert!(gpu.iter().zip(&cpu).all(|(g, c)| (g - c).abs() <= 1e-3 * scale));
+        }
+    }
+
+    #[test]
+    fn gpu_layouts_fall_back_without_adapter() {
+        let layout = grid(4, 4).layout(FruchtermanReingold::default().backend(Backend::Gpu));
+        assert!(layout.view().iter().all(|x| x.is_finite()));
+        assert!(layout.bbox().width() > 0.);
+        if !is_available() {
+            let auto = grid(4, 4).layout(FruchtermanReingold::default());
+            assert_eq!(layout.view(), auto.view());
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use ndarray::{Array1, Array2, Axis};
+
+use crate::analysis::community::Partition;
+use crate::{Float, Graph};
+
+/// A force pulling every node towards the centroid of all nodes, which keeps disconnected or weakly
+/// connected parts of a graph from drifting apart.
+///
+/// The pull has a constant magnitude of `strength * k`, independent of the distance to the centroid,
+/// similar to the gravity of ForceAtlas2. Weighted by degree, nodes with many neighbours are pulled
+/// harder, which moves hubs to the center and keeps leaves on the outside.
+///
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::engines::gravity::Gravity;
+///
+/// let engine = FruchtermanReingold::default().gravity(Gravity::new(0.1).by_degree());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gravity {
+    strength: Float,
+    by_degree: bool,
+}
+
+impl Gravity {
+    /// A pull of the given strength relative to the ideal edge length.
+    pub fn new(strength: Float) -> Self {
+        Self {
+            strength: Float::max(strength, 0.),
+            by_degree: false,
+        }
+    }
+
+    /// Scale the pull of every node by its degree plus one.
+    pub fn by_degree(mut self) -> Self {
+        self.by_degree = true;
+        self
+    }
+
+    /// The magnitude of the pull on each node for the ideal edge length `k`.
+    pub(crate) fn magnitudes(&self, graph: &impl Graph, k: Float) -> Array1<Float> {
+        let mut magnitudes = Array1::from_elem(graph.nodes(), self.strength * k);
+        if self.by_degree {
+            let mut degrees = Array1::<Float>::ones(graph.nodes());
+            for (u, v) in graph.edges() {
+                degrees[u] += 1.;
+                degrees[v] += 1.;
+            }
+            magnitudes *= &degrees;
+        }
+        magnitudes
+    }
+}
+
+/// The V x D shaped displacements towards the centroid of the positions, with given magnitude per
+/// node. Nodes at the centroid are not displaced.
+pub(crate) fn gravitational_force(positions: &Array2<Float>, magnitudes: &Array1<Float>) -> Array2<Float> {
+    let Some(centroid) = positions.mean_axis(Axis(0)) else {
+        return Array2::zeros(positions.dim());
+    };
+    pull(&centroid - positions, |n| magnitudes[n])
+}
+
+/// The V x D shaped displacements towards the centroid of the community of each node, with given
+/// magnitude. Nodes without community are not displaced.
+pub(crate) fn community_force(positions: &Array2<Float>, partition: &Partition, magnitude: Float) -> Array2<Float> {
+    let labels = partition.labels();
+    let mut centroids = Array2::<Float>::zeros((partition.count(), positions.ncols()));
+    let mut sizes = vec![0.; partition.count()];
+    for (&c, row) in labels.iter().zip(positions.rows()) {
+        let mut centroid = centroids.row_mut(c);
+        centroid += &row;
+        sizes[c] += 1.;
+    }
+    for (mut centroid, size) in centroids.rows_mut().into_iter().zip(sizes) {
+        centroid /= Float::max(size, 1.);
+    }
+    let disp = Array2::from_shape_fn(positions.dim(), |(n, d)| {
+        labels.get(n).map_or(0., |&c| centroids[[c, d]] - positions[[n, d]])
+    });
+    pull(disp, |_| magnitude)
+}
+
+/// Scale each row of the displacements to the given magnitude, keeping zero rows.
+fn pull(mut disp: Array2<Float>, magnitude: impl Fn(usize) -> Float) -> Array2<Float> {
+    for (n, mut row) in disp.rows_mut().into_iter().enumerate() {
+        let distance = row.dot(&row).sqrt();
+        if distance > 0. {
+            row *= magnitude(n) / distance;
+        }
+    }
+    disp
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::{arr1, arr2};
+
+    use super::{community_force, gravitational_force, Gravity};
+    use crate::analysis::community::Partition;
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::termination::Termination;
+    use crate::layout::scatter::ScatterLayout;
+    use crate::{Float, Graph};
+
+    #[test]
+    fn pull_towards_centroid() {
+        let graph: Vec<(usize, usize)> = vec![(0, 1), (1, 2)];
+        assert_eq!(Gravity::new(0.5).magnitudes(&graph, 10.), arr1(&[5., 5., 5.]));
+        assert_eq!(Gravity::new(0.5).by_degree().magnitudes(&graph, 10.), arr1(&[10., 15., 10.]));
+        let force = gravitational_force(&arr2(&[[-4., 0.], [0., -3.], [4., 3.]]), &arr1(&[2., 2., 5.]));
+        assert_eq!(force, arr2(&[[2., 0.], [0., 2.], [-4., -3.]]));
+
+        // two components and isolated nodes stay closer together
+        let graph: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (6, 6), (7, 7)];
+        let engine = || FruchtermanReingold::default().until(Termination::Iterations(100));
+        let extent = |layout: ScatterLayout<Vec<(usize, usize)>>| {
+            let bbox = layout.bbox();
+            Float::hypot(bbox.width(), bbox.height())
+        };
+        let free = extent(graph.clone().layout(engine()));
+        let pulled = extent(graph.clone().layout(engine().gravity(Gravity::new(0.5))));
+        assert!(pulled < free, "{pulled} < {free}");
+
+        // nodes 0 and 2 pull together, node 3 has no community
+        let partition = Partition::new(vec![0, 1, 0]);
+        let force = community_force(&arr2(&[[-4., 0.], [5., 5.], [4., 0.], [1., 1.]]), &partition, 3.);
+        assert_eq!(force, arr2(&[[3., 0.], [0., 0.], [-3., 0.], [0., 0.]]));
+        let communities = |engine: FruchtermanReingold| {
+            let layout = graph.clone().layout(engine.until(Termination::Iterations(100)));
+            (layout.coord(0).x() - layout.coord(4).x()).abs() + (layout.coord(0).y() - layout.coord(4).y()).abs()
+        };
+        // pull the two triangles into one cluster
+        let together = Partition::new(vec![0, 0, 0, 0, 0, 0, 1, 2]);
+        assert!(communities(FruchtermanReingold::default().communities(together, 1.)) < communities(FruchtermanReingold::default()));
+    }
+}
@@ -0,0 +1,222 @@
+//! Layouts of graphs that change over time.
+use ndarray::{Array2, ArrayView2, Axis};
+
+use crate::graph::AdjacencyGraph;
+use crate::layout::scatter::ScatterLayout;
+use crate::{Engine, Float};
+
+use super::observer::Control;
+use super::termination::IterationState;
+use super::{Refine, DIRECTIONS};
+
+/// Keeps the layout of an evolving graph: nodes and edges are added and removed one by one and
+/// every [`update`](Self::update) refines the previous positions instead of starting over from
+/// random ones, such that unchanged parts of the graph stay in place.
+///
+/// The first update lays out the graph with the wrapped engine. Later updates place each new node
+/// at the barycenter of its neighbours that have a position already, or next to the centroid of
+/// the layout if it has none, and let the engine refine all positions.
+///
+/// Nodes are indexed consecutively: new nodes get the next free index and removing a node shifts
+/// the indices of all later nodes down by one, like [`Vec::remove`].
+///
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::engines::incremental::IncrementalEngine;
+///
+/// let mut engine = IncrementalEngine::new(FruchtermanReingold::default());
+/// let (a, b) = (engine.add_node(), engine.add_node());
+/// engine.add_edge(a, b).unwrap();
+/// engine.update();
+///
+/// let c = engine.add_node();
+/// engine.add_edge(b, c).unwrap();
+/// let after = engine.update();
+/// assert_eq!(after.view().nrows(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IncrementalEngine<E> {
+    engine: E,
+    nodes: usize,
+    edges: Vec<(usize, usize)>,
+    /// the positions of the nodes of the last update, which precede all nodes added since
+    positions: Option<Array2<Float>>,
+}
+
+impl<E> IncrementalEngine<E> {
+    pub fn new(engine: E) -> Self {
+        Self {
+            engine,
+            nodes: 0,
+            edges: Vec::new(),
+            positions: None,
+        }
+    }
+
+    /// The current number of nodes.
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// The current edges.
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    /// Add a node without edges and return its index.
+    pub fn add_node(&mut self) -> usize {
+        self.nodes += 1;
+        self.nodes - 1
+    }
+
+    /// Add an edge between existing nodes.
+    pub fn add_edge(&mut self, source: usize, target: usize) -> Result<(), String> {
+        if let Some(n) = [source, target].into_iter().find(|&n| n >= self.nodes) {
+            return Err(format!("Node {} does not exist, the graph has {} nodes", n, self.nodes));
+        }
+        self.edges.push((source, target));
+        Ok(())
+    }
+
+    /// Remove one edge between the nodes, in either direction, and return whether there was one.
+    pub fn remove_edge(&mut self, source: usize, target: usize) -> bool {
+        let found = self
+            .edges
+            .iter()
+            .position(|&(u, v)| (u, v) == (source, target) || (v, u) == (source, target));
+        found.map(|i| self.edges.remove(i)).is_some()
+    }
+
+    /// Remove the node together with its edges. All later nodes move down by one index.
+    pub fn remove_node(&mut self, node: usize) -> Result<(), String> {
+        if node >= self.nodes {
+            return Err(format!("Node {} does not exist, the graph has {} nodes", node, self.nodes));
+        }
+        self.nodes -= 1;
+        let shift = |n: usize| if n > node { n - 1 } else { n };
+        self.edges.retain(|&(u, v)| u != node && v != node);
+        for (u, v) in self.edges.iter_mut() {
+            (*u, *v) = (shift(*u), shift(*v));
+        }
+        if let Some(positions) = &mut self.positions {
+            if node < positions.nrows() {
+                positions.remove_index(Axis(0), node);
+            }
+        }
+        Ok(())
+    }
+
+    /// The graph of the current nodes and edges.
+    pub fn graph(&self) -> AdjacencyGraph {
+        AdjacencyGraph::from_edges(self.nodes, self.edges.iter().copied()).unwrap()
+    }
+
+    /// Extend the positions of the last update by positions for the nodes added since.
+    fn place_new_nodes(&self, positions: ArrayView2<Float>) -> Array2<Float> {
+        let known = positions.nrows();
+        let spread = spread(&self.edges, positions);
+        let centroid = positions.mean_axis(Axis(0)).map_or([0., 0.], |c| [c[0], c[1]]);
+        let mut placed = Array2::zeros((self.nodes, 2));
+        placed.slice_mut(ndarray::s![..known, ..]).assign(&positions);
+        for n in known..self.nodes {
+            let neighbours: Vec<usize> = self
+                .edges
+                .iter()
+                .filter_map(|&(u, v)| match (u == n, v == n) {
+                    (true, false) => Some(v),
+                    (false, true) => Some(u),
+                    _ => None,
+                })
+                .filter(|&m| m < known)
+                .collect();
+            let center = match neighbours.is_empty() {
+                true => centroid,
+                false => {
+                    let sum = neighbours.iter().fold([0., 0.], |s, &m| [s[0] + positions[[m, 0]], s[1] + positions[[m, 1]]]);
+                    [sum[0] / neighbours.len() as Float, sum[1] / neighbours.len() as Float]
+                }
+            };
+            // move aside, such that new nodes never coincide with their neighbours or each other
+            let direction = DIRECTIONS[n % DIRECTIONS.len()];
+            placed[[n, 0]] = center[0] + spread * direction[0];
+            placed[[n, 1]] = center[1] + spread * direction[1];
+        }
+        placed
+    }
+}
+
+/// Half the mean length of the edges between positioned nodes, or 1 if there are none.
+fn spread(edges: &[(usize, usize)], positions: ArrayView2<Float>) -> Float {
+    let known = positions.nrows();
+    let lengths: Vec<Float> = edges
+        .iter()
+        .filter(|&&(u, v)| u < known && v < known)
+        .map(|&(u, v)| ((positions[[u, 0]] - positions[[v, 0]]).powi(2) + (positions[[u, 1]] - positions[[v, 1]]).powi(2)).sqrt())
+        .collect();
+    match lengths.is_empty() {
+        true => 1.,
+        false => Float::max(lengths.iter().sum::<Float>() / lengths.len() as Float / 2., 1e-3),
+    }
+}
+
+impl<E> IncrementalEngine<E>
+where
+    E: Refine + Clone + Engine<Layout<AdjacencyGraph> = ScatterLayout<AdjacencyGraph>>,
+{
+    /// Lay out the current graph, starting from the positions of the previous update.
+    pub fn update(&mut self) -> ScatterLayout<AdjacencyGraph> {
+        let graph = self.graph();
+        let layout = match &self.positions {
+            None => self.engine.clone().compute(graph),
+            Some(positions) => {
+                let start = self.place_new_nodes(positions.view());
+                let noop = |_: ArrayView2<Float>, _: &IterationState| Control::Continue;
+                self.engine.clone().refine(graph, start, noop)
+            }
+        };
+        self.positions = Some(layout.view().to_owned());
+        layout
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IncrementalEngine;
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::termination::Termination;
+    use crate::{Float, Graph};
+
+    #[test]
+    fn incremental_updates() {
+        let mut engine = IncrementalEngine::new(FruchtermanReingold::new(50., 3).until(Termination::Iterations(50)));
+        for _ in 0..6 {
+            engine.add_node();
+        }
+        for n in 0..6 {
+            engine.add_edge(n, (n + 1) % 6).unwrap();
+        }
+        assert!(engine.add_edge(0, 6).is_err());
+        let ring = engine.update();
+
+        // a new leaf starts next to its neighbour and the ring barely moves
+        let leaf = engine.add_node();
+        engine.add_edge(leaf, 2).unwrap();
+        let start = engine.place_new_nodes(ring.view());
+        let offset = (start[[leaf, 0]] - ring.coord(2).x()).hypot(start[[leaf, 1]] - ring.coord(2).y());
+        assert!(offset > 0. && offset < 50.);
+        let grown = engine.update();
+        let moved = (0..6).map(|n| (grown.coord(n).x() - ring.coord(n).x()).hypot(grown.coord(n).y() - ring.coord(n).y())).fold(0., Float::max);
+        assert!(moved < 50., "{moved}");
+
+        // removing a node shifts the later ones down, including their positions
+        assert!(engine.remove_edge(0, 5));
+        assert!(!engine.remove_edge(0, 5));
+        engine.remove_node(1).unwrap();
+        assert_eq!(engine.nodes(), 6);
+        assert_eq!(engine.edges(), &[(1, 2), (2, 3), (3, 4), (5, 1)]);
+        assert_eq!(engine.positions.as_ref().unwrap().row(1), grown.view().row(2));
+        let shrunk = engine.update();
+        assert_eq!(shrunk.graph().edges().count(), 4);
+        assert!(engine.remove_node(6).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use ndarray::{Array2, Axis};
+
+use crate::Float;
+
+/// How force directed engines turn the forces acting on the nodes into their movement.
+///
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::engines::integrator::Integrator;
+///
+/// let engine = FruchtermanReingold::default().integrator(Integrator::SemiImplicitEuler { friction: 0.3 });
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Integrator {
+    /// Move each node along its force by at most the temperature, as in the original paper. Nodes
+    /// have no momentum besides the optional smoothing, which lets stiff graphs oscillate.
+    #[default]
+    Displacement,
+    /// Accumulate the forces into a velocity that loses the given fraction to friction in every
+    /// iteration, then move each node by its velocity, limited to the temperature. Momentum carries
+    /// nodes through small local minima while friction damps oscillations. The friction is clamped
+    /// to `[0, 1]`, where 1 degrades to [`Integrator::Displacement`].
+    SemiImplicitEuler { friction: Float },
+}
+
+impl Integrator {
+    /// The V x D shaped velocity of the next iteration from the current one and the forces, given
+    /// the temperature `t` and the smoothing `inertia` of displacement integration.
+    pub(crate) fn velocity(&self, velocity: &Array2<Float>, force: &Array2<Float>, t: Float, inertia: Float) -> Array2<Float> {
+        match *self {
+            Integrator::Displacement => {
+                let displacement = limit(force.clone(), t);
+                // blend with the previous displacement to smooth out jumps between iterations
+                velocity * inertia + &displacement * (1. - inertia)
+            }
+            Integrator::SemiImplicitEuler { friction } => {
+                limit(velocity * (1. - friction.clamp(0., 1.)) + force, t)
+            }
+        }
+    }
+}
+
+/// Shorten all rows longer than `t` to length `t`.
+fn limit(vectors: Array2<Float>, t: Float) -> Array2<Float> {
+    let norm = (&vectors * &vectors)
+        .sum_axis(Axis(1))
+        .mapv(|x: Float| Float::max(1., x).sqrt());
+    let scale = norm.mapv(|x: Float| Float::min(t, x));
+    (&vectors / &norm.insert_axis(Axis(1))) * &scale.insert_axis(Axis(1))
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+
+    use super::Integrator;
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::termination::Termination;
+    use crate::graph::generators::grid;
+    use crate::Graph;
+
+    #[test]
+    fn friction_damps_momentum() {
+        let force = array![[3., 4.], [0., 0.]];
+        let still = array![[0., 0.], [0., 0.]];
+        assert_eq!(Integrator::Displacement.velocity(&still, &force, 1., 0.), array![[0.6, 0.8], [0., 0.]]);
+        let euler = Integrator::SemiImplicitEuler { friction: 0.5 };
+        assert_eq!(euler.velocity(&still, &force, 10., 0.), force);
+        // without force the node coasts, losing half its velocity per iteration
+        assert_eq!(euler.velocity(&force, &still, 10., 0.), array![[1.5, 2.], [0., 0.]]);
+        assert_eq!(euler.velocity(&force, &force, 1., 0.), array![[0.6, 0.8], [0., 0.]]);
+
+        // the temperature still bounds the movement, hence the layout settles
+        let engine = FruchtermanReingold::default().integrator(euler);
+        let sequence = grid(4, 4).animate(engine.until(Termination::Iterations(50)));
+        for state in sequence.telemetry().unwrap().states() {
+            assert!(state.max_displacement <= state.temperature * 1.001);
+        }
+    }
+}
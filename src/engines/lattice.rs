@@ -0,0 +1,155 @@
+//! Trivial deterministic layouts placing nodes by index, ignoring the graph topology.
+//!
+//! They are useful as baselines, as initializers for iterative engines and for graphs where a
+//! stable placement matters more than the structure.
+use crate::consts::PI;
+
+use ndarray::Array2;
+
+use crate::{Float, Graph};
+
+use super::Placement;
+
+/// Places nodes on a sunflower (Vogel) spiral, such that neighbouring indices are close to each
+/// other and the nodes cover a disc evenly.
+#[derive(Clone, Debug)]
+pub struct Spiral {
+    k: Float,
+}
+
+impl Spiral {
+    /// Create a spiral where nodes are roughly `k` apart.
+    pub fn new(k: Float) -> Self {
+        Self { k }
+    }
+}
+
+impl Default for Spiral {
+    fn default() -> Self {
+        Self::new(150.)
+    }
+}
+
+impl Placement for Spiral {
+    fn place(&self, graph: &impl Graph) -> Array2<Float> {
+        let golden_angle = PI * (3. - Float::sqrt(5.));
+        // scale such that the area per node matches a disc of diameter k
+        let scale = self.k / 2.;
+        Array2::from_shape_fn((graph.nodes(), 2), |(n, d)| {
+            let (r, theta) = (scale * (n as Float).sqrt(), n as Float * golden_angle);
+            if d == 0 {
+                r * theta.cos()
+            } else {
+                r * theta.sin()
+            }
+        })
+    }
+}
+
+/// Places nodes row by row on a square grid.
+#[derive(Clone, Debug)]
+pub struct Grid {
+    k: Float,
+    columns: Option<usize>,
+}
+
+impl Grid {
+    /// Create a grid with spacing `k`, as square as possible.
+    pub fn new(k: Float) -> Self {
+        Self { k, columns: None }
+    }
+
+    /// Fix the number of columns.
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = Some(usize::max(columns, 1));
+        self
+    }
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new(150.)
+    }
+}
+
+impl Placement for Grid {
+    fn place(&self, graph: &impl Graph) -> Array2<Float> {
+        let columns = self.columns.unwrap_or_else(|| usize::max(1, (graph.nodes() as Float).sqrt().ceil() as usize));
+        Array2::from_shape_fn((graph.nodes(), 2), |(n, d)| {
+            if d == 0 {
+                (n % columns) as Float * self.k
+            } else {
+                (n / columns) as Float * self.k
+            }
+        })
+    }
+}
+
+/// Places nodes row by row on a hexagonal (triangular) lattice, where each node has six
+/// neighbours at distance `k`.
+#[derive(Clone, Debug)]
+pub struct Hexagonal {
+    k: Float,
+}
+
+impl Hexagonal {
+    pub fn new(k: Float) -> Self {
+        Self { k }
+    }
+}
+
+impl Default for Hexagonal {
+    fn default() -> Self {
+        Self::new(150.)
+    }
+}
+
+impl Placement for Hexagonal {
+    fn place(&self, graph: &impl Graph) -> Array2<Float> {
+        let columns = usize::max(1, (graph.nodes() as Float).sqrt().ceil() as usize);
+        let row_height = self.k * Float::sqrt(3.) / 2.;
+        Array2::from_shape_fn((graph.nodes(), 2), |(n, d)| {
+            let (row, column) = (n / columns, n % columns);
+            if d == 0 {
+                (column as Float + 0.5 * (row % 2) as Float) * self.k
+            } else {
+                row as Float * row_height
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Grid, Hexagonal, Spiral};
+    use crate::engines::Placement;
+    use crate::Float;
+
+    fn distance(p: &ndarray::Array2<Float>, a: usize, b: usize) -> Float {
+        ((p[[a, 0]] - p[[b, 0]]).powi(2) + (p[[a, 1]] - p[[b, 1]]).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn lattice_spacing() {
+        let graph: Vec<(usize, usize)> = vec![(0, 8)];
+        let grid = Grid::new(10.).place(&graph);
+        assert_eq!((grid[[4, 0]], grid[[4, 1]]), (10., 10.));
+        assert_eq!(Grid::new(10.).columns(9).place(&graph)[[8, 1]], 0.);
+
+        let hex = Hexagonal::new(10.).place(&graph);
+        // node 4 in the second row has both nodes 1 and 2 above it at distance k
+        assert!((distance(&hex, 4, 1) - 10.).abs() < 1e-4);
+        assert!((distance(&hex, 4, 2) - 10.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn spiral_is_distinct() {
+        let graph: Vec<(usize, usize)> = vec![(0, 99)];
+        let spiral = Spiral::new(10.).place(&graph);
+        for a in 0..100 {
+            for b in a + 1..100 {
+                assert!(distance(&spiral, a, b) > 1.);
+            }
+        }
+    }
+}
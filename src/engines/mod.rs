@@ -1,2 +1,148 @@
+use ndarray::Array2;
+
+use crate::layout::scatter::{ScatterLayout, ScatterLayoutSequence};
+use crate::{Engine, Float, Graph};
+
+use self::observer::Observer;
+
+pub mod annealing;
+pub mod arc;
+// needs threads, which wasm32-unknown-unknown lacks
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub mod asynchronous;
+pub mod backend;
+pub mod bipartite;
+pub mod bounds;
+pub mod contraction;
+pub mod convergence;
+pub mod cooling;
 pub mod fruchterman_reingold;
+#[cfg(feature = "gpu")]
+mod gpu;
+pub mod gravity;
+pub mod incremental;
+mod instant;
+pub mod integrator;
+pub mod lattice;
+pub mod multilevel;
+pub mod observer;
+mod repulsion;
+pub mod shell;
+pub mod telemetry;
+pub mod termination;
+
+/// The random number generator of all stochastic engines.
+///
+/// It is the generator behind `StdRng` of rand 0.8, but unlike `StdRng` its algorithm is guaranteed
+/// not to change, which keeps seeded layouts reproducible across releases.
+pub(crate) type EngineRng = rand_chacha::ChaCha12Rng;
+
+/// Eight unit vectors, 45 degrees apart, counterclockwise from the x axis.
+///
+/// Engines use them instead of `sin` and `cos`, whose results differ between the math libraries
+/// of different platforms, to move nodes aside deterministically.
+pub(crate) const DIRECTIONS: [[Float; 2]; 8] = {
+    use crate::consts::FRAC_1_SQRT_2 as D;
+    [[1., 0.], [D, D], [0., 1.], [-D, D], [-1., 0.], [-D, -D], [0., -1.], [D, -D]]
+};
+
+/// Engines that draw random numbers, e.g. for their initial positions.
+///
+/// The same seed, graph and configuration yield bit-identical layouts on every platform and in
+/// every release: engines draw from a generator with a fixed algorithm, never iterate hash maps and
+/// only use floating point operations that IEEE 754 rounds exactly. Changing the layout of a given
+/// seed is a breaking change, such that snapshots of layouts can be checked into CI.
+/// The `f64` feature yields different, but equally reproducible layouts.
+///
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::engines::Seeded;
+/// use rs_plode::Graph;
+///
+/// let graph = vec![(0usize, 1usize), (1, 2), (2, 0)];
+/// let a = graph.clone().layout(FruchtermanReingold::default().with_seed(7));
+/// let b = graph.layout(FruchtermanReingold::default().with_seed(7));
+/// assert_eq!(a.view(), b.view());
+/// ```
+pub trait Seeded: Engine {
+    /// Restart the random number generator from the given seed.
+    fn with_seed(self, seed: u64) -> Self;
+}
+
+/// Engines that compute the node positions directly, without iterating.
+///
+/// Every placement is an [`Engine`] whose sequences consist of a single frame. Observers are
+/// never notified.
+pub trait Placement {
+    /// Compute the V x 2 shaped node positions.
+    fn place(&self, graph: &impl Graph) -> Array2<Float>;
+}
+
+/// Engines that can start from given positions instead of their own initialization, e.g. to improve
+/// a layout that is roughly right already.
+pub trait Refine: Engine {
+    /// Improve the V x 2 shaped positions, notifying the observer about every iteration.
+    fn refine<G: Graph, O: Observer>(self, graph: G, positions: Array2<Float>, observer: O) -> ScatterLayout<G>;
+}
+
+impl<P: Placement> Engine for P {
+    type Layout<G: Graph> = ScatterLayout<G>;
+    type LayoutSequence<G: Graph> = ScatterLayoutSequence<G>;
+
+    fn compute<G: Graph>(self, graph: G) -> Self::Layout<G> {
+        let pos = self.place(&graph);
+        ScatterLayout::new(graph, pos).unwrap()
+    }
+
+    fn compute_observed<G: Graph, O: Observer>(self, graph: G, _: O) -> Self::Layout<G> {
+        self.compute(graph)
+    }
+
+    fn animate<G: Graph>(self, graph: G) -> Self::LayoutSequence<G> {
+        let pos = self.place(&graph);
+        ScatterLayoutSequence::new(graph, vec![pos]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::ArrayView2;
+
+    use super::annealing::DavidsonHarel;
+    use super::contraction::ChainContraction;
+    use super::fruchterman_reingold::FruchtermanReingold;
+    use super::multilevel::Multilevel;
+    use super::termination::Termination;
+    use super::Seeded;
+    use crate::graph::generators::grid;
+    use crate::{Float, Graph};
+
+    /// FNV-1a hash of the bit patterns of all coordinates.
+    fn fingerprint(positions: ArrayView2<Float>) -> u64 {
+        positions.iter().fold(0xcbf29ce484222325, |hash, x| {
+            x.to_bits().to_le_bytes().iter().fold(hash, |h, &b| (h ^ b as u64).wrapping_mul(0x100000001b3))
+        })
+    }
+
+    #[test]
+    fn seeded_layouts_are_reproducible() {
+        let fr = || FruchtermanReingold::default().until(Termination::Iterations(50));
+        let layout = grid(3, 4).layout(fr().with_seed(7));
+        assert_eq!(layout.view(), grid(3, 4).layout(FruchtermanReingold::new(150., 7).until(Termination::Iterations(50))).view());
+        assert_ne!(layout.view(), grid(3, 4).layout(fr().with_seed(8)).view());
 
+        // snapshots of single precision layouts, which must only change with a breaking release
+        if cfg!(feature = "f64") {
+            return;
+        }
+        assert_eq!(fingerprint(layout.view()), 9109073435781261246);
+        assert_eq!(fingerprint(grid(3, 4).layout(fr().in_3d().with_seed(7)).view()), 1891645956696730557);
+        let annealing = DavidsonHarel::default().until(Termination::Iterations(5)).with_seed(7);
+        assert_eq!(fingerprint(grid(3, 4).layout(annealing).view()), 16410801227407347290);
+        let multilevel = Multilevel::new(fr()).min_nodes(10).with_seed(7);
+        assert_eq!(fingerprint(grid(6, 6).layout(multilevel).view()), 14980083766286967855);
+        // a triangle with a pendant node, whose contracted self loop is placed around a square
+        let contraction = ChainContraction::new(fr()).with_seed(7);
+        assert_eq!(fingerprint(vec![(0usize, 1usize), (1, 2), (2, 0), (0, 3)].layout(contraction).view()), 5985189095743624521);
+    }
+}
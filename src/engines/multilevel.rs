@@ -0,0 +1,311 @@
+//! Multilevel layouts of large graphs.
+//!
+//! Original paper: Walshaw, A Multilevel Algorithm for Force-Directed Graph-Drawing,
+//! https://doi.org/10.7155/jgaa.00070
+use std::collections::BTreeMap;
+
+use ndarray::{Array2, ArrayView2};
+
+use crate::graph::GraphCache;
+use crate::layout::scatter::{ScatterLayout, ScatterLayoutSequence};
+use crate::{EdgeIter, Engine, Float, Graph};
+
+use super::observer::{Control, Observer};
+use super::termination::IterationState;
+use super::{Refine, Seeded, DIRECTIONS};
+
+/// A coarsened graph, whose nodes stand for groups of nodes of the finer graph.
+#[derive(Clone, Debug)]
+pub struct CoarseGraph {
+    nodes: usize,
+    edges: Vec<(usize, usize)>,
+    /// the number of original edges merged into each edge, which guides the matching
+    multiplicity: Vec<Float>,
+}
+
+impl Graph for CoarseGraph {
+    type Edges<'a> = EdgeIter<'a>;
+
+    fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.edges.iter().copied()
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edges.len())
+    }
+}
+
+/// A coarsening step, mapping the nodes of the finer graph onto the nodes of the coarse one.
+struct Level {
+    graph: CoarseGraph,
+    /// the coarse node of each node of the finer graph
+    parent: Vec<usize>,
+}
+
+/// Merge pairs of neighbours, preferring the edges that already stand for the most original edges
+/// (heavy edge matching). Nodes are visited by increasing degree, such that leaves are merged into
+/// their neighbours first.
+fn coarsen(nodes: usize, edges: &[(usize, usize)], multiplicity: &[Float]) -> Level {
+    let mut neighbours: Vec<Vec<(usize, Float)>> = vec![Vec::new(); nodes];
+    for (&(u, v), &m) in edges.iter().zip(multiplicity) {
+        if u != v {
+            neighbours[u].push((v, m));
+            neighbours[v].push((u, m));
+        }
+    }
+    let mut order: Vec<usize> = (0..nodes).collect();
+    order.sort_by_key(|&n| neighbours[n].len());
+
+    let mut parent = vec![usize::MAX; nodes];
+    let mut next = 0;
+    for u in order {
+        if parent[u] != usize::MAX {
+            continue;
+        }
+        let partner = neighbours[u]
+            .iter()
+            .filter(|&&(v, _)| parent[v] == usize::MAX)
+            .fold(None, |best: Option<(usize, Float)>, &(v, m)| match best {
+                Some((_, b)) if b >= m => best,
+                _ => Some((v, m)),
+            });
+        parent[u] = next;
+        if let Some((v, _)) = partner {
+            parent[v] = next;
+        }
+        next += 1;
+    }
+
+    let mut merged: BTreeMap<(usize, usize), Float> = BTreeMap::new();
+    for (&(u, v), &m) in edges.iter().zip(multiplicity) {
+        let (a, b) = (parent[u], parent[v]);
+        if a != b {
+            *merged.entry((a.min(b), a.max(b))).or_insert(0.) += m;
+        }
+    }
+    Level {
+        graph: CoarseGraph {
+            nodes: next,
+            edges: merged.keys().copied().collect(),
+            multiplicity: merged.values().copied().collect(),
+        },
+        parent,
+    }
+}
+
+/// Place the nodes of the finer graph at the position of their coarse node. The second node of a
+/// merged pair is moved aside by `spread`, such that forces between the pair are well defined.
+fn prolong(coarse: ArrayView2<Float>, parent: &[usize], spread: Float) -> Array2<Float> {
+    let mut placed = vec![false; coarse.nrows()];
+    let aside: Vec<bool> = parent.iter().map(|&c| std::mem::replace(&mut placed[c], true)).collect();
+    Array2::from_shape_fn((parent.len(), 2), |(n, d)| {
+        let p = coarse[[parent[n], d]];
+        match aside[n] {
+            false => p,
+            true => p + spread * DIRECTIONS[n % DIRECTIONS.len()][d],
+        }
+    })
+}
+
+/// A tenth of the mean edge length, or 1 for layouts without edges.
+fn spread(graph: &CoarseGraph, pos: ArrayView2<Float>) -> Float {
+    let lengths: Vec<Float> = graph
+        .edges
+        .iter()
+        .map(|&(u, v)| ((pos[[u, 0]] - pos[[v, 0]]).powi(2) + (pos[[u, 1]] - pos[[v, 1]]).powi(2)).sqrt())
+        .collect();
+    match lengths.is_empty() {
+        true => 1.,
+        false => Float::max(lengths.iter().sum::<Float>() / lengths.len() as Float / 10., 1e-3),
+    }
+}
+
+/// Wraps a force directed engine to lay out large graphs: the graph is coarsened repeatedly by
+/// merging pairs of neighbours, the coarsest graph is laid out by the engine and each finer graph
+/// starts from the positions of the next coarser one, which the engine only needs to refine.
+///
+/// Observers passed to `compute_observed` see the refinement of the original graph. Sequences
+/// hold the layout of each level, expanded to the original nodes, from the coarsest to the
+/// original graph.
+///
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::engines::multilevel::Multilevel;
+/// use rs_plode::graph::generators::grid;
+/// use rs_plode::Graph;
+///
+/// let layout = grid(10, 10).layout(Multilevel::new(FruchtermanReingold::default()).min_nodes(20));
+/// assert_eq!(layout.view().nrows(), 100);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Multilevel<E> {
+    engine: E,
+    min_nodes: usize,
+    max_levels: usize,
+}
+
+impl<E> Multilevel<E> {
+    pub fn new(engine: E) -> Self {
+        Self {
+            engine,
+            min_nodes: 50,
+            max_levels: 20,
+        }
+    }
+
+    /// Stop coarsening once a graph has at most this many nodes. Defaults to 50.
+    pub fn min_nodes(mut self, nodes: usize) -> Self {
+        self.min_nodes = usize::max(nodes, 1);
+        self
+    }
+
+    /// Set the maximal number of coarsening steps. Defaults to 20.
+    pub fn max_levels(mut self, levels: usize) -> Self {
+        self.max_levels = levels;
+        self
+    }
+
+    /// Coarsen until the graph is small enough, or the matching stalls, e.g. for stars.
+    fn levels(&self, graph: &GraphCache) -> Vec<Level> {
+        let mut levels: Vec<Level> = Vec::new();
+        let mut nodes = graph.nodes();
+        let mut edges: Vec<(usize, usize)> = graph.edges().collect();
+        let mut multiplicity = vec![1.; edges.len()];
+        while levels.len() < self.max_levels && nodes > self.min_nodes {
+            let level = coarsen(nodes, &edges, &multiplicity);
+            if level.graph.nodes as Float > 0.9 * nodes as Float {
+                break;
+            }
+            nodes = level.graph.nodes;
+            edges = level.graph.edges.clone();
+            multiplicity = level.graph.multiplicity.clone();
+            levels.push(level);
+        }
+        levels
+    }
+}
+
+impl<E> Multilevel<E>
+where
+    E: Refine + Clone + Engine<Layout<CoarseGraph> = ScatterLayout<CoarseGraph>>,
+{
+    /// Lay out all levels, passing the positions of each level but the original one to `frame`.
+    fn run<G: Graph, O: Observer>(self, graph: G, observer: O, mut frame: impl FnMut(&[Level], usize, ArrayView2<Float>)) -> ScatterLayout<G> {
+        let cache = GraphCache::for_layout(&graph);
+        let levels = self.levels(&cache);
+        let Some(coarsest) = levels.last() else {
+            // nothing to coarsen, lay out a copy such that the engine sees a coarse graph
+            let edges: Vec<(usize, usize)> = cache.edges().collect();
+            let copy = CoarseGraph { nodes: cache.nodes(), multiplicity: vec![1.; edges.len()], edges };
+            let layout = self.engine.compute_observed(copy, observer);
+            return ScatterLayout::new(graph, layout.view().to_owned()).unwrap();
+        };
+        let mut pos = self.engine.clone().compute(coarsest.graph.clone()).view().to_owned();
+        frame(&levels, levels.len() - 1, pos.view());
+        for i in (1..levels.len()).rev() {
+            let finer = &levels[i - 1].graph;
+            let prolonged = prolong(pos.view(), &levels[i].parent, spread(&levels[i].graph, pos.view()));
+            let noop = |_: ArrayView2<Float>, _: &IterationState| Control::Continue;
+            pos = self.engine.clone().refine(finer.clone(), prolonged, noop).view().to_owned();
+            frame(&levels, i - 1, pos.view());
+        }
+        let prolonged = prolong(pos.view(), &levels[0].parent, spread(&levels[0].graph, pos.view()));
+        self.engine.refine(graph, prolonged, observer)
+    }
+}
+
+impl<E> Engine for Multilevel<E>
+where
+    E: Refine + Clone + Engine<Layout<CoarseGraph> = ScatterLayout<CoarseGraph>>,
+{
+    type Layout<G: Graph> = ScatterLayout<G>;
+    type LayoutSequence<G: Graph> = ScatterLayoutSequence<G>;
+
+    fn compute<G: Graph>(self, graph: G) -> Self::Layout<G> {
+        self.compute_observed(graph, |_: ArrayView2<Float>, _: &IterationState| Control::Continue)
+    }
+
+    fn compute_observed<G: Graph, O: Observer>(self, graph: G, observer: O) -> Self::Layout<G> {
+        self.run(graph, observer, |_, _, _| {})
+    }
+
+    fn animate<G: Graph>(self, graph: G) -> Self::LayoutSequence<G> {
+        let mut frames = Vec::new();
+        let noop = |_: ArrayView2<Float>, _: &IterationState| Control::Continue;
+        let layout = self.run(graph, noop, |levels: &[Level], level: usize, pos: ArrayView2<Float>| {
+            let original = levels[0].parent.len();
+            frames.push(Array2::from_shape_fn((original, 2), |(n, d)| {
+                let node = levels[..=level].iter().fold(n, |node, l| l.parent[node]);
+                pos[[node, d]]
+            }));
+        });
+        frames.push(layout.view().to_owned());
+        ScatterLayoutSequence::new(layout.graph, frames).unwrap()
+    }
+}
+
+impl<E> Seeded for Multilevel<E>
+where
+    E: Seeded + Refine + Clone + Engine<Layout<CoarseGraph> = ScatterLayout<CoarseGraph>>,
+{
+    fn with_seed(self, seed: u64) -> Self {
+        Self {
+            engine: self.engine.with_seed(seed),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{coarsen, prolong, Multilevel};
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::termination::Termination;
+    use crate::graph::generators::{grid, star};
+    use crate::graph::GraphCache;
+    use crate::{Engine, Float};
+    use crate::Graph;
+
+    #[test]
+    fn coarsen_by_heavy_edge_matching() {
+        // a triangle with a heavy edge between 2 and 0
+        let level = coarsen(3, &[(0, 1), (1, 2), (2, 0)], &[1., 1., 5.]);
+        assert_eq!(level.parent, vec![0, 1, 0]);
+        assert_eq!((level.graph.edges, level.graph.multiplicity), (vec![(0, 1)], vec![2.]));
+
+        let engine = Multilevel::new(FruchtermanReingold::default()).min_nodes(10);
+        let levels = engine.levels(&GraphCache::new(&grid(8, 8)).unwrap());
+        assert!(levels.len() >= 2);
+        assert!(levels.last().unwrap().graph.nodes <= 10);
+        // stars cannot be coarsened by matching
+        assert!(engine.levels(&GraphCache::new(&star(30)).unwrap()).len() <= 1);
+
+        let pos = prolong(arr2(&[[0., 0.], [10., 0.]]).view(), &[0, 0, 1], 1.);
+        assert_eq!((pos[[0, 0]], pos[[0, 1]]), (0., 0.));
+        assert!((pos[[1, 0]].powi(2) + pos[[1, 1]].powi(2) - 1.).abs() < 1e-5);
+        assert_eq!((pos[[2, 0]], pos[[2, 1]]), (10., 0.));
+    }
+
+    #[test]
+    fn multilevel_layout() {
+        let graph = grid(8, 8);
+        let engine = Multilevel::new(FruchtermanReingold::new(50., 1).until(Termination::Iterations(50))).min_nodes(10);
+        let layout = graph.clone().layout(engine.clone());
+        assert!(layout.view().iter().all(|p| p.is_finite()));
+        // neighbours end up closer than the average pair of nodes
+        let distance = |u: usize, v: usize| ((layout.coord(u).x() - layout.coord(v).x()).powi(2) + (layout.coord(u).y() - layout.coord(v).y()).powi(2)).sqrt();
+        let edges = graph.edges().map(|(u, v)| distance(u, v)).sum::<Float>() / graph.edges().count() as Float;
+        let pairs = (0..64).flat_map(|u| (0..64).map(move |v| (u, v))).map(|(u, v)| distance(u, v)).sum::<Float>() / (64. * 63.);
+        assert!(edges < pairs / 2.);
+
+        let sequence = engine.animate(graph);
+        assert!(sequence.frames() >= 3);
+        assert_eq!(sequence.frame(0).nrows(), 64);
+    }
+}
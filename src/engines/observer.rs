@@ -0,0 +1,210 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ndarray::ArrayView2;
+
+use super::instant::Instant;
+use super::termination::IterationState;
+use crate::Float;
+
+/// Decision of an [`Observer`] whether the engine shall go on iterating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Stop,
+}
+
+/// Gets notified by iterative engines after every iteration.
+///
+/// Observers see the current V x 2 shaped node positions together with the engines convergence
+/// state and may stop the engine early. Closures with a matching signature implement this trait.
+pub trait Observer {
+    fn observe(&mut self, positions: ArrayView2<Float>, state: &IterationState) -> Control;
+}
+
+impl<F> Observer for F
+where
+    F: FnMut(ArrayView2<Float>, &IterationState) -> Control,
+{
+    fn observe(&mut self, positions: ArrayView2<Float>, state: &IterationState) -> Control {
+        self(positions, state)
+    }
+}
+
+/// Cooperative cancellation of a running engine, e.g. from the UI thread of a GUI app.
+///
+/// Clones share the same flag. As an [`Observer`] the token stops the engine after the current
+/// iteration once cancelled, which then returns the layout reached so far.
+///
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::engines::observer::CancellationToken;
+/// use rs_plode::Graph;
+///
+/// let token = CancellationToken::new();
+/// token.cancel();
+/// let layout = vec![(0, 1), (1, 2)].layout_observed(FruchtermanReingold::default(), token.clone());
+/// assert_eq!(layout.view().nrows(), 3);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask all engines observed by this token or its clones to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Share an existing flag, where `true` means cancelled.
+impl From<Arc<AtomicBool>> for CancellationToken {
+    fn from(flag: Arc<AtomicBool>) -> Self {
+        Self(flag)
+    }
+}
+
+impl Observer for CancellationToken {
+    fn observe(&mut self, _: ArrayView2<Float>, _: &IterationState) -> Control {
+        if self.is_cancelled() {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    }
+}
+
+/// How far a running engine got, see [`ProgressReporter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    /// The number of completed iterations.
+    pub iteration: usize,
+    /// The number of iterations the engine runs at most, if known, e.g. from
+    /// [`Termination::max_iterations`](super::termination::Termination::max_iterations).
+    pub estimated_total: Option<usize>,
+    /// The time since the reporter was created.
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// The completed fraction of the estimated total, between 0 and 1.
+    pub fn fraction(&self) -> Option<Float> {
+        let total = self.estimated_total?;
+        Some(if total == 0 { 1. } else { Float::min(1., self.iteration as Float / total as Float) })
+    }
+
+    /// The estimated remaining time, assuming the remaining iterations take as long as the
+    /// completed ones on average.
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.estimated_total?.saturating_sub(self.iteration);
+        if self.iteration == 0 {
+            return None;
+        }
+        Some(self.elapsed.mul_f64(remaining as f64 / self.iteration as f64))
+    }
+}
+
+/// An [`Observer`] that reports the [`Progress`] of the engine to a callback after every
+/// iteration, e.g. to update a progress bar, and stops the engine once the optional
+/// [`CancellationToken`] is cancelled.
+///
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::engines::observer::{CancellationToken, Progress, ProgressReporter};
+/// use rs_plode::engines::termination::Termination;
+/// use rs_plode::Graph;
+///
+/// let token = CancellationToken::new();
+/// let abort = token.clone();
+/// let reporter = ProgressReporter::new(Termination::Iterations(100).max_iterations(), |progress: &Progress| {
+///     println!("{:.0}% done, {:?} left", progress.fraction().unwrap() * 100., progress.eta());
+///     if progress.iteration == 10 {
+///         abort.cancel();
+///     }
+/// })
+/// .cancellation(token);
+/// let layout = vec![(0, 1), (1, 2)].layout_observed(FruchtermanReingold::default(), reporter);
+/// ```
+pub struct ProgressReporter<F> {
+    callback: F,
+    total: Option<usize>,
+    start: Instant,
+    cancellation: Option<CancellationToken>,
+}
+
+impl<F: FnMut(&Progress)> ProgressReporter<F> {
+    /// Report to the callback, given the number of iterations the engine runs at most, if known.
+    /// The elapsed time is measured from now on.
+    pub fn new(estimated_total: Option<usize>, callback: F) -> Self {
+        Self {
+            callback,
+            total: estimated_total,
+            start: Instant::now(),
+            cancellation: None,
+        }
+    }
+
+    /// Stop the engine once the token is cancelled.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+}
+
+impl<F: FnMut(&Progress)> Observer for ProgressReporter<F> {
+    fn observe(&mut self, positions: ArrayView2<Float>, state: &IterationState) -> Control {
+        (self.callback)(&Progress {
+            iteration: state.iteration,
+            estimated_total: self.total,
+            elapsed: self.start.elapsed(),
+        });
+        match &mut self.cancellation {
+            Some(token) => token.observe(positions, state),
+            None => Control::Continue,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{CancellationToken, Progress, ProgressReporter};
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::termination::Termination;
+    use crate::graph::generators::grid;
+    use crate::Graph;
+
+    #[test]
+    fn report_progress_until_cancelled() {
+        let token = CancellationToken::new();
+        let abort = token.clone();
+        let mut reports = Vec::new();
+        let reporter = ProgressReporter::new(Some(50), |progress: &Progress| {
+            reports.push(*progress);
+            if progress.iteration == 5 {
+                abort.cancel();
+            }
+        })
+        .cancellation(token.clone());
+        let engine = FruchtermanReingold::default().until(Termination::Iterations(50));
+        let layout = grid(3, 3).layout_observed(engine, reporter);
+        assert!(token.is_cancelled());
+        assert_eq!(layout.view().nrows(), 9);
+        assert_eq!(reports.iter().map(|p| p.iteration).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(reports[4].fraction(), Some(0.1));
+
+        let progress = Progress { iteration: 10, estimated_total: Some(40), elapsed: Duration::from_secs(2) };
+        assert_eq!(progress.eta(), Some(Duration::from_secs(6)));
+        assert_eq!(Progress { iteration: 0, ..progress }.eta(), None);
+        assert_eq!(Progress { estimated_total: None, ..progress }.fraction(), None);
+    }
+}
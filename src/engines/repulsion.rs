@@ -0,0 +1,140 @@
+//! The repulsive forces of [`FruchtermanReingold`](super::fruchterman_reingold::FruchtermanReingold)
+//! between all pairs of nodes.
+//!
+//! Two kernels compute the same, bit-identical forces: the dense one with whole array operations
+//! per node, and the blocked one, which walks the positions in tiles small enough to stay in the
+//! cache without allocating any temporary arrays. Both sum up the forces acting on a node in the
+//! order of the other nodes, which keeps layouts independent of the chosen kernel.
+use ndarray::{s, Array1, Array2, ArrayView2, Axis};
+#[cfg(feature = "ndarray-stats")]
+use ndarray_stats::MaybeNanExt;
+
+use crate::Float;
+
+/// The number of nodes per tile of the blocked kernel.
+const TILE: usize = 64;
+
+/// The magnitude of the repulsion between two nodes at distance `r`, given the product of their
+/// masses, if any.
+#[inline(always)]
+fn magnitude(r: Float, k: Float, mass: Option<Float>) -> Float {
+    // see page 1136 for details. This is actually pretty important, as otherwise
+    // nodes keep getting pushed to the edge of the boundingbox forever.
+    match mass {
+        None if r < 2. * k => k * k / r,
+        Some(m) if r < 2. * k * m.sqrt() => m * k * k / r,
+        _ => 0.,
+    }
+}
+
+/// Compute the repulsion by whole array operations, one node at a time.
+pub(super) fn dense(positions: ArrayView2<Float>, k: Float, masses: Option<&[Float]>) -> Array2<Float> {
+    let mass = |n: usize| masses.and_then(|m| m.get(n)).copied().unwrap_or(1.);
+    let nodes = positions.shape()[0];
+    // V x D shaped displacements for all nodes
+    let mut disp = Array2::<Float>::zeros(positions.dim());
+
+    // repulsive displacements for each node
+    for j in 0..nodes {
+        // V x D shaped matrix of delta vectors from node j to all other nodes.
+        let delta: Array2<Float> = &positions.slice(s![j, ..]) - &positions;
+        // V x 1 shaped matrix holding the absolute distance between v and each other vertex
+        let abs_delta: Array2<Float> = (&delta * &delta)
+            .sum_axis(Axis(1))
+            .map(|x: &Float| Float::sqrt(*x))
+            .insert_axis(Axis(1));
+        let mut force = abs_delta.clone();
+        for (i, f) in force.iter_mut().enumerate() {
+            *f = magnitude(*f, k, masses.map(|_| mass(i) * mass(j)));
+        }
+        // V x D shaped displacements for node j caused by all other nodes.
+        disp.slice_mut(s![j, ..]).assign(&sum_skipnan((&delta / &abs_delta) * force));
+    }
+
+    disp
+}
+
+/// Compute the repulsion tile by tile, accumulating the displacements of a tile of nodes over the
+/// tiles of other nodes.
+pub(super) fn blocked<const D: usize>(positions: ArrayView2<Float>, k: Float, masses: Option<&[Float]>) -> Array2<Float> {
+    let mass = |n: usize| masses.and_then(|m| m.get(n)).copied().unwrap_or(1.);
+    let nodes = positions.shape()[0];
+    let points: Vec<[Float; D]> = positions
+        .rows()
+        .into_iter()
+        .map(|row| std::array::from_fn(|d| row[d]))
+        .collect();
+
+    let mut disp = Array2::<Float>::zeros((nodes, D));
+    let mut acc = [[0.; D]; TILE];
+    for start in (0..nodes).step_by(TILE) {
+        let targets = &points[start..usize::min(start + TILE, nodes)];
+        acc[..targets.len()].fill([0.; D]);
+        for (tile, sources) in points.chunks(TILE).enumerate() {
+            for (t, target) in targets.iter().enumerate() {
+                let j = start + t;
+                for (s, source) in sources.iter().enumerate() {
+                    let delta: [Float; D] = std::array::from_fn(|d| target[d] - source[d]);
+                    let r = delta.iter().fold(0., |sum: Float, x| sum + x * x).sqrt();
+                    let i = tile * TILE + s;
+                    let f = magnitude(r, k, masses.map(|_| mass(i) * mass(j)));
+                    for d in 0..D {
+                        // coincident nodes, including the node itself, do not repel each other
+                        let x = (delta[d] / r) * f;
+                        if !x.is_nan() {
+                            acc[t][d] += x;
+                        }
+                    }
+                }
+            }
+        }
+        for (t, row) in acc[..targets.len()].iter().enumerate() {
+            for d in 0..D {
+                disp[[start + t, d]] = row[d];
+            }
+        }
+    }
+
+    disp
+}
+
+/// Sum up the rows of the displacements, skipping NaN values caused by coincident nodes.
+#[cfg(feature = "ndarray-stats")]
+fn sum_skipnan(displacements: Array2<Float>) -> Array1<Float> {
+    displacements.fold_axis_skipnan(Axis(0), 0., |agr, val| agr + val.const_raw())
+}
+
+/// Sum up the rows of the displacements, skipping NaN values caused by coincident nodes.
+#[cfg(not(feature = "ndarray-stats"))]
+fn sum_skipnan(displacements: Array2<Float>) -> Array1<Float> {
+    displacements.fold_axis(Axis(0), 0., |agr, val| if val.is_nan() { *agr } else { agr + val })
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::{Array2, ArrayView2};
+    use rand::{Rng, SeedableRng};
+
+    use super::{blocked, dense};
+    use crate::engines::EngineRng;
+    use crate::Float;
+
+    fn bits(forces: Array2<Float>) -> Array2<impl PartialEq + std::fmt::Debug> {
+        forces.mapv(Float::to_bits)
+    }
+
+    #[test]
+    fn kernels_agree_bitwise() {
+        let mut rng = EngineRng::seed_from_u64(3);
+        // more nodes than fit into a tile, some of them coincident
+        let mut positions = Array2::from_shape_simple_fn((150, 3), || rng.gen_range(-400. ..400.));
+        let first = positions.row(0).to_owned();
+        positions.row_mut(1).assign(&first);
+        let masses: Vec<Float> = (0..100).map(|n| 1. + n as Float / 10.).collect();
+        for masses in [None, Some(masses.as_slice())] {
+            let plane: ArrayView2<Float> = positions.slice(ndarray::s![.., ..2]);
+            assert_eq!(bits(blocked::<2>(plane, 150., masses)), bits(dense(plane, 150., masses)));
+            assert_eq!(bits(blocked::<3>(positions.view(), 150., masses)), bits(dense(positions.view(), 150., masses)));
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use crate::consts::PI;
+
+use ndarray::Array2;
+
+use crate::{Float, Graph};
+
+use super::Placement;
+
+/// Places groups of nodes on concentric circles, similar to networkx's `shell_layout`.
+///
+/// The first group is placed on the innermost circle, or in the center if it holds a single node.
+/// Nodes that are not part of any group are placed on an additional outermost circle. Within each
+/// shell, nodes are ordered by the mean angle of their neighbours in the inner shells (barycenter
+/// heuristic), which reduces the number of edge crossings between adjacent shells.
+///
+/// This is a deterministic [`Placement`].
+#[derive(Clone, Debug)]
+pub struct Shell {
+    shells: Vec<Vec<usize>>,
+    /// derive the shells from the breadth first search depth from this node instead
+    root: Option<usize>,
+    k: Float,
+    sweeps: usize,
+}
+
+impl Shell {
+    /// Create a shell layout from the given node groups, innermost first.
+    pub fn new(shells: Vec<Vec<usize>>) -> Self {
+        Self {
+            shells,
+            root: None,
+            k: 150.,
+            sweeps: 4,
+        }
+    }
+
+    /// Create a shell layout with the given root in the center, surrounded by one shell per breadth
+    /// first search depth. Nodes unreachable from the root go to the outermost shell.
+    pub fn from_root(root: usize) -> Self {
+        Self {
+            root: Some(root),
+            ..Self::new(Vec::new())
+        }
+    }
+
+    /// Set the distance between adjacent shells.
+    pub fn spacing(mut self, k: Float) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// Set the number of ordering optimization sweeps. Zero keeps the given order.
+    pub fn sweeps(mut self, sweeps: usize) -> Self {
+        self.sweeps = sweeps;
+        self
+    }
+
+    /// The groups including a final group for all nodes not assigned to any shell.
+    fn groups(&self, nodes: usize, neighbours: &[Vec<usize>]) -> Vec<Vec<usize>> {
+        let depths;
+        let shells = match self.root {
+            Some(root) if root < nodes => {
+                depths = breadth_first_shells(root, neighbours);
+                &depths
+            }
+            _ => &self.shells,
+        };
+        let mut assigned = vec![false; nodes];
+        let mut groups: Vec<Vec<usize>> = shells
+            .iter()
+            .map(|shell| {
+                shell
+                    .iter()
+                    .cloned()
+                    .filter(|&n| n < nodes && !std::mem::replace(&mut assigned[n], true))
+                    .collect::<Vec<usize>>()
+            })
+            .filter(|shell| !shell.is_empty())
+            .collect();
+        let rest: Vec<usize> = (0..nodes).filter(|&n| !assigned[n]).collect();
+        if !rest.is_empty() {
+            groups.push(rest);
+        }
+        groups
+    }
+}
+
+/// Group the nodes reachable from the root by their breadth first search depth.
+fn breadth_first_shells(root: usize, neighbours: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut visited = vec![false; neighbours.len()];
+    visited[root] = true;
+    let mut shells = vec![vec![root]];
+    loop {
+        let mut next = Vec::new();
+        for &n in shells.last().unwrap() {
+            for &m in &neighbours[n] {
+                if !std::mem::replace(&mut visited[m], true) {
+                    next.push(m);
+                }
+            }
+        }
+        if next.is_empty() {
+            return shells;
+        }
+        shells.push(next);
+    }
+}
+
+impl Placement for Shell {
+    fn place(&self, graph: &impl Graph) -> Array2<Float> {
+        let nodes = graph.nodes();
+        let mut neighbours = vec![Vec::new(); nodes];
+        for (u, v) in graph.edges() {
+            neighbours[u].push(v);
+            neighbours[v].push(u);
+        }
+        let mut groups = self.groups(nodes, &neighbours);
+
+        let center_first = groups.first().is_some_and(|g| g.len() == 1);
+        let radius = |shell: usize| -> Float {
+            if center_first {
+                shell as Float * self.k
+            } else {
+                (shell + 1) as Float * self.k
+            }
+        };
+
+        let mut angle: Vec<Float> = vec![0.; nodes];
+        let mut level = vec![usize::MAX; nodes];
+        let place = |group: &[usize], angle: &mut Vec<Float>| {
+            for (j, &n) in group.iter().enumerate() {
+                angle[n] = 2. * PI * j as Float / group.len() as Float;
+            }
+        };
+        for (i, group) in groups.iter().enumerate() {
+            place(group, &mut angle);
+            for &n in group {
+                level[n] = i;
+            }
+        }
+
+        for _ in 0..self.sweeps {
+            for (i, group) in groups.iter_mut().enumerate().skip(1) {
+                // circular mean of the angles of all neighbours in inner shells
+                let barycenter = |n: usize| -> Float {
+                    let (sin, cos) = neighbours[n]
+                        .iter()
+                        .filter(|&&m| level[m] < i)
+                        .fold((0., 0.), |(s, c), &m| (s + angle[m].sin(), c + angle[m].cos()));
+                    if sin == 0. && cos == 0. {
+                        angle[n]
+                    } else {
+                        sin.atan2(cos).rem_euclid(2. * PI)
+                    }
+                };
+                let mut keyed: Vec<(Float, usize)> = group.iter().map(|&n| (barycenter(n), n)).collect();
+                keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+                *group = keyed.into_iter().map(|(_, n)| n).collect();
+                place(group, &mut angle);
+            }
+        }
+
+        let mut pos = Array2::<Float>::zeros((nodes, 2));
+        for n in 0..nodes {
+            let r = radius(level[n]);
+            pos[[n, 0]] = r * angle[n].cos();
+            pos[[n, 1]] = r * angle[n].sin();
+        }
+        pos
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Shell;
+    use crate::engines::Placement;
+    use crate::graph::AdjacencyGraph;
+    use crate::metrics::crossings;
+    use crate::{Engine, Float, Graph};
+
+    #[test]
+    fn nodes_on_concentric_circles() {
+        let graph: Vec<(usize, usize)> = vec![(0, 1), (0, 2), (0, 3), (1, 4), (2, 5), (3, 6)];
+        let layout = graph.layout(Shell::new(vec![vec![0], vec![1, 2, 3]]).spacing(10.));
+        let radius = |n: usize| (layout.coord(n).x().powi(2) + layout.coord(n).y().powi(2)).sqrt();
+        assert!(radius(0) < 1e-4);
+        for n in 1..4 {
+            assert!((radius(n) - 10.).abs() < 1e-4);
+        }
+        // unassigned nodes end up on the outermost shell
+        for n in 4..7 {
+            assert!((radius(n) - 20.).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn ordering_reduces_crossings() {
+        // the outer shell is listed in an order that crosses all edges to the inner shell
+        let graph: Vec<(usize, usize)> = vec![(0, 7), (1, 6), (2, 5), (3, 4)];
+        let shells = vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]];
+        let unordered = Shell::new(shells.clone()).sweeps(0).place(&graph);
+        let ordered = Shell::new(shells).place(&graph);
+        assert!(crossings(&graph, ordered.view()) < crossings(&graph, unordered.view()));
+        assert_eq!(crossings(&graph, ordered.view()), 0);
+        let _ = Shell::new(vec![]).compute(graph);
+    }
+
+    #[test]
+    fn shells_by_depth_from_root() {
+        // a path 0 - 1 - 3 - 4 with a leaf 2 below 1 and an isolated node 5
+        let graph = AdjacencyGraph::from_edges(6, vec![(0, 1), (1, 2), (1, 3), (3, 4)]).unwrap();
+        let layout = graph.layout(Shell::from_root(0).spacing(10.));
+        let radius = |n: usize| (layout.coord(n).x().powi(2) + layout.coord(n).y().powi(2)).sqrt();
+        let radii: Vec<Float> = (0..6).map(|n| (radius(n) / 10.).round()).collect();
+        assert_eq!(radii, vec![0., 1., 2., 2., 3., 4.]);
+    }
+}
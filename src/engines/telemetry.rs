@@ -0,0 +1,113 @@
+use std::fmt::Write;
+
+use ndarray::ArrayView2;
+
+use super::observer::{Control, Observer};
+use super::termination::IterationState;
+use crate::Float;
+
+/// The state of an iterative engine after each iteration, to diagnose layouts that oscillate and
+/// never settle.
+///
+/// Sequences computed by [`Engine::animate`](crate::Engine::animate) carry the telemetry of their
+/// engine, see [`ScatterLayoutSequence::telemetry`]. To stream it while computing a single layout,
+/// pass a mutable reference as observer:
+/// ```
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::engines::telemetry::Telemetry;
+/// use rs_plode::Graph;
+///
+/// let mut telemetry = Telemetry::default();
+/// let layout = vec![(0usize, 1usize), (1, 2), (2, 0)].layout_observed(FruchtermanReingold::default(), &mut telemetry);
+/// assert_eq!(telemetry.states().len(), 200);
+/// println!("{}", telemetry.to_csv());
+/// ```
+///
+/// [`ScatterLayoutSequence::telemetry`]: crate::layout::scatter::ScatterLayoutSequence::telemetry
+#[derive(Clone, Debug, Default)]
+pub struct Telemetry {
+    states: Vec<IterationState>,
+}
+
+impl Telemetry {
+    /// Record the state of the next iteration.
+    pub(crate) fn push(&mut self, state: IterationState) {
+        self.states.push(state);
+    }
+
+    /// The recorded states, one per iteration.
+    pub fn states(&self) -> &[IterationState] {
+        &self.states
+    }
+
+    /// The temperature of each iteration.
+    pub fn temperatures(&self) -> Vec<Float> {
+        self.states.iter().map(|s| s.temperature).collect()
+    }
+
+    /// The largest displacement of any node in each iteration.
+    pub fn max_displacements(&self) -> Vec<Float> {
+        self.states.iter().map(|s| s.max_displacement).collect()
+    }
+
+    /// The total stress of each iteration.
+    pub fn stresses(&self) -> Vec<Float> {
+        self.states.iter().map(|s| s.stress).collect()
+    }
+
+    /// Count how often the stress turned from falling to rising or vice versa, a hint for layouts
+    /// that oscillate instead of settling.
+    pub fn reversals(&self) -> usize {
+        let deltas: Vec<Float> = self.states.windows(2).map(|w| w[1].stress - w[0].stress).filter(|d| *d != 0.).collect();
+        deltas.windows(2).filter(|d| d[0].signum() != d[1].signum()).count()
+    }
+
+    /// Serialize into comma separated values with a header line, e.g. for plotting.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("iteration,temperature,max_displacement,stress\n");
+        for s in &self.states {
+            writeln!(out, "{},{},{},{}", s.iteration, s.temperature, s.max_displacement, s.stress).unwrap();
+        }
+        out
+    }
+}
+
+impl Observer for &mut Telemetry {
+    fn observe(&mut self, _: ArrayView2<Float>, state: &IterationState) -> Control {
+        self.push(*state);
+        Control::Continue
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array2;
+
+    use super::Telemetry;
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::engines::observer::Observer;
+    use crate::engines::termination::{IterationState, Termination};
+    use crate::{Float, Graph};
+
+    #[test]
+    fn record_states() {
+        let mut telemetry = Telemetry::default();
+        let positions = Array2::zeros((1, 2));
+        for (i, stress) in [5., 4., 4., 6., 3., 2.].into_iter().enumerate() {
+            let state = IterationState { iteration: i + 1, temperature: 1. / (i + 1) as Float, max_displacement: 0.5, stress, previous_stress: None };
+            (&mut telemetry).observe(positions.view(), &state);
+        }
+        assert_eq!(telemetry.stresses(), vec![5., 4., 4., 6., 3., 2.]);
+        // falling, rising and falling again
+        assert_eq!(telemetry.reversals(), 2);
+        assert!(telemetry.to_csv().starts_with("iteration,temperature,max_displacement,stress\n1,1,0.5,5\n2,0.5,0.5,4\n"));
+
+        let sequence = vec![(0usize, 1usize), (1, 2)].animate(FruchtermanReingold::default().until(Termination::Iterations(20)));
+        let telemetry = sequence.telemetry().unwrap();
+        assert_eq!(telemetry.states().len(), sequence.frames() - 1);
+        // the temperature cools down linearly
+        let temperatures = telemetry.temperatures();
+        assert!(temperatures.windows(2).all(|t| t[1] <= t[0]) && temperatures[19] < temperatures[0]);
+        assert_eq!(telemetry.max_displacements().len(), 20);
+    }
+}
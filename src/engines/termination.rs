@@ -0,0 +1,113 @@
+use crate::Float;
+
+/// The state of an iterative engine after an iteration, used to decide about termination.
+#[derive(Clone, Copy, Debug)]
+pub struct IterationState {
+    /// The number of completed iterations.
+    pub iteration: usize,
+    /// The temperature of the last iteration, i.e. how far nodes were allowed to move.
+    pub temperature: Float,
+    /// The largest displacement of any node during the last iteration.
+    pub max_displacement: Float,
+    /// The total stress (sum of the force magnitudes acting on all nodes) of the last iteration.
+    pub stress: Float,
+    /// The total stress of the iteration before, if any.
+    pub previous_stress: Option<Float>,
+}
+
+/// Policy deciding when an iterative engine stops.
+///
+/// ```
+/// use rs_plode::engines::termination::Termination;
+///
+/// let policy = Termination::MaxDisplacement(0.01).or(Termination::Iterations(1000));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Termination {
+    /// Stop after the given number of iterations.
+    Iterations(usize),
+    /// Stop once no node moved further than the given distance during an iteration.
+    MaxDisplacement(Float),
+    /// Stop once the relative change of the total stress between two iterations drops below the
+    /// given threshold.
+    StressDelta(Float),
+    /// Stop as soon as any of the policies says so.
+    Any(Vec<Termination>),
+}
+
+impl Default for Termination {
+    fn default() -> Self {
+        Termination::Iterations(200)
+    }
+}
+
+impl Termination {
+    /// Combine two policies, stopping as soon as either of them is satisfied.
+    pub fn or(self, other: Termination) -> Termination {
+        match (self, other) {
+            (Termination::Any(mut a), Termination::Any(b)) => {
+                a.extend(b);
+                Termination::Any(a)
+            }
+            (Termination::Any(mut a), b) => {
+                a.push(b);
+                Termination::Any(a)
+            }
+            (a, b) => Termination::Any(vec![a, b]),
+        }
+    }
+
+    /// The iteration count after which the policy stops for sure, if any.
+    pub fn max_iterations(&self) -> Option<usize> {
+        match self {
+            Termination::Iterations(n) => Some(*n),
+            Termination::Any(policies) => policies.iter().filter_map(Termination::max_iterations).min(),
+            _ => None,
+        }
+    }
+
+    /// Whether the engine should stop after reaching the given state.
+    pub fn should_stop(&self, state: &IterationState) -> bool {
+        match self {
+            Termination::Iterations(n) => state.iteration >= *n,
+            Termination::MaxDisplacement(d) => state.max_displacement <= *d,
+            Termination::StressDelta(delta) => match state.previous_stress {
+                Some(previous) => {
+                    (state.stress - previous).abs() <= *delta * Float::max(previous.abs(), Float::EPSILON)
+                }
+                None => false,
+            },
+            Termination::Any(policies) => policies.iter().any(|p| p.should_stop(state)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{IterationState, Termination};
+    use crate::Float;
+
+    fn state(iteration: usize, max_displacement: Float, stress: Float, previous_stress: Option<Float>) -> IterationState {
+        IterationState { iteration, temperature: 1., max_displacement, stress, previous_stress }
+    }
+
+    #[test]
+    fn combined_policies() {
+        let policy = Termination::MaxDisplacement(0.01)
+            .or(Termination::Iterations(1000))
+            .or(Termination::Iterations(500));
+        assert_eq!(policy.max_iterations(), Some(500));
+        assert!(!policy.should_stop(&state(10, 1., 1., None)));
+        assert!(policy.should_stop(&state(10, 0.001, 1., None)));
+        assert!(policy.should_stop(&state(500, 1., 1., None)));
+    }
+
+    #[test]
+    fn stress_delta_is_relative() {
+        let policy = Termination::StressDelta(0.1);
+        assert_eq!(policy.max_iterations(), None);
+        assert!(!policy.should_stop(&state(1, 1., 100., None)));
+        assert!(!policy.should_stop(&state(2, 1., 80., Some(100.))));
+        assert!(policy.should_stop(&state(3, 1., 95., Some(100.))));
+    }
+}
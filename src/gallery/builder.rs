@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+
+use super::demo_graphs;
+use crate::engines::fruchterman_reingold::FruchtermanReingold;
+use crate::layout::scatter::ScatterLayout;
+use crate::render::json_string as string;
+use crate::render::svg::{RenderOptions, RenderSVG};
+use crate::metrics::{crossings, mean_edge_length};
+use crate::{Float, Graph};
+
+/// Metadata about a single rendered gallery entry.
+#[derive(Clone, Debug)]
+pub struct GalleryEntry {
+    pub graph: String,
+    pub engine: String,
+    /// The engine parameters as (name, value) pairs.
+    pub params: Vec<(String, f64)>,
+    /// Quality and size metrics of the final layout as (name, value) pairs.
+    pub metrics: Vec<(String, f64)>,
+    /// The rendered files, relative to the gallery directory.
+    pub files: Vec<PathBuf>,
+}
+
+/// Description of all files rendered by a [`Gallery`].
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+    pub version: String,
+    pub entries: Vec<GalleryEntry>,
+}
+
+impl Manifest {
+    /// Serialize the manifest as JSON.
+    pub fn to_json(&self) -> String {
+        fn object(pairs: &[(String, f64)]) -> String {
+            let fields: Vec<String> = pairs
+                .iter()
+                .map(|(k, v)| format!("{}: {}", string(k), if v.is_finite() { v.to_string() } else { "null".to_string() }))
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|e| {
+                let files: Vec<String> = e.files.iter().map(|f| string(&f.to_string_lossy())).collect();
+                format!(
+                    "    {{\"graph\": {}, \"engine\": {}, \"params\": {}, \"metrics\": {}, \"files\": [{}]}}",
+                    string(&e.graph),
+                    string(&e.engine),
+                    object(&e.params),
+                    object(&e.metrics),
+                    files.join(", ")
+                )
+            })
+            .collect();
+        format!(
+            "{{\n  \"version\": {},\n  \"entries\": [\n{}\n  ]\n}}\n",
+            string(&self.version),
+            entries.join(",\n")
+        )
+    }
+}
+
+/// Renders the [`demo_graphs`] into a directory, next to a `manifest.json` describing the output.
+///
+/// ```no_run
+/// use rs_plode::gallery::Gallery;
+///
+/// let manifest = Gallery::new("target/gallery").seed(42).render().unwrap();
+/// ```
+pub struct Gallery {
+    directory: PathBuf,
+    options: RenderOptions,
+    k: Float,
+    seed: u64,
+}
+
+impl Gallery {
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+            options: RenderOptions::default(),
+            k: 150.,
+            seed: 0,
+        }
+    }
+
+    /// Set the options used for rendering all graphs.
+    pub fn options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the optimal distance parameter of the engine.
+    pub fn k(mut self, k: Float) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// Set the random seed of the engine.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Render an animation and the final layout for each demo graph and write the manifest.
+    #[allow(clippy::unnecessary_cast)] // with the f64 feature, `Float` is f64 already
+    pub fn render(&self) -> Result<Manifest, String> {
+        std::fs::create_dir_all(&self.directory).map_err(|e| e.to_string())?;
+        let mut manifest = Manifest {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            entries: Vec::new(),
+        };
+
+        for (name, graph) in demo_graphs() {
+            let sequence = (&graph).animate(FruchtermanReingold::new(self.k, self.seed));
+            let frames = sequence.frames();
+            let positions = sequence.frame(frames - 1);
+            let mut metrics = vec![
+                ("nodes".to_string(), graph.nodes() as f64),
+                ("edges".to_string(), graph.edges().count() as f64),
+                ("frames".to_string(), frames as f64),
+                ("mean_edge_length".to_string(), mean_edge_length(&graph, positions) as f64),
+                ("crossings".to_string(), crossings(&graph, positions) as f64),
+            ];
+            let last = ScatterLayout::new(&graph, positions.to_owned())?;
+            metrics.push(("width".to_string(), last.bbox().width() as f64));
+            metrics.push(("height".to_string(), last.bbox().height() as f64));
+
+            let files = vec![
+                PathBuf::from(format!("{}.svg", name)),
+                PathBuf::from(format!("{}-final.svg", name)),
+            ];
+            svg::save(self.directory.join(&files[0]), &sequence.to_document(&self.options)?)
+                .map_err(|e| e.to_string())?;
+            svg::save(self.directory.join(&files[1]), &last.to_document(&self.options)?)
+                .map_err(|e| e.to_string())?;
+
+            manifest.entries.push(GalleryEntry {
+                graph: name.to_string(),
+                engine: "fruchterman-reingold".to_string(),
+                params: vec![("k".to_string(), self.k as f64), ("seed".to_string(), self.seed as f64)],
+                metrics,
+                files,
+            });
+        }
+
+        std::fs::write(self.directory.join("manifest.json"), manifest.to_json()).map_err(|e| e.to_string())?;
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Gallery;
+    use crate::gallery::demo_graphs;
+
+    #[test]
+    fn render_gallery_with_manifest() {
+        let directory = std::env::temp_dir().join(format!("rs-plode-gallery-{}", std::process::id()));
+        let manifest = Gallery::new(&directory).render().unwrap();
+        assert_eq!(manifest.entries.len(), demo_graphs().len());
+        for entry in &manifest.entries {
+            for file in &entry.files {
+                assert!(directory.join(file).exists());
+            }
+        }
+        let json = std::fs::read_to_string(directory.join("manifest.json")).unwrap();
+        assert!(json.contains("\"graph\": \"triangle\""));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+//! Demo graphs and a gallery builder rendering them for documentation and comparisons.
+#[cfg(feature = "svg")]
+mod builder;
+
+#[cfg(feature = "svg")]
+pub use builder::{Gallery, GalleryEntry, Manifest};
+
+/// Some predefined regular graphs helpful for testing and demonstration.
+#[rustfmt::skip]
+pub fn demo_graphs() -> Vec<(&'static str, Vec<(usize, usize)>)> {
+    let graphs: Vec<(&'static str, &'static [(usize, usize)])> = vec![
+        ("triangle", &[(0, 1), (1, 2), (2, 0)]),
+        ("square", &[(0, 1), (1, 2), (2, 3), (3, 0)]),
+        ("pentagon", &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]),
+        ("tetrahedron", &[(0, 1), (1, 2), (2, 0), (0, 3), (1, 3), (2, 3)]),
+        ("custom", &[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (1, 2), (2, 4), (2, 5), (4, 5)]),
+        ("cube", &[
+            // plane 1
+            (0, 1), (1, 2), (2, 3), (3, 0),
+            // plane 1
+            (4, 5), (5, 6), (6, 7), (7, 4),
+            // plane connections
+            (0, 4), (1, 5), (2, 6), (3, 7),
+        ],
+        ),
+        (
+            "tree",
+            &[
+                // root->level1
+                (0, 1), (0, 2),
+                // level1->level2
+                (1, 3), (1, 4), (1, 5), (2, 6), (2, 7),
+                // level2->level3
+                (3, 8), (4, 9), (4, 10), (6, 11), (6, 12), (6, 13), (7, 14),
+                // level3->level4
+                (14, 15), (14, 16), (14, 17), (14, 18), (14, 19)
+            ],
+        ),
+        (
+            "prism",
+            &[
+                // plane 1
+                (0, 1), (1, 2), (2, 0),
+                // plane 2
+                (3, 4), (4, 5), (5, 3),
+                // connections
+                (0, 3), (1, 4), (2, 5),
+            ],
+        ),
+        (
+            "pentagram",
+            &[
+                // pentagon
+                (0, 1), (1, 2), (2, 3), (3, 4), (4, 0),
+                // diagonals
+                (0, 2), (1, 3), (2, 4), (3, 0), (4, 1),
+            ],
+        ),
+        (
+            "disconnected-components",
+            &[
+                // triangle 1
+                (0, 1), (1, 2), (2, 0),
+                // triangle 2
+                (3, 4), (4, 5), (5, 3),
+            ],
+        ),
+        (
+            "triangulated-triangle",
+            &[
+                // outer edge 1
+                (0, 1), (1, 2), (2, 3),
+                // outer edge 2
+                (3, 4), (4, 5), (5, 6),
+                // outer edge 3
+                (6, 7), (7, 8), (8, 0),
+                // cut edges
+                (1, 8), (2, 4), (5, 7),
+                // connections to center
+                (1, 9), (2, 9), (4, 9), (5, 9), (7, 9), (8, 9),
+            ],
+        ),
+    ];
+    graphs.iter().map(|&(name, edges)| (name, Vec::from(edges))).collect()
+}
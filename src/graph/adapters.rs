@@ -0,0 +1,197 @@
+//! [`Graph`] implementations for std containers, so edge lists and adjacency lists can be laid out
+//! as they are.
+//!
+//! Adjacency lists map each node to its neighbours and describe undirected graphs. An edge listed
+//! by both of its nodes is yielded once, from the smaller node, while edges listed by only one of
+//! their nodes are yielded as they are. Their node count is defined by the largest node id they
+//! reference, like the one of edge lists.
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use rs_plode::Graph;
+//!
+//! let lists = vec![vec![1, 2], vec![0], vec![0]];
+//! assert_eq!(lists.edges().collect::<Vec<_>>(), vec![(0, 1), (0, 2)]);
+//!
+//! let map = BTreeMap::from([(0, vec![1]), (3, vec![0])]);
+//! assert_eq!(map.nodes(), 4);
+//! assert_eq!(map.edges().collect::<Vec<_>>(), vec![(0, 1), (3, 0)]);
+//!
+//! let slice: &[(usize, usize)] = &[(0, 1), (1, 2)];
+//! assert_eq!(slice.nodes(), 3);
+//! ```
+use std::collections::{btree_map, hash_map, BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+use crate::{EdgeIter, Graph};
+
+/// A borrowed edge list, like the owned `Vec<(usize, usize)>`.
+impl Graph for &[(usize, usize)] {
+    type Edges<'a> = EdgeIter<'a> where Self: 'a;
+
+    fn nodes(&self) -> usize {
+        self.iter().map(|&(s, t)| usize::max(s, t)).max().unwrap_or(0) + 1
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.iter().copied()
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+/// Containers mapping nodes to their neighbours.
+pub trait AdjacencyList {
+    /// The neighbours of the node, empty for nodes without an entry.
+    fn neighbours(&self, node: usize) -> &[usize];
+}
+
+impl AdjacencyList for Vec<Vec<usize>> {
+    fn neighbours(&self, node: usize) -> &[usize] {
+        self.get(node).map_or(&[], |n| n.as_slice())
+    }
+}
+
+impl<S: BuildHasher> AdjacencyList for HashMap<usize, Vec<usize>, S> {
+    fn neighbours(&self, node: usize) -> &[usize] {
+        self.get(&node).map_or(&[], |n| n.as_slice())
+    }
+}
+
+impl AdjacencyList for BTreeMap<usize, Vec<usize>> {
+    fn neighbours(&self, node: usize) -> &[usize] {
+        self.get(&node).map_or(&[], |n| n.as_slice())
+    }
+}
+
+/// The edges of an adjacency list, given the iterator over its (node, neighbours) entries.
+pub struct AdjacencyListEdges<'a, L, I> {
+    list: &'a L,
+    entries: I,
+    current: Option<(usize, std::slice::Iter<'a, usize>)>,
+}
+
+impl<'a, L, I> AdjacencyListEdges<'a, L, I> {
+    fn new(list: &'a L, entries: I) -> Self {
+        Self { list, entries, current: None }
+    }
+}
+
+impl<'a, L, I> Iterator for AdjacencyListEdges<'a, L, I>
+where
+    L: AdjacencyList,
+    I: Iterator<Item = (usize, &'a Vec<usize>)>,
+{
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((u, neighbours)) = &mut self.current {
+                let u = *u;
+                // skip the second listing of edges listed by both nodes
+                let list = self.list;
+                if let Some(&v) = neighbours.find(|&&v| u <= v || !list.neighbours(v).contains(&u)) {
+                    return Some((u, v));
+                }
+            }
+            let (u, neighbours) = self.entries.next()?;
+            self.current = Some((u, neighbours.iter()));
+        }
+    }
+}
+
+/// The largest node id referenced by the entries, plus one.
+fn referenced_nodes<'a>(entries: impl Iterator<Item = (usize, &'a Vec<usize>)>) -> usize {
+    entries
+        .map(|(u, neighbours)| neighbours.iter().fold(u, |n, &v| usize::max(n, v)))
+        .max()
+        .map_or(0, |n| n + 1)
+}
+
+/// The entries of map based adjacency lists.
+type MapEntries<I> = std::iter::Map<I, for<'a> fn((&'a usize, &'a Vec<usize>)) -> (usize, &'a Vec<usize>)>;
+
+fn copy_node<'a>((u, neighbours): (&'a usize, &'a Vec<usize>)) -> (usize, &'a Vec<usize>) {
+    (*u, neighbours)
+}
+
+/// Adjacency lists indexed by node, where node `n` has the neighbours `self[n]`.
+impl Graph for Vec<Vec<usize>> {
+    type Edges<'a> = AdjacencyListEdges<'a, Self, std::iter::Enumerate<std::slice::Iter<'a, Vec<usize>>>>;
+
+    fn nodes(&self) -> usize {
+        usize::max(self.len(), referenced_nodes(self.iter().enumerate()))
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        AdjacencyListEdges::new(self, self.iter().enumerate())
+    }
+}
+
+/// Adjacency lists keyed by node. The edges follow the iteration order of the map, which differs
+/// between runs; use a `BTreeMap` for reproducible layouts.
+impl<S: BuildHasher> Graph for HashMap<usize, Vec<usize>, S> {
+    type Edges<'a> = AdjacencyListEdges<'a, Self, MapEntries<hash_map::Iter<'a, usize, Vec<usize>>>> where Self: 'a;
+
+    fn nodes(&self) -> usize {
+        referenced_nodes(self.iter().map(copy_node))
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        AdjacencyListEdges::new(self, self.iter().map(copy_node as _))
+    }
+}
+
+/// Adjacency lists keyed by node, whose edges are ordered by their first node.
+impl Graph for BTreeMap<usize, Vec<usize>> {
+    type Edges<'a> = AdjacencyListEdges<'a, Self, MapEntries<btree_map::Iter<'a, usize, Vec<usize>>>>;
+
+    fn nodes(&self) -> usize {
+        referenced_nodes(self.iter().map(copy_node))
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        AdjacencyListEdges::new(self, self.iter().map(copy_node as _))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::{validate, Graph};
+
+    #[test]
+    fn adjacency_lists() {
+        // the triangle 0-1-2, listed both ways, with the pendant node 3 listed only by node 2 and a
+        // self loop
+        let lists = vec![vec![1, 2], vec![0, 2], vec![0, 1, 3, 2], vec![]];
+        let edges = vec![(0, 1), (0, 2), (1, 2), (2, 3), (2, 2)];
+        assert_eq!(lists.nodes(), 4);
+        assert_eq!(lists.edges().collect::<Vec<_>>(), edges);
+
+        let tree: BTreeMap<usize, Vec<usize>> = lists.iter().cloned().enumerate().filter(|(_, n)| !n.is_empty()).collect();
+        assert_eq!(tree.nodes(), 4);
+        assert_eq!(tree.edges().collect::<Vec<_>>(), edges);
+
+        let map: HashMap<usize, Vec<usize>> = tree.clone().into_iter().collect();
+        let mut unordered = map.edges().collect::<Vec<_>>();
+        unordered.sort();
+        let mut sorted = edges.clone();
+        sorted.sort();
+        assert_eq!(unordered, sorted);
+
+        // nodes without neighbours or entries
+        assert_eq!(vec![vec![], vec![5]].nodes(), 6);
+        assert_eq!(BTreeMap::new().nodes(), 0);
+        assert!(validate(&vec![vec![], vec![5]]).is_ok());
+
+        let slice: &[(usize, usize)] = &edges;
+        assert_eq!((slice.nodes(), slice.edge_count_hint()), (4, Some(5)));
+        let layout = slice.layout(FruchtermanReingold::default());
+        assert!(layout.bbox().width() > 0.);
+    }
+}
@@ -0,0 +1,218 @@
+use ndarray::ArrayView2;
+
+use crate::{EdgeIter, Float, Graph};
+
+/// A compact graph owned by the crate, storing the edge list together with a compressed sparse row
+/// (CSR) index of the neighbours of each node.
+///
+/// ```
+/// use rs_plode::graph::AdjacencyGraph;
+/// use rs_plode::Graph;
+///
+/// let star = AdjacencyGraph::from_edges(4, vec![(0, 1), (0, 2), (0, 3)]).unwrap();
+/// assert_eq!(star.degree(0), 3);
+/// assert_eq!(star.neighbors(2), &[0]);
+/// assert_eq!(star.edges().count(), 3);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdjacencyGraph {
+    edges: Vec<(usize, usize)>,
+    weights: Option<Vec<Float>>,
+    directed: bool,
+    /// neighbours of node n are `targets[offsets[n]..offsets[n + 1]]`
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    out_degrees: Vec<usize>,
+}
+
+impl AdjacencyGraph {
+    /// Create an undirected graph with the given number of nodes.
+    pub fn from_edges(nodes: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Result<Self, String> {
+        Self::build(nodes, edges.into_iter().collect(), false)
+    }
+
+    /// Create a directed graph with the given number of nodes.
+    pub fn from_directed_edges(nodes: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Result<Self, String> {
+        Self::build(nodes, edges.into_iter().collect(), true)
+    }
+
+    /// Create a graph from a square matrix, where every non-zero entry `(i, j)` is an edge from i to
+    /// j with the entry as weight.
+    ///
+    /// Symmetric matrices yield undirected graphs with one edge per pair of nodes. If all entries are
+    /// zero or one, the graph is unweighted.
+    pub fn from_adjacency_matrix(matrix: ArrayView2<Float>) -> Result<Self, String> {
+        let (rows, columns) = matrix.dim();
+        if rows != columns {
+            return Err(format!("Adjacency matrix must be square, got {} x {}", rows, columns));
+        }
+        if let Some(value) = matrix.iter().find(|v| !v.is_finite()) {
+            return Err(format!("Adjacency matrix contains the invalid entry {}", value));
+        }
+        let directed = matrix != matrix.t();
+        let (mut edges, mut weights) = (Vec::new(), Vec::new());
+        for ((i, j), &value) in matrix.indexed_iter() {
+            if value != 0. && (directed || i <= j) {
+                edges.push((i, j));
+                weights.push(value);
+            }
+        }
+        let graph = Self::build(rows, edges, directed)?;
+        if weights.iter().all(|&w| w == 1.) {
+            Ok(graph)
+        } else {
+            graph.with_weights(weights)
+        }
+    }
+
+    /// Attach one weight per edge.
+    pub fn with_weights(mut self, weights: Vec<Float>) -> Result<Self, String> {
+        if weights.len() != self.edges.len() {
+            return Err(format!("Got {} weights for {} edges", weights.len(), self.edges.len()));
+        }
+        self.weights = Some(weights);
+        Ok(self)
+    }
+
+    /// The weight of each edge, if the graph is weighted.
+    pub fn weights(&self) -> Option<&[Float]> {
+        self.weights.as_deref()
+    }
+
+    fn build(nodes: usize, edges: Vec<(usize, usize)>, directed: bool) -> Result<Self, String> {
+        if let Some((i, &(s, t))) = edges.iter().enumerate().find(|(_, &(s, t))| s >= nodes || t >= nodes) {
+            return Err(format!(
+                "Edge {} ({}, {}) references node {}, but the graph has only {} nodes",
+                i,
+                s,
+                t,
+                usize::max(s, t),
+                nodes
+            ));
+        }
+        let mut degrees = vec![0; nodes];
+        let mut out_degrees = vec![0; nodes];
+        for &(s, t) in &edges {
+            degrees[s] += 1;
+            out_degrees[s] += 1;
+            if s != t {
+                degrees[t] += 1;
+            }
+        }
+        let mut offsets = vec![0; nodes + 1];
+        for n in 0..nodes {
+            offsets[n + 1] = offsets[n] + degrees[n];
+        }
+        let mut fill = offsets.clone();
+        let mut targets = vec![0; offsets[nodes]];
+        for &(s, t) in &edges {
+            targets[fill[s]] = t;
+            fill[s] += 1;
+            if s != t {
+                targets[fill[t]] = s;
+                fill[t] += 1;
+            }
+        }
+        Ok(Self {
+            edges,
+            weights: None,
+            directed,
+            offsets,
+            targets,
+            out_degrees,
+        })
+    }
+
+    /// The nodes adjacent to the given node, regardless of the edge direction. Nodes connected by
+    /// multiple edges appear multiple times.
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        &self.targets[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    /// The number of edges incident to the node, counting self loops once.
+    pub fn degree(&self, node: usize) -> usize {
+        self.offsets[node + 1] - self.offsets[node]
+    }
+
+    /// The number of edges starting at the node.
+    pub fn out_degree(&self, node: usize) -> usize {
+        self.out_degrees[node]
+    }
+
+    /// The number of edges ending at the node.
+    pub fn in_degree(&self, node: usize) -> usize {
+        let loops = self.neighbors(node).iter().filter(|&&m| m == node).count();
+        self.degree(node) - self.out_degree(node) + loops
+    }
+}
+
+impl Graph for AdjacencyGraph {
+    type Edges<'a> = EdgeIter<'a>;
+
+    fn nodes(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.edges.iter().copied()
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edges.len())
+    }
+
+    fn edge_weights(&self) -> Option<Vec<Float>> {
+        self.weights.clone()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::AdjacencyGraph;
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::{Float, Graph};
+
+    #[test]
+    fn neighbors_and_degrees() {
+        let graph = AdjacencyGraph::from_directed_edges(4, vec![(0, 1), (1, 2), (2, 0), (2, 2)]).unwrap();
+        assert_eq!(graph.nodes(), 4);
+        assert!(graph.is_directed());
+        assert_eq!(graph.neighbors(2), &[1, 0, 2]);
+        assert_eq!((graph.degree(2), graph.out_degree(2), graph.in_degree(2)), (3, 2, 2));
+        assert_eq!(graph.degree(3), 0);
+        assert!(graph.neighbors(3).is_empty());
+    }
+
+    #[test]
+    fn from_matrix() {
+        let symmetric = AdjacencyGraph::from_adjacency_matrix(arr2(&[[0., 1., 1.], [1., 0., 0.], [1., 0., 0.]]).view()).unwrap();
+        assert!(!symmetric.is_directed());
+        assert_eq!(symmetric.edges().collect::<Vec<_>>(), vec![(0, 1), (0, 2)]);
+        assert_eq!(symmetric.edge_weights(), None);
+
+        let weighted = AdjacencyGraph::from_adjacency_matrix(arr2(&[[0., 2.], [0., 0.]]).view()).unwrap();
+        assert!(weighted.is_directed());
+        assert_eq!(weighted.edge_weights(), Some(vec![2.]));
+    }
+
+    #[test]
+    fn reject_invalid_input() {
+        assert!(AdjacencyGraph::from_edges(2, vec![(0, 2)]).unwrap_err().contains("only 2 nodes"));
+        assert!(AdjacencyGraph::from_adjacency_matrix(arr2(&[[0., 1.]]).view()).is_err());
+        assert!(AdjacencyGraph::from_adjacency_matrix(arr2(&[[Float::NAN]]).view()).is_err());
+        assert!(AdjacencyGraph::from_edges(2, vec![(0, 1)]).unwrap().with_weights(vec![]).is_err());
+    }
+
+    #[test]
+    fn layout_isolated_nodes() {
+        let graph = AdjacencyGraph::from_edges(5, vec![(0, 1)]).unwrap();
+        let layout = graph.layout(FruchtermanReingold::default());
+        assert!(layout.bbox().width() > 0.);
+    }
+}
@@ -0,0 +1,135 @@
+use super::AdjacencyGraph;
+use crate::{EdgeIter, Float, Graph};
+
+/// The structure of a graph derived once, for engines that would otherwise re-derive it in every
+/// iteration: the node count, which is O(E) for edge lists, the edges and weights, the degrees and
+/// neighbours of each node, and the node labels.
+///
+/// ```
+/// use rs_plode::graph::GraphCache;
+/// use rs_plode::Graph;
+///
+/// let cache = GraphCache::new(&vec![(0, 1), (0, 2), (2, 2)]).unwrap();
+/// assert_eq!(cache.nodes(), 3);
+/// assert_eq!(cache.degrees(), &[2, 1, 2]);
+/// assert_eq!(cache.neighbors(0), &[1, 2]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphCache {
+    graph: AdjacencyGraph,
+    degrees: Vec<usize>,
+    /// The label of each node, empty if no node has one.
+    labels: Vec<Option<String>>,
+}
+
+impl GraphCache {
+    /// Derive the structure of the graph, failing like [`validate`](crate::validate) if its edges
+    /// reference missing nodes, or if it has not one weight per edge.
+    pub fn new(graph: &impl Graph) -> Result<Self, String> {
+        let nodes = graph.nodes();
+        let mut adjacency = match graph.is_directed() {
+            true => AdjacencyGraph::from_directed_edges(nodes, graph.edges())?,
+            false => AdjacencyGraph::from_edges(nodes, graph.edges())?,
+        };
+        if let Some(weights) = graph.edge_weights() {
+            adjacency = adjacency.with_weights(weights)?;
+        }
+        let degrees = (0..nodes).map(|n| adjacency.degree(n)).collect();
+        let mut labels: Vec<_> = (0..nodes).map(|n| graph.node_label(n)).collect();
+        if labels.iter().all(Option::is_none) {
+            labels = Vec::new();
+        }
+        Ok(Self { graph: adjacency, degrees, labels })
+    }
+
+    /// Like [`new`](Self::new), but panics with the reason if the graph is malformed, for engines,
+    /// whose layouts cannot fail.
+    pub(crate) fn for_layout(graph: &impl Graph) -> Self {
+        Self::new(graph).unwrap_or_else(|error| panic!("Cannot lay out a malformed graph: {}", error))
+    }
+
+    /// The number of edges incident to each node, counting self loops once.
+    pub fn degrees(&self) -> &[usize] {
+        &self.degrees
+    }
+
+    /// The number of edges incident to the node, counting self loops once.
+    pub fn degree(&self, node: usize) -> usize {
+        self.degrees[node]
+    }
+
+    /// The nodes adjacent to the given node, regardless of the edge direction.
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        self.graph.neighbors(node)
+    }
+
+    /// The weight of each edge, if the graph is weighted.
+    pub fn weights(&self) -> Option<&[Float]> {
+        self.graph.weights()
+    }
+
+    /// The cached graph, e.g. for its in and out degrees.
+    pub fn adjacency(&self) -> &AdjacencyGraph {
+        &self.graph
+    }
+}
+
+impl Graph for GraphCache {
+    type Edges<'a> = EdgeIter<'a>;
+
+    fn nodes(&self) -> usize {
+        self.degrees.len()
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.graph.edges()
+    }
+
+    fn edge_weights(&self) -> Option<Vec<Float>> {
+        self.graph.edge_weights()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        self.graph.edge_count_hint()
+    }
+
+    fn node_label(&self, node: usize) -> Option<String> {
+        self.labels.get(node).cloned().flatten()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::GraphCache;
+    use crate::{Graph, Weighted};
+
+    #[test]
+    fn cache_structure() {
+        let matrix = arr2(&[[0., 2., 0.], [2., 0., 0.5], [0., 0.5, 0.]]);
+        let cache = GraphCache::new(&Weighted(matrix)).unwrap();
+        assert_eq!(cache.nodes(), 3);
+        assert_eq!(cache.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+        assert_eq!(cache.weights(), Some(&[2., 0.5][..]));
+        assert_eq!((cache.degree(1), cache.neighbors(1)), (2, &[0, 2][..]));
+        assert!(!cache.is_directed());
+        assert_eq!(cache.node_label(0), None);
+
+        let names = crate::io::edgelist::EdgeListGraph::parse("a b\nb c").unwrap();
+        let cache = GraphCache::new(&names).unwrap();
+        assert_eq!(cache.node_label(2).as_deref(), Some("c"));
+
+        struct Liar;
+        impl Graph for Liar {
+            type Edges<'a> = std::vec::IntoIter<(usize, usize)>;
+            fn nodes(&self) -> usize { 2 }
+            fn edges(&self) -> Self::Edges<'_> { vec![(0, 1), (1, 2)].into_iter() }
+        }
+        assert!(GraphCache::new(&Liar).unwrap_err().contains("only 2 nodes"));
+    }
+}
@@ -0,0 +1,219 @@
+//! Generators for synthetic graphs of configurable size, e.g. for benchmarks and tests.
+use std::collections::BTreeSet;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::AdjacencyGraph;
+
+/// A path of the given number of nodes, `0 - 1 - ... - (nodes - 1)`.
+pub fn path(nodes: usize) -> AdjacencyGraph {
+    AdjacencyGraph::from_edges(nodes, (1..nodes).map(|n| (n - 1, n))).unwrap()
+}
+
+/// A path whose ends are connected. Graphs of fewer than three nodes are paths.
+pub fn cycle(nodes: usize) -> AdjacencyGraph {
+    let closing = if nodes > 2 { Some((nodes - 1, 0)) } else { None };
+    AdjacencyGraph::from_edges(nodes, (1..nodes).map(|n| (n - 1, n)).chain(closing)).unwrap()
+}
+
+/// A star with node 0 in the center, connected to all other nodes.
+pub fn star(nodes: usize) -> AdjacencyGraph {
+    AdjacencyGraph::from_edges(nodes, (1..nodes).map(|n| (0, n))).unwrap()
+}
+
+/// The complete graph, with an edge between every pair of distinct nodes.
+pub fn complete(nodes: usize) -> AdjacencyGraph {
+    let edges = (0..nodes).flat_map(|u| (u + 1..nodes).map(move |v| (u, v)));
+    AdjacencyGraph::from_edges(nodes, edges).unwrap()
+}
+
+/// A rows x columns grid, where each node is connected to its right and lower neighbour. Node
+/// `r * columns + c` sits in row r and column c.
+pub fn grid(rows: usize, columns: usize) -> AdjacencyGraph {
+    let mut edges = Vec::with_capacity(2 * rows * columns);
+    for r in 0..rows {
+        for c in 0..columns {
+            let n = r * columns + c;
+            if c + 1 < columns {
+                edges.push((n, n + 1));
+            }
+            if r + 1 < rows {
+                edges.push((n, n + columns));
+            }
+        }
+    }
+    AdjacencyGraph::from_edges(rows * columns, edges).unwrap()
+}
+
+/// A scale-free graph following the Barabási–Albert model: starting from a clique of `m + 1`
+/// nodes, every further node connects to m distinct existing nodes, chosen with a probability
+/// proportional to their degree.
+///
+/// ```
+/// use rs_plode::graph::generators::scale_free;
+/// use rs_plode::Graph;
+///
+/// let graph = scale_free(100, 2, 0);
+/// assert_eq!(graph.nodes(), 100);
+/// assert_eq!(graph.edges().count(), 3 + 97 * 2);
+/// ```
+pub fn scale_free(nodes: usize, m: usize, seed: u64) -> AdjacencyGraph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let m = usize::max(m, 1);
+    let core = usize::min(m + 1, nodes);
+    let mut edges = Vec::with_capacity(nodes * m);
+    for u in 0..core {
+        for v in u + 1..core {
+            edges.push((u, v));
+        }
+    }
+    // every node appears once per incident edge, such that uniform sampling is degree proportional
+    let mut endpoints: Vec<usize> = edges.iter().flat_map(|&(u, v)| [u, v]).collect();
+    for n in core..nodes {
+        let mut targets: Vec<usize> = Vec::with_capacity(m);
+        while targets.len() < m {
+            let t = endpoints[rng.gen_range(0..endpoints.len())];
+            if !targets.contains(&t) {
+                targets.push(t);
+            }
+        }
+        for t in targets {
+            edges.push((t, n));
+            endpoints.extend([t, n]);
+        }
+    }
+    AdjacencyGraph::from_edges(nodes, edges).unwrap()
+}
+
+/// A random graph following the Erdős–Rényi model G(n, p), where each pair of distinct nodes is
+/// connected with probability p.
+pub fn erdos_renyi(nodes: usize, p: f64, seed: u64) -> AdjacencyGraph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let p = p.clamp(0., 1.);
+    let mut edges = Vec::new();
+    for u in 0..nodes {
+        for v in u + 1..nodes {
+            if rng.gen_bool(p) {
+                edges.push((u, v));
+            }
+        }
+    }
+    AdjacencyGraph::from_edges(nodes, edges).unwrap()
+}
+
+/// A small-world graph following the Watts–Strogatz model: a ring where every node is connected
+/// to its `k / 2` nearest neighbours on either side, after which the far end of each edge is
+/// rewired to a random node with probability `beta`, avoiding self loops and duplicate edges.
+///
+/// ```
+/// use rs_plode::graph::generators::watts_strogatz;
+/// use rs_plode::Graph;
+///
+/// let graph = watts_strogatz(30, 4, 0.1, 0);
+/// assert_eq!(graph.edges().count(), 60);
+/// ```
+pub fn watts_strogatz(nodes: usize, k: usize, beta: f64, seed: u64) -> AdjacencyGraph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let half = usize::min(k / 2, nodes.saturating_sub(1) / 2);
+    let mut edges: Vec<(usize, usize)> = (0..nodes)
+        .flat_map(|u| (1..=half).map(move |d| (u, (u + d) % nodes)))
+        .collect();
+    let mut neighbours = vec![BTreeSet::new(); nodes];
+    for &(u, v) in &edges {
+        neighbours[u].insert(v);
+        neighbours[v].insert(u);
+    }
+    for edge in edges.iter_mut() {
+        let (u, old) = *edge;
+        if !rng.gen_bool(beta.clamp(0., 1.)) {
+            continue;
+        }
+        let free = nodes - 1 - neighbours[u].len();
+        if free == 0 {
+            continue;
+        }
+        // the chosen free node, skipping u and its neighbours in ascending order
+        let mut v = rng.gen_range(0..free);
+        let mut taken: Vec<usize> = neighbours[u].iter().copied().chain([u]).collect();
+        taken.sort_unstable();
+        for t in taken {
+            if t <= v {
+                v += 1;
+            }
+        }
+        neighbours[u].remove(&old);
+        neighbours[old].remove(&u);
+        neighbours[u].insert(v);
+        neighbours[v].insert(u);
+        edge.1 = v;
+    }
+    AdjacencyGraph::from_edges(nodes, edges).unwrap()
+}
+
+/// A random graph with the given number of edges between uniformly chosen distinct nodes.
+/// Multiple edges between the same nodes are possible.
+pub fn random(nodes: usize, edges: usize, seed: u64) -> AdjacencyGraph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let edges: Vec<(usize, usize)> = if nodes < 2 {
+        Vec::new()
+    } else {
+        (0..edges)
+            .map(|_| {
+                let u = rng.gen_range(0..nodes);
+                let v = (u + rng.gen_range(1..nodes)) % nodes;
+                (u, v)
+            })
+            .collect()
+    };
+    AdjacencyGraph::from_edges(nodes, edges).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{complete, cycle, erdos_renyi, grid, path, random, scale_free, star, watts_strogatz};
+    use crate::Graph;
+
+    #[test]
+    fn generate() {
+        let g = grid(3, 4);
+        assert_eq!((g.nodes(), g.edges().count()), (12, 3 * 3 + 2 * 4));
+        assert_eq!((g.degree(0), g.degree(5)), (2, 4));
+
+        let g = scale_free(200, 1, 7);
+        assert_eq!(g.edges().count(), 199);
+        // preferential attachment creates hubs
+        assert!((0..200).map(|n| g.degree(n)).max().unwrap() > 10);
+        assert_eq!(scale_free(200, 1, 7), g);
+        assert_eq!(scale_free(1, 3, 0).nodes(), 1);
+
+        let g = random(10, 30, 1);
+        assert_eq!(g.edges().count(), 30);
+        assert!(g.edges().all(|(u, v)| u != v));
+        assert_eq!(random(1, 5, 0).edges().count(), 0);
+    }
+
+    #[test]
+    fn generate_classic_graphs() {
+        assert_eq!(path(4).edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(cycle(4).edges().count(), 4);
+        assert_eq!(cycle(2).edges().count(), 1);
+        assert_eq!(star(5).degree(0), 4);
+        assert_eq!(complete(5).edges().count(), 10);
+        assert_eq!(path(0).nodes(), 0);
+
+        assert_eq!(erdos_renyi(20, 0., 0).edges().count(), 0);
+        assert_eq!(erdos_renyi(20, 1., 0).edges().count(), 190);
+
+        // without rewiring, every node has k neighbours
+        let ring = watts_strogatz(10, 4, 0., 0);
+        assert!((0..10).all(|n| ring.degree(n) == 4));
+        let rewired = watts_strogatz(10, 4, 1., 3);
+        assert_eq!(rewired.edges().count(), 20);
+        assert!(rewired.edges().all(|(u, v)| u != v));
+        assert_ne!(rewired, ring);
+        // benchmark sizes, which rewire thousands of edges
+        let large = watts_strogatz(20000, 10, 0.2, 1);
+        assert_eq!(large.edges().count(), 100000);
+    }
+}
@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::{Float, Graph};
+
+/// Something that gives access to a graph of type `G`, while being a [`Graph`] itself.
+///
+/// Layouts store whatever graph type they were computed with. Computing them on a handle decides
+/// whether the layout owns the graph (`G`, `Box<G>`), borrows it (`&G`, `Cow<G>`) or shares it
+/// (`Rc<G>`, `Arc<G>`). Shared handles allow returning layouts from functions without cloning the
+/// graph or tying the layout to the lifetime of a borrow:
+///
+/// ```
+/// use std::rc::Rc;
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::graph::AdjacencyGraph;
+/// use rs_plode::layout::scatter::ScatterLayout;
+/// use rs_plode::Graph;
+///
+/// fn compute(graph: &Rc<AdjacencyGraph>) -> ScatterLayout<Rc<AdjacencyGraph>> {
+///     Rc::clone(graph).layout(FruchtermanReingold::default())
+/// }
+///
+/// let graph = Rc::new(AdjacencyGraph::from_edges(3, vec![(0, 1), (1, 2)]).unwrap());
+/// let layout = compute(&graph);
+/// assert_eq!(layout.graph().degree(1), 2);
+/// ```
+pub trait GraphHandle<G: Graph>: Graph {
+    /// The graph behind the handle.
+    fn graph(&self) -> &G;
+}
+
+impl<G: Graph> GraphHandle<G> for G {
+    fn graph(&self) -> &G {
+        self
+    }
+}
+
+impl<G: Graph> GraphHandle<G> for &G {
+    fn graph(&self) -> &G {
+        self
+    }
+}
+
+macro_rules! forward_graph {
+    ($($handle:ty),* $(,)?) => {$(
+        impl<G: Graph> Graph for $handle {
+            type Edges<'a> = G::Edges<'a> where Self: 'a;
+            fn nodes(&self) -> usize { (**self).nodes() }
+            fn edges(&self) -> G::Edges<'_> { (**self).edges() }
+            fn edge_weights(&self) -> Option<Vec<Float>> { (**self).edge_weights() }
+            fn is_directed(&self) -> bool { (**self).is_directed() }
+            fn edge_count_hint(&self) -> Option<usize> { (**self).edge_count_hint() }
+            fn node_label(&self, node: usize) -> Option<String> { (**self).node_label(node) }
+        }
+
+        impl<G: Graph> GraphHandle<G> for $handle {
+            fn graph(&self) -> &G {
+                self
+            }
+        }
+    )*};
+}
+
+forward_graph!(Box<G>, Rc<G>, Arc<G>);
+
+impl<G: Graph + Clone> Graph for Cow<'_, G> {
+    type Edges<'a> = G::Edges<'a> where Self: 'a;
+    fn nodes(&self) -> usize { (**self).nodes() }
+    fn edges(&self) -> G::Edges<'_> { (**self).edges() }
+    fn edge_weights(&self) -> Option<Vec<Float>> { (**self).edge_weights() }
+    fn is_directed(&self) -> bool { (**self).is_directed() }
+    fn edge_count_hint(&self) -> Option<usize> { (**self).edge_count_hint() }
+    fn node_label(&self, node: usize) -> Option<String> { (**self).node_label(node) }
+}
+
+impl<G: Graph + Clone> GraphHandle<G> for Cow<'_, G> {
+    fn graph(&self) -> &G {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    use super::GraphHandle;
+    use crate::engines::lattice::Grid;
+    use crate::graph::AdjacencyGraph;
+    use crate::Graph;
+
+    fn degree_sum<H: GraphHandle<AdjacencyGraph>>(handle: H) -> usize {
+        let layout = handle.layout(Grid::default());
+        let graph = layout.graph().graph();
+        (0..graph.nodes()).map(|n| graph.degree(n)).sum()
+    }
+
+    #[test]
+    fn layouts_on_handles() {
+        let graph = AdjacencyGraph::from_edges(3, vec![(0, 1), (1, 2)]).unwrap();
+        assert_eq!(degree_sum(&graph), 4);
+        assert_eq!(degree_sum(Rc::new(graph.clone())), 4);
+        assert_eq!(degree_sum(Arc::new(graph.clone())), 4);
+        assert_eq!(degree_sum(Box::new(graph.clone())), 4);
+        assert_eq!(degree_sum(Cow::Borrowed(&graph)), 4);
+        assert_eq!(degree_sum(graph), 4);
+    }
+}
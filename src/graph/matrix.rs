@@ -0,0 +1,149 @@
+use ndarray::iter::IndexedIter;
+use ndarray::{ArrayBase, Data, Ix2};
+
+use crate::{Float, Graph, WeightedGraph};
+
+/// The entry type of adjacency matrices: any non-zero entry is an edge, weighted by its value.
+pub trait AdjacencyEntry: PartialEq + 'static {
+    /// Whether the entry is an edge.
+    fn is_edge(&self) -> bool;
+
+    /// The weight of the edge, one for `true`.
+    fn weight(&self) -> Float;
+}
+
+impl AdjacencyEntry for bool {
+    fn is_edge(&self) -> bool {
+        *self
+    }
+
+    fn weight(&self) -> Float {
+        if *self { 1. } else { 0. }
+    }
+}
+
+impl AdjacencyEntry for f32 {
+    fn is_edge(&self) -> bool {
+        *self != 0.
+    }
+
+    #[allow(clippy::unnecessary_cast)] // with the f64 feature, `Float` is f64 instead
+    fn weight(&self) -> Float {
+        *self as Float
+    }
+}
+
+impl AdjacencyEntry for f64 {
+    fn is_edge(&self) -> bool {
+        *self != 0.
+    }
+
+    #[allow(clippy::unnecessary_cast)] // with the f64 feature, `Float` is f64 already
+    fn weight(&self) -> Float {
+        *self as Float
+    }
+}
+
+/// The weighted edges of an adjacency matrix, iterating its entries lazily in row major order.
+pub struct MatrixEdges<'a, A> {
+    entries: IndexedIter<'a, A, Ix2>,
+    directed: bool,
+}
+
+impl<'a, A: AdjacencyEntry> Iterator for MatrixEdges<'a, A> {
+    type Item = (usize, usize, Float);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries
+            .find(|((i, j), value)| (self.directed || i <= j) && value.is_edge())
+            .map(|((i, j), value)| (i, j, value.weight()))
+    }
+}
+
+/// The edge iterator of adjacency matrices.
+pub type MatrixEdgePairs<'a, A> = std::iter::Map<MatrixEdges<'a, A>, fn((usize, usize, Float)) -> (usize, usize)>;
+
+/// Adjacency matrices, owned or views, are graphs without copying them into an edge list: every
+/// non-zero (or `true`) entry `(i, j)` is an edge from i to j.
+///
+/// Like [`AdjacencyGraph::from_adjacency_matrix`](super::AdjacencyGraph::from_adjacency_matrix),
+/// symmetric matrices are undirected graphs with one edge per pair of nodes, from the upper
+/// triangle. Checking the symmetry and iterating the edges visit all entries of the matrix. Wrap
+/// the matrix into [`Weighted`](crate::Weighted) to use the entries as edge weights.
+///
+/// ```
+/// use ndarray::arr2;
+/// use rs_plode::{Graph, WeightedGraph};
+///
+/// let matrix = arr2(&[[false, true, true], [true, false, false], [true, false, false]]);
+/// assert!(!matrix.is_directed());
+/// assert_eq!(matrix.edges().collect::<Vec<_>>(), vec![(0, 1), (0, 2)]);
+///
+/// let weighted = arr2(&[[0., 2.], [0., 0.]]);
+/// assert!(weighted.is_directed());
+/// assert_eq!(weighted.view().weighted_edges().collect::<Vec<_>>(), vec![(0, 1, 2.)]);
+/// ```
+impl<S, A> Graph for ArrayBase<S, Ix2>
+where
+    S: Data<Elem = A>,
+    A: AdjacencyEntry,
+{
+    type Edges<'a> = MatrixEdgePairs<'a, A> where Self: 'a;
+
+    /// The larger dimension of the matrix, such that the edges of non-square matrices are valid.
+    fn nodes(&self) -> usize {
+        let (rows, columns) = self.dim();
+        usize::max(rows, columns)
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.weighted_edges().map(|(i, j, _)| (i, j))
+    }
+
+    fn is_directed(&self) -> bool {
+        let (rows, columns) = self.dim();
+        rows != columns || *self != self.t()
+    }
+}
+
+impl<S, A> WeightedGraph for ArrayBase<S, Ix2>
+where
+    S: Data<Elem = A>,
+    A: AdjacencyEntry,
+{
+    type WeightedEdges<'a> = MatrixEdges<'a, A> where Self: 'a;
+
+    fn weighted_edges(&self) -> Self::WeightedEdges<'_> {
+        MatrixEdges {
+            entries: self.indexed_iter(),
+            directed: self.is_directed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::{arr2, Array2};
+
+    use crate::engines::fruchterman_reingold::FruchtermanReingold;
+    use crate::{validate, Float, Graph, Weighted};
+
+    #[test]
+    fn matrix_graphs() {
+        let directed = arr2(&[[false, true, false], [false, false, true], [true, false, true]]);
+        assert!(directed.is_directed());
+        assert_eq!(directed.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2), (2, 0), (2, 2)]);
+
+        let weighted: Array2<Float> = arr2(&[[0., 0.5, 0.], [0.5, 0., 2.], [0., 2., 0.]]);
+        assert!(!weighted.is_directed());
+        assert_eq!(Weighted(weighted.view()).edge_weights(), Some(vec![0.5, 2.]));
+
+        // the edges of wide matrices reference nodes without a row
+        let wide = arr2(&[[0., 1., 1.]]);
+        assert_eq!(wide.nodes(), 3);
+        assert!(wide.is_directed() && validate(&wide).is_ok());
+
+        let layout = weighted.layout(FruchtermanReingold::default());
+        assert!(layout.bbox().width() > 0.);
+    }
+}
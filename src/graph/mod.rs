@@ -0,0 +1,14 @@
+//! Concrete graph types owned by the crate.
+mod adjacency;
+pub mod adapters;
+mod cache;
+pub mod generators;
+mod handle;
+mod matrix;
+mod subgraph;
+
+pub use adjacency::AdjacencyGraph;
+pub use cache::GraphCache;
+pub use handle::GraphHandle;
+pub use matrix::{AdjacencyEntry, MatrixEdgePairs, MatrixEdges};
+pub use subgraph::SubGraphView;
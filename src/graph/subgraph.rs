@@ -0,0 +1,131 @@
+use crate::{EdgeIter, Float, Graph};
+
+/// The subgraph induced by a subset of the nodes of another graph: the given nodes and all edges
+/// between them, with weights and direction of the original graph.
+///
+/// Nodes are renumbered consecutively in the order of the subset, [`original`](Self::original) and
+/// [`local`](Self::local) map between the indices of both graphs.
+///
+/// ```
+/// use rs_plode::graph::SubGraphView;
+/// use rs_plode::Graph;
+///
+/// let path = vec![(0usize, 1usize), (1, 2), (2, 3)];
+/// let sub = SubGraphView::induced(&path, &[3, 2, 0]).unwrap();
+/// assert_eq!(sub.nodes(), 3);
+/// assert_eq!(sub.edges().collect::<Vec<_>>(), vec![(1, 0)]);
+/// assert_eq!(sub.original(0), Some(3));
+/// assert_eq!(sub.local(1), None);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubGraphView {
+    /// the original index of each node
+    nodes: Vec<usize>,
+    edges: Vec<(usize, usize)>,
+    /// the original index of each edge
+    edge_indices: Vec<usize>,
+    weights: Option<Vec<Float>>,
+    directed: bool,
+}
+
+impl SubGraphView {
+    /// Extract the subgraph induced by the given nodes. Repeated nodes are kept only once.
+    pub fn induced(graph: &impl Graph, nodes: &[usize]) -> Result<Self, String> {
+        let total = graph.nodes();
+        let mut local = vec![None; total];
+        let mut kept = Vec::with_capacity(nodes.len());
+        for &n in nodes {
+            if n >= total {
+                return Err(format!("Node {} does not exist, the graph has {} nodes", n, total));
+            }
+            if local[n].is_none() {
+                local[n] = Some(kept.len());
+                kept.push(n);
+            }
+        }
+        let (mut edges, mut edge_indices) = (Vec::new(), Vec::new());
+        for (i, (u, v)) in graph.edges().enumerate() {
+            if let (Some(Some(u)), Some(Some(v))) = (local.get(u), local.get(v)) {
+                edges.push((*u, *v));
+                edge_indices.push(i);
+            }
+        }
+        let weights = graph.edge_weights().map(|w| edge_indices.iter().map(|&i| w[i]).collect());
+        Ok(Self {
+            nodes: kept,
+            edges,
+            edge_indices,
+            weights,
+            directed: graph.is_directed(),
+        })
+    }
+
+    /// The index in the original graph of the given node, if it exists.
+    pub fn original(&self, node: usize) -> Option<usize> {
+        self.nodes.get(node).copied()
+    }
+
+    /// The index in the subgraph of the given node of the original graph, if it is part of it.
+    pub fn local(&self, original: usize) -> Option<usize> {
+        self.nodes.iter().position(|&n| n == original)
+    }
+
+    /// The original index of each node.
+    pub fn original_nodes(&self) -> &[usize] {
+        &self.nodes
+    }
+
+    /// The original index of each edge, in the order of [`Graph::edges`].
+    pub fn original_edges(&self) -> &[usize] {
+        &self.edge_indices
+    }
+}
+
+impl Graph for SubGraphView {
+    type Edges<'a> = EdgeIter<'a>;
+
+    fn nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.edges.iter().copied()
+    }
+
+    fn edge_weights(&self) -> Option<Vec<Float>> {
+        self.weights.clone()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edges.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SubGraphView;
+    use crate::graph::AdjacencyGraph;
+    use crate::Graph;
+
+    #[test]
+    fn induced_subgraph() {
+        let graph = AdjacencyGraph::from_directed_edges(5, vec![(0, 1), (1, 2), (2, 0), (3, 4), (4, 4)])
+            .unwrap()
+            .with_weights(vec![1., 2., 3., 4., 5.])
+            .unwrap();
+        let sub = SubGraphView::induced(&graph, &[4, 2, 4, 1]).unwrap();
+        assert_eq!(sub.original_nodes(), &[4, 2, 1]);
+        assert_eq!(sub.edges().collect::<Vec<_>>(), vec![(2, 1), (0, 0)]);
+        assert_eq!(sub.original_edges(), &[1, 4]);
+        assert_eq!(sub.edge_weights(), Some(vec![2., 5.]));
+        assert!(sub.is_directed());
+        assert_eq!(sub.local(2), Some(1));
+        assert_eq!(sub.original(3), None);
+        assert!(SubGraphView::induced(&graph, &[5]).is_err());
+        assert_eq!(SubGraphView::induced(&graph, &[]).unwrap().nodes(), 0);
+    }
+}
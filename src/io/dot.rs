@@ -0,0 +1,448 @@
+//! A small parser for the Graphviz DOT language.
+//!
+//! Only the graph structure is read: node and edge statements, including subgraphs on either side
+//! of an edge. Attributes are skipped, except for the `weight` of edges.
+use std::collections::HashMap;
+
+use crate::{EdgeIter, Float, Graph};
+
+/// A graph read from a DOT document, mapping node names to consecutive indices in the order of
+/// their first appearance.
+///
+/// ```
+/// use rs_plode::io::dot::DotGraph;
+/// use rs_plode::Graph;
+///
+/// let graph = DotGraph::parse("digraph { a -> b -> c; a -> c [weight=2] }").unwrap();
+/// assert_eq!(graph.nodes(), 3);
+/// assert_eq!(graph.name(2), "c");
+/// assert_eq!(graph.index("b"), Some(1));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DotGraph {
+    names: Vec<String>,
+    edges: Vec<(usize, usize)>,
+    weights: Vec<Option<Float>>,
+    directed: bool,
+}
+
+impl DotGraph {
+    /// Parse a DOT document, returning a string describing the first syntax error.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            graph: DotGraph {
+                names: Vec::new(),
+                edges: Vec::new(),
+                weights: Vec::new(),
+                directed: false,
+            },
+            indices: HashMap::new(),
+        };
+        parser.document()?;
+        Ok(parser.graph)
+    }
+
+    /// Read and parse a DOT file.
+    pub fn read(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&source)
+    }
+
+    /// The name of the node with the given index.
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    /// The index of the node with the given name.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// The names of all nodes, indexed by node.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+impl Graph for DotGraph {
+    type Edges<'a> = EdgeIter<'a>;
+
+    fn nodes(&self) -> usize {
+        self.names.len()
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.edges.iter().copied()
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edges.len())
+    }
+
+    /// The `weight` attributes of the edges, if any edge has one. Other edges default to 1.
+    fn edge_weights(&self) -> Option<Vec<Float>> {
+        if self.weights.iter().any(Option::is_some) {
+            Some(self.weights.iter().map(|w| w.unwrap_or(1.)).collect())
+        } else {
+            None
+        }
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// The name of the node.
+    fn node_label(&self, node: usize) -> Option<String> {
+        self.names.get(node).cloned()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// An identifier, number or (quoted or html) string.
+    Id(String),
+    Punct(char),
+    Edge,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let (mut i, mut line) = (0, 1);
+    let mut line_start = true;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\n' => {
+                line += 1;
+                line_start = true;
+                i += 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            // preprocessor output lines
+            '#' if line_start => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    line += (chars[i] == '\n') as usize;
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(format!("Unterminated comment in line {}", line));
+                }
+                i += 2;
+            }
+            '-' if matches!(chars.get(i + 1), Some('-') | Some('>')) => {
+                tokens.push((Token::Edge, line));
+                i += 2;
+            }
+            '{' | '}' | '[' | ']' | ';' | ',' | '=' | ':' => {
+                tokens.push((Token::Punct(c), line));
+                i += 1;
+            }
+            '"' => {
+                let start = line;
+                let mut value = String::new();
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' && chars.get(i + 1) == Some(&'"') {
+                        i += 1;
+                    } else if chars[i] == '\\' && chars.get(i + 1) == Some(&'\n') {
+                        i += 2;
+                        line += 1;
+                        continue;
+                    }
+                    line += (chars[i] == '\n') as usize;
+                    value.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(format!("Unterminated string in line {}", start));
+                }
+                i += 1;
+                tokens.push((Token::Id(value), start));
+            }
+            '<' => {
+                let start = line;
+                let (mut depth, begin) = (0, i);
+                while i < chars.len() {
+                    match chars[i] {
+                        '<' => depth += 1,
+                        '>' => depth -= 1,
+                        '\n' => line += 1,
+                        _ => {}
+                    }
+                    i += 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                if depth != 0 {
+                    return Err(format!("Unterminated html string in line {}", start));
+                }
+                tokens.push((Token::Id(chars[begin + 1..i - 1].iter().collect()), start));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' || !c.is_ascii() => {
+                let begin = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.' || !chars[i].is_ascii() || (i == begin && chars[i] == '-')) {
+                    i += 1;
+                }
+                tokens.push((Token::Id(chars[begin..i].iter().collect()), line));
+            }
+            c => return Err(format!("Unexpected character '{}' in line {}", c, line)),
+        }
+        line_start = false;
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    graph: DotGraph,
+    indices: HashMap<String, usize>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(t, _)| t)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn error(&self, expected: &str) -> String {
+        match self.tokens.get(self.position) {
+            Some((token, line)) => format!("Expected {} but found {:?} in line {}", expected, token, line),
+            None => format!("Expected {} but reached the end of the document", expected),
+        }
+    }
+
+    fn keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Id(id)) if id.eq_ignore_ascii_case(keyword))
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{}'", c)))
+        }
+    }
+
+    fn id(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some(Token::Id(id)) => {
+                let id = id.clone();
+                self.position += 1;
+                Ok(id)
+            }
+            _ => Err(self.error("an identifier")),
+        }
+    }
+
+    fn node(&mut self, name: String) -> usize {
+        let names = &mut self.graph.names;
+        *self.indices.entry(name).or_insert_with_key(|name| {
+            names.push(name.clone());
+            names.len() - 1
+        })
+    }
+
+    fn document(&mut self) -> Result<(), String> {
+        if self.keyword("strict") {
+            self.position += 1;
+        }
+        if self.keyword("digraph") {
+            self.graph.directed = true;
+        } else if !self.keyword("graph") {
+            return Err(self.error("'graph' or 'digraph'"));
+        }
+        self.position += 1;
+        if let Some(Token::Id(_)) = self.peek() {
+            self.position += 1;
+        }
+        self.block()?;
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error("the end of the document")),
+        }
+    }
+
+    /// Parse `{ stmt_list }`, returning all nodes that appear in it.
+    fn block(&mut self) -> Result<Vec<usize>, String> {
+        self.expect('{')?;
+        let mut nodes = Vec::new();
+        while self.peek() != Some(&Token::Punct('}')) {
+            if self.peek().is_none() {
+                return Err(self.error("'}'"));
+            }
+            self.statement(&mut nodes)?;
+            if self.peek() == Some(&Token::Punct(';')) {
+                self.position += 1;
+            }
+        }
+        self.position += 1;
+        Ok(nodes)
+    }
+
+    fn statement(&mut self, nodes: &mut Vec<usize>) -> Result<(), String> {
+        // attribute defaults
+        if self.keyword("graph") || self.keyword("node") || self.keyword("edge") {
+            self.position += 1;
+            self.attributes()?;
+            return Ok(());
+        }
+        // graph attribute assignment
+        if matches!(self.peek(), Some(Token::Id(_))) && self.tokens.get(self.position + 1).map(|(t, _)| t) == Some(&Token::Punct('=')) {
+            self.position += 2;
+            self.id()?;
+            return Ok(());
+        }
+
+        let mut groups = vec![self.operand()?];
+        while self.peek() == Some(&Token::Edge) {
+            self.position += 1;
+            groups.push(self.operand()?);
+        }
+        let attributes = self.attributes()?;
+        let weight = attributes
+            .iter()
+            .find(|(key, _)| key == "weight")
+            .map(|(_, value)| value.parse::<Float>().map_err(|_| format!("Invalid edge weight '{}'", value)))
+            .transpose()?;
+        for pair in groups.windows(2) {
+            for &u in &pair[0] {
+                for &v in &pair[1] {
+                    self.graph.edges.push((u, v));
+                    self.graph.weights.push(weight);
+                }
+            }
+        }
+        nodes.extend(groups.into_iter().flatten());
+        Ok(())
+    }
+
+    /// Parse a node id with optional port or a subgraph, returning the nodes it refers to.
+    fn operand(&mut self) -> Result<Vec<usize>, String> {
+        if self.keyword("subgraph") {
+            self.position += 1;
+            if let Some(Token::Id(_)) = self.peek() {
+                self.position += 1;
+            }
+            return self.block();
+        }
+        if self.peek() == Some(&Token::Punct('{')) {
+            return self.block();
+        }
+        let name = self.id()?;
+        // ports and compass points do not affect the structure
+        while self.peek() == Some(&Token::Punct(':')) {
+            self.position += 1;
+            self.id()?;
+        }
+        Ok(vec![self.node(name)])
+    }
+
+    /// Parse any number of `[ a_list ]` blocks, returning the key value pairs.
+    fn attributes(&mut self) -> Result<Vec<(String, String)>, String> {
+        let mut attributes = Vec::new();
+        while self.peek() == Some(&Token::Punct('[')) {
+            self.position += 1;
+            while self.peek() != Some(&Token::Punct(']')) {
+                let key = self.id()?;
+                let value = match self.peek() {
+                    Some(Token::Punct('=')) => {
+                        self.position += 1;
+                        self.id()?
+                    }
+                    _ => String::from("true"),
+                };
+                attributes.push((key, value));
+                if let Some(Token::Punct(';' | ',')) = self.peek() {
+                    self.position += 1;
+                }
+            }
+            self.next();
+        }
+        Ok(attributes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DotGraph;
+    use crate::Graph;
+
+    #[test]
+    fn parse_statements() {
+        let graph = DotGraph::parse(
+            r#"
+            # generated
+            strict graph "name" {
+                graph [rankdir=LR]; node [shape=box]
+                label = "a graph" // comment
+                a -- "b c" -- d:port:n [color=red];
+                /* subgraphs connect all their nodes */
+                e -- { a; d }
+                f [label=<<b>f</b>>]
+                subgraph cluster { g }
+            }
+            "#,
+        )
+        .unwrap();
+        assert!(!graph.is_directed());
+        assert_eq!(graph.names(), ["a", "b c", "d", "e", "f", "g"]);
+        assert_eq!(graph.node_label(1).as_deref(), Some("b c"));
+        assert_eq!(graph.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2), (3, 0), (3, 2)]);
+        assert_eq!(graph.edge_weights(), None);
+    }
+
+    #[test]
+    fn parse_weights() {
+        let graph = DotGraph::parse("digraph { 0 -> 1 [weight=2.5]; 1 -> 2 }").unwrap();
+        assert!(graph.is_directed());
+        assert_eq!(graph.edge_weights(), Some(vec![2.5, 1.]));
+    }
+
+    #[test]
+    fn report_errors() {
+        assert!(DotGraph::parse("graph { a -- }").unwrap_err().contains("identifier"));
+        assert!(DotGraph::parse("graph { a -- b").unwrap_err().contains("end of the document"));
+        assert!(DotGraph::parse("tree { }").is_err());
+        assert!(DotGraph::parse("graph { \"a }").unwrap_err().contains("line 1"));
+    }
+
+    #[test]
+    fn roundtrip_export() {
+        use crate::layout::scatter::ScatterLayout;
+        use crate::render::dot::RenderDOT;
+
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (1, 2)], ndarray::arr2(&[[0., 0.], [1., 0.], [2., 1.]])).unwrap();
+        let graph = DotGraph::parse(&layout.to_dot().unwrap()).unwrap();
+        assert_eq!(graph.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+        assert_eq!(graph.names(), ["0", "1", "2"]);
+    }
+}
@@ -0,0 +1,144 @@
+//! Reading plain text edge lists, one edge per line.
+//!
+//! Columns are separated by whitespace or commas. The first two columns name the source and target
+//! node, an optional third column holds the weight of the edge. Empty lines and lines starting
+//! with `#` or `%` are ignored.
+use std::collections::HashMap;
+
+use crate::{EdgeIter, Float, Graph};
+
+/// A graph read from an edge list, mapping node names to consecutive indices in the order of
+/// their first appearance.
+///
+/// ```
+/// use rs_plode::io::edgelist::EdgeListGraph;
+/// use rs_plode::Graph;
+///
+/// let graph = EdgeListGraph::parse("# roads\nberlin,hamburg,289\nhamburg,bremen,125\n").unwrap();
+/// assert_eq!(graph.nodes(), 3);
+/// assert_eq!(graph.name(2), "bremen");
+/// assert_eq!(graph.edge_weights(), Some(vec![289., 125.]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct EdgeListGraph {
+    names: Vec<String>,
+    edges: Vec<(usize, usize)>,
+    weights: Vec<Option<Float>>,
+}
+
+impl EdgeListGraph {
+    /// Parse an edge list, returning a string describing the first malformed line.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut graph = Self {
+            names: Vec::new(),
+            edges: Vec::new(),
+            weights: Vec::new(),
+        };
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        let mut node = |name: &str, names: &mut Vec<String>| -> usize {
+            *indices.entry(name.to_owned()).or_insert_with(|| {
+                names.push(name.to_owned());
+                names.len() - 1
+            })
+        };
+
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('%') {
+                continue;
+            }
+            let columns: Vec<&str> = line
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|c| !c.is_empty())
+                .collect();
+            let weight = match columns.as_slice() {
+                [_, _] => None,
+                [_, _, weight, ..] => Some(
+                    weight
+                        .parse::<Float>()
+                        .map_err(|_| format!("Invalid weight '{}' in line {}", weight, i + 1))?,
+                ),
+                _ => return Err(format!("Expected source and target in line {}", i + 1)),
+            };
+            let source = node(columns[0], &mut graph.names);
+            let target = node(columns[1], &mut graph.names);
+            graph.edges.push((source, target));
+            graph.weights.push(weight);
+        }
+        Ok(graph)
+    }
+
+    /// Read and parse an edge list file.
+    pub fn read(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&source)
+    }
+
+    /// The name of the node with the given index.
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    /// The index of the node with the given name.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// The names of all nodes, indexed by node.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+impl Graph for EdgeListGraph {
+    type Edges<'a> = EdgeIter<'a>;
+
+    fn nodes(&self) -> usize {
+        self.names.len()
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.edges.iter().copied()
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edges.len())
+    }
+
+    /// The weights of the edges, if any line has a weight column. Other edges default to 1.
+    fn edge_weights(&self) -> Option<Vec<Float>> {
+        if self.weights.iter().any(Option::is_some) {
+            Some(self.weights.iter().map(|w| w.unwrap_or(1.)).collect())
+        } else {
+            None
+        }
+    }
+
+    /// The name of the node.
+    fn node_label(&self, node: usize) -> Option<String> {
+        self.names.get(node).cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EdgeListGraph;
+    use crate::Graph;
+
+    #[test]
+    fn parse_edge_list() {
+        let graph = EdgeListGraph::parse("% tsv\n1\t2\n\n2 3\n  # indented comment\n3, 1, 0.5\n").unwrap();
+        assert_eq!(graph.names(), ["1", "2", "3"]);
+        assert_eq!(graph.node_label(2).as_deref(), Some("3"));
+        assert_eq!(graph.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(graph.edge_weights(), Some(vec![1., 1., 0.5]));
+        assert_eq!(EdgeListGraph::parse("a b").unwrap().edge_weights(), None);
+    }
+
+    #[test]
+    fn report_errors() {
+        assert_eq!(EdgeListGraph::parse("a b\nc\n").unwrap_err(), "Expected source and target in line 2");
+        assert!(EdgeListGraph::parse("a b heavy").unwrap_err().contains("'heavy'"));
+    }
+}
@@ -0,0 +1,266 @@
+//! Reading graphs from and writing layouts to GraphML.
+//!
+//! Only the first graph of a document is read, nested graphs and hyperedges are not supported.
+//! Edge weights are read from the `weight` attribute and node positions from the `x` and `y`
+//! attributes, which are also the ones written by [`to_graphml`].
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use ndarray::Array2;
+
+use crate::layout::scatter::ScatterLayout;
+use crate::{validate, EdgeIter, Float, Graph};
+
+/// A graph read from a GraphML document, mapping node ids to consecutive indices in document order.
+///
+/// ```
+/// use rs_plode::io::graphml::GraphMLGraph;
+/// use rs_plode::Graph;
+///
+/// let graph = GraphMLGraph::parse(r#"
+///     <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+///       <graph edgedefault="undirected">
+///         <node id="a"/><node id="b"/>
+///         <edge source="a" target="b"/>
+///       </graph>
+///     </graphml>"#).unwrap();
+/// assert_eq!(graph.edges().collect::<Vec<_>>(), vec![(0, 1)]);
+/// assert_eq!(graph.id(1), "b");
+/// ```
+#[derive(Clone, Debug)]
+pub struct GraphMLGraph {
+    ids: Vec<String>,
+    edges: Vec<(usize, usize)>,
+    weights: Option<Vec<Float>>,
+    positions: Option<Array2<Float>>,
+    directed: bool,
+}
+
+impl GraphMLGraph {
+    /// Parse a GraphML document, returning a string describing the first error.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let document = roxmltree::Document::parse(source).map_err(|e| format!("Invalid xml: {}", e))?;
+        let root = document.root_element();
+        if root.tag_name().name() != "graphml" {
+            return Err(format!("Expected a graphml document, found <{}>", root.tag_name().name()));
+        }
+
+        // attribute name and default value of each key id, ordered such that defaults of keys with the
+        // same name resolve the same way on every run
+        let mut keys = BTreeMap::new();
+        for key in root.children().filter(|n| n.tag_name().name() == "key") {
+            let (Some(id), Some(name)) = (key.attribute("id"), key.attribute("attr.name")) else {
+                continue;
+            };
+            let default = key
+                .children()
+                .find(|n| n.tag_name().name() == "default")
+                .and_then(|n| n.text())
+                .map(str::to_owned);
+            keys.insert(id, (name, default));
+        }
+        // the value of the attribute with given name from the data children of an element
+        let value = |element: roxmltree::Node, name: &str| -> Result<Option<Float>, String> {
+            let data = element.children().filter(|n| n.tag_name().name() == "data").find_map(|n| {
+                match keys.get(n.attribute("key")?) {
+                    Some((key, _)) if *key == name => Some(n.text().unwrap_or("").to_owned()),
+                    _ => None,
+                }
+            });
+            let default = || keys.values().find(|(key, _)| *key == name).and_then(|(_, d)| d.clone());
+            data.or_else(default)
+                .map(|v| v.trim().parse::<Float>().map_err(|_| format!("Invalid {} value '{}'", name, v)))
+                .transpose()
+        };
+
+        let graph = root
+            .children()
+            .find(|n| n.tag_name().name() == "graph")
+            .ok_or("The document contains no graph")?;
+        let directed = graph.attribute("edgedefault") != Some("undirected");
+
+        let mut ids = Vec::new();
+        let mut indices = HashMap::new();
+        let mut coordinates = Vec::new();
+        for node in graph.children().filter(|n| n.tag_name().name() == "node") {
+            let id = node.attribute("id").ok_or("Node without id")?;
+            if indices.insert(id, ids.len()).is_some() {
+                return Err(format!("Duplicate node id '{}'", id));
+            }
+            ids.push(id.to_owned());
+            coordinates.push((value(node, "x")?, value(node, "y")?));
+        }
+
+        let mut edges = Vec::new();
+        let mut weights = Vec::new();
+        for edge in graph.children().filter(|n| n.tag_name().name() == "edge") {
+            let node = |attribute: &str| -> Result<usize, String> {
+                let id = edge.attribute(attribute).ok_or_else(|| format!("Edge without {}", attribute))?;
+                indices.get(id).copied().ok_or_else(|| format!("Edge references unknown node '{}'", id))
+            };
+            edges.push((node("source")?, node("target")?));
+            weights.push(value(edge, "weight")?);
+        }
+
+        let positions = if !ids.is_empty() && coordinates.iter().all(|(x, y)| x.is_some() && y.is_some()) {
+            let flat: Vec<Float> = coordinates.iter().flat_map(|&(x, y)| [x.unwrap(), y.unwrap()]).collect();
+            Some(Array2::from_shape_vec((ids.len(), 2), flat).unwrap())
+        } else {
+            None
+        };
+        Ok(Self {
+            ids,
+            edges,
+            weights: if weights.iter().any(Option::is_some) {
+                Some(weights.iter().map(|w| w.unwrap_or(1.)).collect())
+            } else {
+                None
+            },
+            positions,
+            directed,
+        })
+    }
+
+    /// Read and parse a GraphML file.
+    pub fn read(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&source)
+    }
+
+    /// The id of the node with the given index.
+    pub fn id(&self, node: usize) -> &str {
+        &self.ids[node]
+    }
+
+    /// The index of the node with the given id.
+    pub fn index(&self, id: &str) -> Option<usize> {
+        self.ids.iter().position(|n| n == id)
+    }
+
+    /// The node positions, if all nodes have `x` and `y` attributes.
+    pub fn positions(&self) -> Option<&Array2<Float>> {
+        self.positions.as_ref()
+    }
+}
+
+impl Graph for GraphMLGraph {
+    type Edges<'a> = EdgeIter<'a>;
+
+    fn nodes(&self) -> usize {
+        self.ids.len()
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.edges.iter().copied()
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edges.len())
+    }
+
+    fn edge_weights(&self) -> Option<Vec<Float>> {
+        self.weights.clone()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// The id of the node.
+    fn node_label(&self, node: usize) -> Option<String> {
+        self.ids.get(node).cloned()
+    }
+}
+
+/// Serialize a layout to GraphML, storing the node positions in `x` and `y` attributes.
+pub fn to_graphml<G: Graph>(layout: &ScatterLayout<G>) -> Result<String, String> {
+    validate(&layout.graph)?;
+    let weights = layout.graph.edge_weights();
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    xml.push_str("  <key id=\"x\" for=\"node\" attr.name=\"x\" attr.type=\"float\"/>\n");
+    xml.push_str("  <key id=\"y\" for=\"node\" attr.name=\"y\" attr.type=\"float\"/>\n");
+    if weights.is_some() {
+        xml.push_str("  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"float\"/>\n");
+    }
+    let edgedefault = if layout.graph.is_directed() { "directed" } else { "undirected" };
+    writeln!(xml, "  <graph edgedefault=\"{}\">", edgedefault).unwrap();
+    for n in 0..layout.graph.nodes() {
+        let p = layout.coord(n);
+        writeln!(
+            xml,
+            "    <node id=\"n{}\"><data key=\"x\">{}</data><data key=\"y\">{}</data></node>",
+            n,
+            p.x(),
+            p.y()
+        )
+        .unwrap();
+    }
+    for (i, (u, v)) in layout.graph.edges().enumerate() {
+        match weights.as_ref().and_then(|w| w.get(i)) {
+            Some(w) => writeln!(
+                xml,
+                "    <edge source=\"n{}\" target=\"n{}\"><data key=\"weight\">{}</data></edge>",
+                u, v, w
+            )
+            .unwrap(),
+            None => writeln!(xml, "    <edge source=\"n{}\" target=\"n{}\"/>", u, v).unwrap(),
+        }
+    }
+    xml.push_str("  </graph>\n</graphml>\n");
+    Ok(xml)
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{to_graphml, GraphMLGraph};
+    use crate::layout::scatter::ScatterLayout;
+    use crate::{Float, Graph, Weighted};
+
+    #[test]
+    fn parse_attributes() {
+        let graph = GraphMLGraph::parse(
+            r#"<?xml version="1.0"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="edge" attr.name="weight" attr.type="double"><default>2</default></key>
+              <key id="d1" for="node" attr.name="color" attr.type="string"/>
+              <graph id="G">
+                <node id="a"><data key="d1">red</data></node>
+                <node id="b"/>
+                <edge source="a" target="b"><data key="d0">0.5</data></edge>
+                <edge source="b" target="a"/>
+              </graph>
+            </graphml>"#,
+        )
+        .unwrap();
+        assert!(graph.is_directed());
+        assert_eq!(graph.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(graph.edge_weights(), Some(vec![0.5, 2.]));
+        assert_eq!(graph.index("b"), Some(1));
+        assert_eq!(graph.node_label(0).as_deref(), Some("a"));
+        assert!(graph.positions().is_none());
+    }
+
+    #[test]
+    fn report_errors() {
+        assert!(GraphMLGraph::parse("<graphml><graph>").is_err());
+        assert!(GraphMLGraph::parse("<svg/>").unwrap_err().contains("graphml"));
+        let unknown = "<graphml><graph><node id=\"a\"/><edge source=\"a\" target=\"b\"/></graph></graphml>";
+        assert!(GraphMLGraph::parse(unknown).unwrap_err().contains("'b'"));
+    }
+
+    #[test]
+    fn roundtrip_layout() {
+        let graph: Vec<(usize, usize, Float)> = vec![(0, 1, 3.), (1, 2, 1.)];
+        let layout = ScatterLayout::new(Weighted(&graph), arr2(&[[0., 0.5], [1., 0.], [2., -1.]])).unwrap();
+        let parsed = GraphMLGraph::parse(&to_graphml(&layout).unwrap()).unwrap();
+        assert!(!parsed.is_directed());
+        assert_eq!(parsed.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+        assert_eq!(parsed.edge_weights(), Some(vec![3., 1.]));
+        assert_eq!(parsed.positions(), Some(&arr2(&[[0., 0.5], [1., 0.], [2., -1.]])));
+    }
+}
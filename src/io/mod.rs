@@ -0,0 +1,7 @@
+//! Reading graphs from and writing layouts to common file formats.
+#[cfg(feature = "dot")]
+pub mod dot;
+pub mod edgelist;
+#[cfg(feature = "graphml")]
+pub mod graphml;
+pub mod report;
@@ -0,0 +1,150 @@
+//! Anonymized, reproducible bug reports of graphs and engine parameters.
+//!
+//! Reports are written as edge lists (see [`crate::io::edgelist`]), whose header comments hold the
+//! node count, the direction and the engine parameters, such that they can be attached to issues
+//! and read back with [`EdgeListGraph::read`](crate::io::edgelist::EdgeListGraph::read).
+use std::fmt::{Display, Write};
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::graph::AdjacencyGraph;
+use crate::{validate, Graph};
+
+/// Copy the structure of the graph with randomly relabeled nodes and shuffled edges, keeping edge
+/// weights and direction. The ends of undirected edges are swapped at random.
+///
+/// Nodes are numbered in the order of their first appearance in the shuffled edges, followed by
+/// isolated nodes, such that edge lists of the copy read back with the same indices.
+pub fn anonymize(graph: &impl Graph, seed: u64) -> Result<AdjacencyGraph, String> {
+    validate(graph)?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let weights = graph.edge_weights();
+    let mut edges: Vec<(usize, (usize, usize))> = graph.edges().enumerate().collect();
+    edges.shuffle(&mut rng);
+    if !graph.is_directed() {
+        for (_, (u, v)) in edges.iter_mut() {
+            if rng.gen_bool(0.5) {
+                std::mem::swap(u, v);
+            }
+        }
+    }
+
+    let mut labels: Vec<Option<usize>> = vec![None; graph.nodes()];
+    let mut next = 0;
+    let mut label = |node: usize| {
+        *labels[node].get_or_insert_with(|| {
+            next += 1;
+            next - 1
+        })
+    };
+    let relabeled: Vec<(usize, usize)> = edges.iter().map(|&(_, (u, v))| (label(u), label(v))).collect();
+    let anonymized = match graph.is_directed() {
+        true => AdjacencyGraph::from_directed_edges(graph.nodes(), relabeled)?,
+        false => AdjacencyGraph::from_edges(graph.nodes(), relabeled)?,
+    };
+    match weights {
+        Some(weights) => anonymized.with_weights(edges.iter().map(|&(i, _)| weights[i]).collect()),
+        None => Ok(anonymized),
+    }
+}
+
+/// An anonymized graph together with the parameters of the engine that failed on it.
+///
+/// ```
+/// use rs_plode::io::report::BugReport;
+///
+/// let graph: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 0)];
+/// let report = BugReport::new(&graph, 42)?.parameter("engine", "fruchterman-reingold").parameter("k", 150.);
+/// assert!(report.to_edgelist().starts_with("# rs-plode bug report\n# nodes = 3\n# directed = false\n# engine = fruchterman-reingold\n# k = 150\n"));
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct BugReport {
+    graph: AdjacencyGraph,
+    parameters: Vec<(String, String)>,
+}
+
+impl BugReport {
+    /// Anonymize the graph with the given seed, see [`anonymize`].
+    pub fn new(graph: &impl Graph, seed: u64) -> Result<Self, String> {
+        Ok(Self {
+            graph: anonymize(graph, seed)?,
+            parameters: Vec::new(),
+        })
+    }
+
+    /// Record a parameter of the engine, e.g. its `k`, seed or iteration count.
+    pub fn parameter(mut self, name: impl Into<String>, value: impl Display) -> Self {
+        self.parameters.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// The anonymized graph.
+    pub fn graph(&self) -> &AdjacencyGraph {
+        &self.graph
+    }
+
+    /// Serialize the report into an edge list with header comments.
+    pub fn to_edgelist(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "# rs-plode bug report").unwrap();
+        writeln!(out, "# nodes = {}", self.graph.nodes()).unwrap();
+        writeln!(out, "# directed = {}", self.graph.is_directed()).unwrap();
+        for (name, value) in &self.parameters {
+            // keep multi line values within the comment
+            writeln!(out, "# {} = {}", name, value.replace('\n', " ")).unwrap();
+        }
+        let weights = self.graph.edge_weights();
+        for (i, (u, v)) in self.graph.edges().enumerate() {
+            match weights.as_ref() {
+                Some(w) => writeln!(out, "{} {} {}", u, v, w[i]).unwrap(),
+                None => writeln!(out, "{} {}", u, v).unwrap(),
+            }
+        }
+        out
+    }
+
+    /// Write the report to the given file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_edgelist()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{anonymize, BugReport};
+    use crate::graph::AdjacencyGraph;
+    use crate::io::edgelist::EdgeListGraph;
+    use crate::Graph;
+
+    #[test]
+    fn anonymize_preserves_structure() {
+        let graph = AdjacencyGraph::from_directed_edges(6, vec![(0, 1), (0, 2), (0, 3), (3, 4)])
+            .unwrap()
+            .with_weights(vec![1., 2., 3., 4.])
+            .unwrap();
+        let copy = anonymize(&graph, 7).unwrap();
+        assert!(copy.is_directed());
+        assert_eq!(copy.nodes(), 6);
+        let mut degrees: Vec<(usize, usize)> = (0..6).map(|n| (copy.out_degree(n), copy.in_degree(n))).collect();
+        degrees.sort();
+        assert_eq!(degrees, vec![(0, 0), (0, 1), (0, 1), (0, 1), (1, 1), (3, 0)]);
+        // the weight of the edge to the leaf below node 3 stays 4
+        let weights = copy.edge_weights().unwrap();
+        let (i, _) = copy.edges().enumerate().find(|&(_, (u, _))| copy.out_degree(u) == 1).unwrap();
+        assert_eq!(weights[i], 4.);
+        assert_eq!(anonymize(&graph, 7).unwrap(), copy);
+
+        // reports read back with the same indices, besides isolated nodes
+        let report = BugReport::new(&graph, 3).unwrap().parameter("seed", 3);
+        let read = EdgeListGraph::parse(&report.to_edgelist()).unwrap();
+        let edges: Vec<(usize, usize)> = report.graph().edges().collect();
+        assert_eq!(read.edges().collect::<Vec<_>>(), edges);
+        assert_eq!(read.edge_weights(), report.graph().edge_weights());
+        assert_eq!(read.name(0), "0");
+    }
+}
@@ -0,0 +1,235 @@
+//! Force directed edge bundling of finished layouts.
+//!
+//! Original paper: Holten and van Wijk, Force-Directed Edge Bundling for Graph Visualization,
+//! https://doi.org/10.1111/j.1467-8659.2009.01450.x
+use crate::{Float, Graph};
+
+use super::scatter::ScatterLayout;
+use super::Point;
+
+/// Bundles edges by subdividing them into polylines, whose points attract the corresponding
+/// points of compatible edges, i.e. edges of similar direction, length and position.
+///
+/// Each cycle doubles the number of subdivision points per edge and halves the step size, while
+/// the iterations per cycle decrease by a third.
+///
+/// ```
+/// use ndarray::arr2;
+/// use rs_plode::layout::bundling::EdgeBundling;
+/// use rs_plode::layout::scatter::ScatterLayout;
+///
+/// // two parallel edges
+/// let layout = ScatterLayout::new(vec![(0, 1), (2, 3)], arr2(&[[0., 0.], [100., 0.], [0., 10.], [100., 10.]])).unwrap();
+/// let bundled = EdgeBundling::default().bundle(layout);
+/// let (upper, lower) = (&bundled.paths()[0], &bundled.paths()[1]);
+/// let middle = upper.len() / 2;
+/// assert!(lower[middle].y() - upper[middle].y() < 10.);
+/// ```
+#[derive(Clone, Debug)]
+pub struct EdgeBundling {
+    cycles: usize,
+    iterations: usize,
+    step: Float,
+    stiffness: Float,
+    compatibility: Float,
+}
+
+impl Default for EdgeBundling {
+    fn default() -> Self {
+        Self {
+            cycles: 6,
+            iterations: 50,
+            step: 0.1,
+            stiffness: 0.1,
+            compatibility: 0.6,
+        }
+    }
+}
+
+/// A layout whose edges are drawn along polylines, see [`EdgeBundling`].
+#[derive(Clone, Debug)]
+pub struct BundledLayout<G: Graph> {
+    layout: ScatterLayout<G>,
+    paths: Vec<Vec<Point>>,
+}
+
+impl<G: Graph> BundledLayout<G> {
+    /// The layout of the nodes.
+    pub fn layout(&self) -> &ScatterLayout<G> {
+        &self.layout
+    }
+
+    /// The polyline of each edge from its source to its target, in the order of `edges()`.
+    pub fn paths(&self) -> &[Vec<Point>] {
+        &self.paths
+    }
+
+    /// Split into the layout of the nodes and the edge paths.
+    pub fn into_parts(self) -> (ScatterLayout<G>, Vec<Vec<Point>>) {
+        (self.layout, self.paths)
+    }
+}
+
+impl EdgeBundling {
+    /// Set the number of subdivision cycles, the final polylines have `2^cycles` segments.
+    pub fn cycles(mut self, cycles: usize) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// Set the number of iterations of the first cycle.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Set the step size of the first cycle, which scales all forces.
+    pub fn step(mut self, step: Float) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set the spring constant that keeps edges straight, higher values give looser bundles.
+    pub fn stiffness(mut self, stiffness: Float) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    /// Set the minimal compatibility between 0 and 1 for two edges to attract each other.
+    pub fn compatibility(mut self, threshold: Float) -> Self {
+        self.compatibility = threshold;
+        self
+    }
+
+    /// Bundle the edges of the layout. Self loops and edges of zero length stay straight.
+    pub fn bundle<G: Graph>(&self, layout: ScatterLayout<G>) -> BundledLayout<G> {
+        let edges: Vec<(Point, Point)> = layout.graph().edges().map(|(u, v)| (layout.coord(u), layout.coord(v))).collect();
+        let compatible: Vec<Vec<usize>> = (0..edges.len())
+            .map(|e| {
+                (0..edges.len())
+                    .filter(|&f| f != e && compatibility(edges[e], edges[f]) >= self.compatibility)
+                    .collect()
+            })
+            .collect();
+
+        let mut paths: Vec<Vec<Point>> = edges.iter().map(|&(a, b)| vec![a, b]).collect();
+        let mut step = self.step;
+        let mut iterations = self.iterations as Float;
+        for _ in 0..self.cycles {
+            paths = paths.iter().map(|path| subdivide(path)).collect();
+            for _ in 0..iterations.round() as usize {
+                let forces: Vec<Vec<Point>> = (0..paths.len())
+                    .map(|e| self.forces(e, &edges, &paths, &compatible[e]))
+                    .collect();
+                for (path, force) in paths.iter_mut().zip(forces) {
+                    for (p, f) in path.iter_mut().zip(force) {
+                        *p = Point([p.x() + step * f.x(), p.y() + step * f.y()]);
+                    }
+                }
+            }
+            step /= 2.;
+            iterations *= 2. / 3.;
+        }
+        BundledLayout { layout, paths }
+    }
+
+    /// The force on each point of the edge, zero for its fixed end points.
+    fn forces(&self, e: usize, edges: &[(Point, Point)], paths: &[Vec<Point>], compatible: &[usize]) -> Vec<Point> {
+        let path = &paths[e];
+        let mut forces = vec![Point([0., 0.]); path.len()];
+        let length = distance(edges[e].0, edges[e].1);
+        if length == 0. {
+            return forces;
+        }
+        let spring = self.stiffness / (length * (path.len() - 1) as Float);
+        for i in 1..path.len() - 1 {
+            let (prev, p, next) = (path[i - 1], path[i], path[i + 1]);
+            let mut fx = spring * (prev.x() - p.x() + next.x() - p.x());
+            let mut fy = spring * (prev.y() - p.y() + next.y() - p.y());
+            for &f in compatible {
+                let q = paths[f][i];
+                let d = distance(p, q);
+                if d > 0. {
+                    fx += (q.x() - p.x()) / d;
+                    fy += (q.y() - p.y()) / d;
+                }
+            }
+            forces[i] = Point([fx, fy]);
+        }
+        forces
+    }
+}
+
+fn distance(a: Point, b: Point) -> Float {
+    ((a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2)).sqrt()
+}
+
+/// Insert a point in the middle of each segment.
+fn subdivide(path: &[Point]) -> Vec<Point> {
+    let mut result = Vec::with_capacity(2 * path.len() - 1);
+    for pair in path.windows(2) {
+        result.push(pair[0]);
+        result.push(Point([(pair[0].x() + pair[1].x()) / 2., (pair[0].y() + pair[1].y()) / 2.]));
+    }
+    result.extend(path.last());
+    result
+}
+
+/// The product of the angle, scale, position and visibility compatibility of two edges.
+fn compatibility(p: (Point, Point), q: (Point, Point)) -> Float {
+    let (lp, lq) = (distance(p.0, p.1), distance(q.0, q.1));
+    if lp == 0. || lq == 0. {
+        return 0.;
+    }
+    let dot = (p.1.x() - p.0.x()) * (q.1.x() - q.0.x()) + (p.1.y() - p.0.y()) * (q.1.y() - q.0.y());
+    let angle = (dot / (lp * lq)).abs();
+    let average = (lp + lq) / 2.;
+    let scale = 2. / (average / lp.min(lq) + lp.max(lq) / average);
+    let middle = |e: (Point, Point)| Point([(e.0.x() + e.1.x()) / 2., (e.0.y() + e.1.y()) / 2.]);
+    let position = average / (average + distance(middle(p), middle(q)));
+    angle * scale * position * visibility(p, q).min(visibility(q, p))
+}
+
+/// How much of edge p is visible from edge q, by projecting q onto the line through p.
+fn visibility(p: (Point, Point), q: (Point, Point)) -> Float {
+    let project = |x: Point| -> Point {
+        let (dx, dy) = (p.1.x() - p.0.x(), p.1.y() - p.0.y());
+        let t = ((x.x() - p.0.x()) * dx + (x.y() - p.0.y()) * dy) / (dx * dx + dy * dy);
+        Point([p.0.x() + t * dx, p.0.y() + t * dy])
+    };
+    let (i0, i1) = (project(q.0), project(q.1));
+    let middle_i = Point([(i0.x() + i1.x()) / 2., (i0.y() + i1.y()) / 2.]);
+    let middle_p = Point([(p.0.x() + p.1.x()) / 2., (p.0.y() + p.1.y()) / 2.]);
+    let length = distance(i0, i1);
+    if length == 0. {
+        return 0.;
+    }
+    (1. - 2. * distance(middle_p, middle_i) / length).max(0.)
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{compatibility, EdgeBundling};
+    use crate::layout::scatter::ScatterLayout;
+    use crate::layout::Point;
+
+    #[test]
+    fn bundle_parallel_edges_only() {
+        let (a, b) = (Point([0., 0.]), Point([100., 0.]));
+        assert!((compatibility((a, b), (Point([0., 10.]), Point([100., 10.]))) - 100. / 110.).abs() < 1e-6);
+        assert_eq!(compatibility((a, b), (Point([50., -50.]), Point([50., 50.]))), 0.);
+
+        // two parallel edges and a perpendicular one
+        let positions = arr2(&[[0., 0.], [100., 0.], [0., 10.], [100., 10.], [50., -50.], [50., -150.]]);
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (2, 3), (4, 5)], positions).unwrap();
+        let bundled = EdgeBundling::default().cycles(4).bundle(layout);
+        let paths = bundled.paths();
+        assert!(paths.iter().all(|p| p.len() == 17));
+        assert_eq!((paths[0][0], paths[0][16]), (a, b));
+        assert!(paths[1][8].y() - paths[0][8].y() < 5.);
+        // the perpendicular edge stays straight
+        assert!(paths[2].iter().all(|p| p.x() == 50.));
+    }
+}
@@ -1,38 +1,124 @@
+use std::fmt;
+
+use crate::Float;
+
+pub mod bundling;
+pub mod postprocess;
 pub mod scatter;
 
-#[derive(Debug, Clone, Copy)]
-pub struct Point(pub f32, pub f32);
+/// A position in D dimensional space, by default in the plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<const D: usize = 2>(pub [Float; D]);
 
-impl Point {
-    pub fn x(&self) -> f32 {
-        self.0
+/// A position in 3D space.
+pub type Point3 = Point<3>;
+
+impl Point<3> {
+    pub fn z(&self) -> Float {
+        self.0[2]
     }
-    pub fn y(&self) -> f32 {
-        self.1
+}
+
+impl<const D: usize> Point<D> {
+    pub fn x(&self) -> Float {
+        self.0[0]
+    }
+    pub fn y(&self) -> Float {
+        self.0[1]
+    }
+
+    /// The coordinate along the given axis.
+    pub fn get(&self, axis: usize) -> Float {
+        self.0[axis]
     }
 }
 
+/// An axis aligned box given by its minimal and maximal corner.
 #[derive(Debug, Clone, Copy)]
-pub struct BoundingBox(pub Point, pub Point);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundingBox<const D: usize = 2>(pub Point<D>, pub Point<D>);
 
-impl BoundingBox {
-    pub fn lower_left(&self) -> Point {
+/// An axis aligned box in 3D space.
+pub type BoundingBox3 = BoundingBox<3>;
+
+impl<const D: usize> BoundingBox<D> {
+    /// The corner with the minimal coordinates.
+    pub fn lower_left(&self) -> Point<D> {
         self.0
     }
 
-    pub fn upper_right(&self) -> Point {
+    /// The corner with the maximal coordinates.
+    pub fn upper_right(&self) -> Point<D> {
         self.1
     }
 
-    pub fn width(&self) -> f32 {
-        self.upper_right().x() - self.lower_left().x()
+    /// The point in the middle of the box.
+    pub fn center(&self) -> Point<D> {
+        Point(std::array::from_fn(|d| (self.lower_left().get(d) + self.upper_right().get(d)) / 2.))
+    }
+
+    /// The length of the box along the given axis.
+    pub fn extent(&self, axis: usize) -> Float {
+        self.upper_right().get(axis) - self.lower_left().get(axis)
+    }
+
+    pub fn width(&self) -> Float {
+        self.extent(0)
     }
 
-    pub fn height(&self) -> f32 {
-        self.upper_right().y() - self.lower_left().y()
+    pub fn height(&self) -> Float {
+        self.extent(1)
     }
+}
 
-    pub fn area(&self) -> f32 {
+impl BoundingBox<2> {
+    pub fn area(&self) -> Float {
         self.width() * self.height()
     }
 }
+
+impl BoundingBox<3> {
+    pub fn depth(&self) -> Float {
+        self.extent(2)
+    }
+}
+
+impl<const D: usize> fmt::Display for Point<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coords: Vec<String> = self.0.iter().map(Float::to_string).collect();
+        write!(f, "({})", coords.join(", "))
+    }
+}
+
+impl<const D: usize> fmt::Display for BoundingBox<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} .. {}]", self.lower_left(), self.upper_right())
+    }
+}
+
+/// Points are (de)serialized as a tuple of their coordinates, serde only supports arrays of a
+/// fixed size.
+#[cfg(feature = "serde")]
+impl<const D: usize> serde::Serialize for Point<D> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+
+        let mut tuple = serializer.serialize_tuple(D)?;
+        for c in &self.0 {
+            tuple.serialize_element(c)?;
+        }
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const D: usize> serde::Deserialize<'de> for Point<D> {
+    fn deserialize<De: serde::Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let coords = Vec::<Float>::deserialize(deserializer)?;
+        let len = coords.len();
+        coords
+            .try_into()
+            .map(Point)
+            .map_err(|_| serde::de::Error::invalid_length(len, &format!("{} coordinates", D).as_str()))
+    }
+}
@@ -0,0 +1,229 @@
+//! Passes that adjust finished layouts.
+use ndarray::{Array2, ArrayView2};
+
+use crate::engines::DIRECTIONS;
+use crate::metrics::{self, cross};
+use crate::{Float, Graph};
+
+use super::scatter::ScatterLayout;
+
+/// The maximum number of sweeps over all pairs of nodes in [`remove_overlaps`].
+const MAX_SWEEPS: usize = 1000;
+
+/// Push overlapping nodes apart, until no two node circles with the given radii intersect.
+///
+/// Each sweep moves both nodes of every overlapping pair by half their overlap along the line
+/// through their centers, nodes at the very same position are separated along a direction derived
+/// from their indices. Nodes only move as far as needed, such that the relative positions of the
+/// layout are mostly preserved. Radii are indexed by node, nodes without radius use the last one.
+///
+/// Fails if nodes still overlap after 1000 sweeps, which only dense clusters of many nodes need.
+///
+/// ```
+/// use ndarray::arr2;
+/// use rs_plode::layout::postprocess::remove_overlaps;
+/// use rs_plode::layout::scatter::ScatterLayout;
+///
+/// let layout = ScatterLayout::new(vec![(0, 1)], arr2(&[[0., 0.], [10., 0.]])).unwrap();
+/// let layout = remove_overlaps(layout, &[30.]).unwrap();
+/// assert!(layout.coord(1).x() - layout.coord(0).x() >= 59.9);
+/// ```
+pub fn remove_overlaps<G: Graph>(layout: ScatterLayout<G>, radii: &[Float]) -> Result<ScatterLayout<G>, String> {
+    separate(layout, radii, MAX_SWEEPS)
+}
+
+/// [`remove_overlaps`] with at most the given number of sweeps.
+fn separate<G: Graph>(layout: ScatterLayout<G>, radii: &[Float], sweeps: usize) -> Result<ScatterLayout<G>, String> {
+    if radii.is_empty() {
+        return Err("Need at least one radius".to_string());
+    }
+    if let Some(r) = radii.iter().find(|r| !r.is_finite() || **r < 0.) {
+        return Err(format!("Invalid radius {}", r));
+    }
+    let nodes = layout.graph.nodes();
+    let radius = |n: usize| radii.get(n).or(radii.last()).copied().unwrap();
+    let mut pos = layout.view().to_owned();
+
+    let mut moved = true;
+    for _ in 0..sweeps {
+        moved = false;
+        for i in 0..nodes {
+            for j in i + 1..nodes {
+                let (dx, dy) = (pos[[j, 0]] - pos[[i, 0]], pos[[j, 1]] - pos[[i, 1]]);
+                let distance = (dx * dx + dy * dy).sqrt();
+                // a tiny tolerance avoids endless sweeps due to rounding
+                let overlap = radius(i) + radius(j) - distance;
+                if overlap <= 1e-3 * (radius(i) + radius(j)) {
+                    continue;
+                }
+                let (ux, uy) = if distance > 0. {
+                    (dx / distance, dy / distance)
+                } else {
+                    let [x, y] = DIRECTIONS[(i * nodes + j) % DIRECTIONS.len()];
+                    (x, y)
+                };
+                let step = overlap / 2.;
+                pos[[i, 0]] -= ux * step;
+                pos[[i, 1]] -= uy * step;
+                pos[[j, 0]] += ux * step;
+                pos[[j, 1]] += uy * step;
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+    if moved {
+        return Err(format!("Nodes still overlap after {} sweeps", sweeps));
+    }
+    ScatterLayout::new(layout.graph, pos)
+}
+
+/// The number of nearest nodes each node tries to swap positions with in [`reduce_crossings`].
+const SWAP_CANDIDATES: usize = 4;
+
+/// Reduce the edge crossings of a layout by local search, e.g. when the crossing count matters more
+/// than the energy the engine optimized.
+///
+/// Each round tries, for every node, to swap positions with its nearest nodes and to move by a
+/// quarter of the mean edge length into eight directions, keeping every change that strictly
+/// reduces the number of crossings. Stops after the given number of rounds or once a round
+/// brings no improvement, hence the result never has more crossings than the input. Each
+/// evaluation costs O(degree * edges), such that the pass suits graphs of up to a few hundred edges.
+///
+/// ```
+/// use ndarray::arr2;
+/// use rs_plode::layout::postprocess::reduce_crossings;
+/// use rs_plode::layout::scatter::ScatterLayout;
+/// use rs_plode::metrics::crossings;
+///
+/// // a square whose corners 1 and 2 are swapped, such that its sides cross
+/// let layout = ScatterLayout::new(vec![(0, 1), (1, 2), (2, 3), (3, 0)], arr2(&[[0., 0.], [1., 1.], [1., 0.], [0., 1.]])).unwrap();
+/// let layout = reduce_crossings(layout, 10);
+/// assert_eq!(crossings(layout.graph(), layout.view()), 0);
+/// ```
+pub fn reduce_crossings<G: Graph>(layout: ScatterLayout<G>, rounds: usize) -> ScatterLayout<G> {
+    let nodes = layout.graph.nodes();
+    let edges: Vec<(usize, usize)> = layout.graph.edges().collect();
+    let mut incident = vec![Vec::new(); nodes];
+    for (i, &(u, v)) in edges.iter().enumerate() {
+        incident[u].push(i);
+        if v != u {
+            incident[v].push(i);
+        }
+    }
+    // the crossings of all edges incident to the given nodes, counting each pair once
+    let local = |pos: ArrayView2<Float>, moved: &[usize]| -> usize {
+        let mut mine: Vec<usize> = moved.iter().flat_map(|&n| incident[n].iter().copied()).collect();
+        mine.sort_unstable();
+        mine.dedup();
+        let mut count = 0;
+        for &i in &mine {
+            for (j, &f) in edges.iter().enumerate() {
+                // pairs of two own edges are visited twice, count them only once
+                if (j > i || mine.binary_search(&j).is_err()) && cross(pos, edges[i], f) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    };
+
+    let step = metrics::mean_edge_length(&layout.graph, layout.view()) / 4.;
+    let mut pos: Array2<Float> = layout.view().to_owned();
+    for _ in 0..rounds {
+        let mut improved = false;
+        for n in 0..nodes {
+            // swaps with the nearest nodes
+            let mut nearest: Vec<usize> = (0..nodes).filter(|&m| m != n).collect();
+            let distance = |m: usize| (pos[[m, 0]] - pos[[n, 0]]).powi(2) + (pos[[m, 1]] - pos[[n, 1]]).powi(2);
+            nearest.sort_by(|&a, &b| distance(a).total_cmp(&distance(b)));
+            for &m in nearest.iter().take(SWAP_CANDIDATES) {
+                let before = local(pos.view(), &[n, m]);
+                swap_rows(&mut pos, n, m);
+                if local(pos.view(), &[n, m]) < before {
+                    improved = true;
+                } else {
+                    swap_rows(&mut pos, n, m);
+                }
+            }
+            // small moves
+            for [x, y] in DIRECTIONS {
+                let before = local(pos.view(), &[n]);
+                let original = (pos[[n, 0]], pos[[n, 1]]);
+                pos[[n, 0]] += step * x;
+                pos[[n, 1]] += step * y;
+                if local(pos.view(), &[n]) < before {
+                    improved = true;
+                } else {
+                    (pos[[n, 0]], pos[[n, 1]]) = original;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+    ScatterLayout::new(layout.graph, pos).unwrap()
+}
+
+/// Exchange the positions of two nodes.
+fn swap_rows(positions: &mut Array2<Float>, a: usize, b: usize) {
+    for d in 0..positions.shape()[1] {
+        positions.swap([a, d], [b, d]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::{arr2, Array2};
+
+    use super::{reduce_crossings, remove_overlaps, separate, MAX_SWEEPS};
+    use crate::layout::scatter::ScatterLayout;
+    use crate::metrics::crossings;
+    use crate::Float;
+
+    #[test]
+    fn push_apart() {
+        let positions = arr2(&[[0., 0.], [5., 0.], [5., 0.], [100., 100.], [-40., 3.]]);
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (2, 3), (3, 4)], positions).unwrap();
+        let radii = [10., 20., 5.];
+        let layout = remove_overlaps(layout, &radii).unwrap();
+        let r = |n: usize| radii.get(n).copied().unwrap_or(5.);
+        for i in 0..5 {
+            for j in i + 1..5 {
+                let (a, b) = (layout.coord(i), layout.coord(j));
+                let distance = ((a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2)).sqrt();
+                assert!(distance >= 0.99 * (r(i) + r(j)), "nodes {} and {} overlap", i, j);
+            }
+        }
+        // the far away node is untouched and the left most node stays on the left
+        assert_eq!((layout.coord(3).x(), layout.coord(3).y()), (100., 100.));
+        assert!(layout.coord(4).x() < layout.coord(0).x());
+
+        // a single sweep does not separate the cluster of coincident nodes
+        let cluster = ScatterLayout::new(vec![(0usize, 1usize), (1, 2), (2, 3)], Array2::zeros((4, 2))).unwrap();
+        assert!(separate(cluster.clone(), &[10.], 1).unwrap_err().contains("after 1 sweeps"));
+        assert!(separate(cluster, &[10.], MAX_SWEEPS).is_ok());
+    }
+
+    #[test]
+    fn reject_invalid_radii() {
+        let layout = || ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [1., 0.]])).unwrap();
+        assert!(remove_overlaps(layout(), &[]).is_err());
+        assert!(remove_overlaps(layout(), &[-1.]).is_err());
+    }
+
+    #[test]
+    fn untangle() {
+        let graph = crate::graph::generators::grid(4, 4);
+        // a grid with shuffled rows and columns
+        let order = [2, 0, 3, 1];
+        let positions = ndarray::Array2::from_shape_fn((16, 2), |(n, d)| order[if d == 0 { n % 4 } else { n / 4 }] as Float * 10.);
+        let before = crossings(&graph, positions.view());
+        let layout = reduce_crossings(ScatterLayout::new(&graph, positions).unwrap(), 20);
+        let after = crossings(&graph, layout.view());
+        assert!(before > 0 && after < before / 2, "{} -> {} crossings", before, after);
+    }
+}
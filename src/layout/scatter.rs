@@ -1,21 +1,160 @@
-use ndarray::{s, stack, Array2, Axis, Array3, ArrayView, ArrayView2};
+use std::fmt;
 
+use ndarray::{s, Array2, Axis, Array3, ArrayView, ArrayView2, ArrayViewMutD, ArrayViewD};
+
+#[cfg(feature = "ndarray-stats")]
 use ndarray_stats::QuantileExt;
 
-use crate::{Graph};
+use crate::engines::telemetry::Telemetry;
+use crate::graph::SubGraphView;
+use crate::{metrics, Float, Graph};
 
 use super::{BoundingBox, Point};
 
-/// A layout where nodes can have a real valued position in 2D space.
-#[derive(Clone, Debug)]
-pub struct ScatterLayout<G: Graph> {
-    positions: Array2<f32>,
+#[cfg(feature = "serde")]
+mod serialize;
+
+/// A layout where nodes can have a real valued position in 3D space.
+pub type ScatterLayout3<G> = ScatterLayout<G, 3>;
+
+/// A sequence of 3D scatter layouts.
+pub type ScatterLayoutSequence3<G> = ScatterLayoutSequence<G, 3>;
+
+/// The bounding box of positions whose last axis holds the D coordinates of each node.
+fn bounding_box<const D: usize>(positions: ArrayViewD<Float>) -> Result<BoundingBox<D>, String> {
+    let last = positions.ndim() - 1;
+    if positions.shape()[last] != D {
+        return Err(format!("Expected {} coordinates per node, got {}", D, positions.shape()[last]));
+    }
+    let mut min = [0.; D];
+    let mut max = [0.; D];
+    for d in 0..D {
+        (min[d], max[d]) = min_max(positions.index_axis(Axis(last), d))?;
+    }
+    if min.iter().chain(max.iter()).any(|v| v.is_infinite()) {
+        return Err("Infinite size bounding box.".to_string());
+    }
+    Ok(BoundingBox(Point(min), Point(max)))
+}
+
+/// The smallest and largest of the values, failing for NaN values or no values at all.
+#[cfg(feature = "ndarray-stats")]
+fn min_max(values: ArrayViewD<Float>) -> Result<(Float, Float), String> {
+    let error = |_| "Found NaN in positions".to_string();
+    Ok((*values.min().map_err(error)?, *values.max().map_err(error)?))
+}
+
+/// The smallest and largest of the values, failing for NaN values or no values at all.
+#[cfg(not(feature = "ndarray-stats"))]
+fn min_max(values: ArrayViewD<Float>) -> Result<(Float, Float), String> {
+    if values.is_empty() || values.iter().any(|v| v.is_nan()) {
+        return Err("Found NaN in positions".to_string());
+    }
+    Ok(values.fold((Float::INFINITY, Float::NEG_INFINITY), |(min, max), &v| (min.min(v), max.max(v))))
+}
+
+/// Map positions whose last axis holds the D coordinates of each node from one bounding box onto
+/// another.
+fn transform_into<const D: usize>(mut positions: ArrayViewMutD<Float>, from: &BoundingBox<D>, to: &BoundingBox<D>) {
+    let last = positions.ndim() - 1;
+    for d in 0..D {
+        let (lower, target) = (from.lower_left().get(d), to.lower_left().get(d));
+        positions
+            .index_axis_mut(Axis(last), d)
+            .mapv_inplace(|v| (v - lower) * to.extent(d) / from.extent(d) + target);
+    }
+}
+
+/// Apply the map to every 2D point of positions whose last axis holds the coordinates of each node.
+fn map_points(mut positions: ArrayViewMutD<Float>, f: impl Fn(Point) -> Point) {
+    let last = positions.ndim() - 1;
+    for mut lane in positions.lanes_mut(Axis(last)) {
+        let p = f(Point([lane[0], lane[1]]));
+        (lane[0], lane[1]) = (p.x(), p.y());
+    }
+}
+
+/// The rotation by theta radians counterclockwise around the center.
+fn rotation(center: Point, theta: Float) -> impl Fn(Point) -> Point {
+    let (sin, cos) = theta.sin_cos();
+    move |p| {
+        let (x, y) = (p.x() - center.x(), p.y() - center.y());
+        Point([center.x() + cos * x - sin * y, center.y() + sin * x + cos * y])
+    }
+}
+
+/// The sign that makes the third moment of the values positive, or, if the values are symmetric, the
+/// first value clearly off zero.
+fn canonical_sign(values: impl Iterator<Item = Float> + Clone) -> Float {
+    let largest = values.clone().fold(0., |m: Float, v| m.max(v.abs()));
+    let (skew, spread) = values.clone().fold((0., 0.), |(s, a): (Float, Float), v| (s + v.powi(3), a + v.abs().powi(3)));
+    if skew.abs() > 1e-4 * spread {
+        return skew.signum();
+    }
+    values.into_iter().find(|v| v.abs() > 1e-4 * largest).map_or(1., Float::signum)
+}
+
+/// The similarity transform, i.e. rotation, uniform scaling and translation, that maps the first V x 2
+/// shaped positions as closely as possible onto the second ones in the least squares sense
+/// (Procrustes analysis). Reflections are not considered.
+fn procrustes(from: ArrayView2<Float>, to: ArrayView2<Float>) -> impl Fn(Point) -> Point {
+    let center = |positions: ArrayView2<Float>| {
+        positions.mean_axis(Axis(0)).map_or(Point([0., 0.]), |c| Point([c[0], c[1]]))
+    };
+    let (a, b) = (center(from), center(to));
+    let (mut dot, mut cross, mut norm) = (0., 0., 0.);
+    for (p, q) in from.rows().into_iter().zip(to.rows()) {
+        let (px, py, qx, qy) = (p[0] - a.x(), p[1] - a.y(), q[0] - b.x(), q[1] - b.y());
+        dot += px * qx + py * qy;
+        cross += px * qy - py * qx;
+        norm += px * px + py * py;
+    }
+    // the optimal rotation and scale of a 2D Procrustes problem, as a complex factor
+    let (cos, sin) = match norm > 0. {
+        true => (dot / norm, cross / norm),
+        false => (1., 0.),
+    };
+    move |p| {
+        let (x, y) = (p.x() - a.x(), p.y() - a.y());
+        Point([b.x() + cos * x - sin * y, b.y() + sin * x + cos * y])
+    }
+}
+
+/// The map of one bounding box into the center of another, scaling both axes by the same factor if
+/// the aspect ratio shall be preserved. Axes without extent are centered without scaling.
+fn fit(from: &BoundingBox, to: &BoundingBox, preserve_aspect: bool) -> impl Fn(Point) -> Point {
+    let factor = |d: usize| (from.extent(d) > 0.).then(|| to.extent(d) / from.extent(d));
+    let (mut sx, mut sy) = (factor(0), factor(1));
+    if preserve_aspect {
+        let uniform = [sx, sy].into_iter().flatten().reduce(Float::min);
+        (sx, sy) = (uniform, uniform);
+    }
+    let (sx, sy) = (sx.unwrap_or(1.), sy.unwrap_or(1.));
+    let (a, b) = (from.center(), to.center());
+    move |p| Point([b.x() + (p.x() - a.x()) * sx, b.y() + (p.y() - a.y()) * sy])
+}
+
+/// A layout where nodes can have a real valued position in D dimensional space, by default in 2D.
+///
+/// Engines like [`FruchtermanReingold`](crate::engines::fruchterman_reingold::FruchtermanReingold)
+/// compute the positions independently of the dimension, only rendering requires 2D layouts.
+#[derive(Clone)]
+pub struct ScatterLayout<G: Graph, const D: usize = 2> {
+    positions: Array2<Float>,
     pub(crate) graph: G,
-    bbox: BoundingBox,
+    bbox: BoundingBox<D>,
 }
 
 impl<G: Graph> ScatterLayout<G> {
-    pub fn new(graph: G, positions: Array2<f32>) -> Result<Self, String> {
+    /// Create a 2D layout from V x 2 shaped positions.
+    pub fn new(graph: G, positions: Array2<Float>) -> Result<Self, String> {
+        Self::with_positions(graph, positions)
+    }
+}
+
+impl<G: Graph, const D: usize> ScatterLayout<G, D> {
+    /// Create a layout from V x D shaped positions, e.g. `ScatterLayout3::with_positions` for 3D.
+    pub fn with_positions(graph: G, positions: Array2<Float>) -> Result<Self, String> {
         if positions.shape()[0] != graph.nodes() {
             return Err(format!(
                 "Node count {} does not match position shape {}",
@@ -24,40 +163,7 @@ impl<G: Graph> ScatterLayout<G> {
             )
                 .to_string());
         }
-        let bbox = BoundingBox(
-            Point(
-                *positions
-                    .slice(s![.., 0])
-                    .min()
-                    .map_err(|_| "Found NaN in positions".to_string())?,
-                *positions
-                    .slice(s![.., 1])
-                    .min()
-                    .map_err(|_| "Found NaN in positions".to_string())?,
-            ),
-            Point(
-                *positions
-                    .slice(s![.., 0])
-                    .max()
-                    .map_err(|_| "Found NaN in positions".to_string())?,
-                *positions
-                    .slice(s![.., 1])
-                    .max()
-                    .map_err(|_| "Found NaN in positions".to_string())?,
-            ),
-        );
-
-        if [
-            bbox.lower_left().x(),
-            bbox.lower_left().y(),
-            bbox.upper_right().x(),
-            bbox.upper_right().y(),
-        ]
-            .into_iter()
-            .any(f32::is_infinite)
-        {
-            return Err("Infinite size bounding box.".to_string());
-        }
+        let bbox = bounding_box(positions.view().into_dyn())?;
 
         Ok(Self {
             positions,
@@ -68,42 +174,541 @@ impl<G: Graph> ScatterLayout<G> {
 
     /// The bounding box that encompasses all nodes.
     /// Returns lower left and upper right corner.
-    pub fn bbox(&self) -> &BoundingBox {
-        return &self.bbox;
+    pub fn bbox(&self) -> &BoundingBox<D> {
+        &self.bbox
+    }
+
+    /// The graph (or graph handle) the layout was computed for.
+    pub fn graph(&self) -> &G {
+        &self.graph
     }
 
     /// Get the location of a node.
-    pub fn coord(&self, node: usize) -> Point {
-        return Point(self.positions[[node, 0]], self.positions[[node, 1]]);
+    pub fn coord(&self, node: usize) -> Point<D> {
+        point(self.positions.view(), node)
+    }
+
+    /// The V x D shaped coordinates.
+    pub fn view(&self) -> ArrayView2<'_, Float> {
+        self.positions.view()
+    }
+
+    /// The V x D shaped coordinates, same as [`view`](Self::view).
+    pub fn positions(&self) -> ArrayView2<'_, Float> {
+        self.positions.view()
+    }
+
+    /// Iterate over the nodes and their locations, in the order of the node indices.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    /// use rs_plode::layout::Point;
+    ///
+    /// let layout = ScatterLayout::new(vec![(0, 1)], arr2(&[[0., 0.], [4., 2.]])).unwrap();
+    /// let right: Vec<usize> = layout.iter().filter(|(_, p)| p.x() > 1.).map(|(n, _)| n).collect();
+    /// assert_eq!(right, vec![1]);
+    /// assert_eq!(layout.to_vec(), vec![Point([0., 0.]), Point([4., 2.])]);
+    /// ```
+    pub fn iter(&self) -> Coords<'_, D> {
+        Coords {
+            positions: self.positions.view(),
+            node: 0,
+        }
+    }
+
+    /// The locations of all nodes, indexed by node.
+    pub fn to_vec(&self) -> Vec<Point<D>> {
+        self.iter().map(|(_, p)| p).collect()
+    }
+
+    /// The layout of the subgraph induced by the given nodes, keeping their positions, e.g. to
+    /// render the neighbourhood of some nodes of a large layout. Fails for unknown or no nodes.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    /// use rs_plode::Graph;
+    ///
+    /// let layout = ScatterLayout::new(vec![(0, 1), (1, 2)], arr2(&[[0., 0.], [1., 0.], [2., 2.]])).unwrap();
+    /// let sub = layout.subgraph(&[2, 1]).unwrap();
+    /// assert_eq!(sub.graph().edges().collect::<Vec<_>>(), vec![(1, 0)]);
+    /// assert_eq!(sub.coord(0), layout.coord(2));
+    /// ```
+    pub fn subgraph(&self, nodes: &[usize]) -> Result<ScatterLayout<SubGraphView, D>, String> {
+        let graph = SubGraphView::induced(&self.graph, nodes)?;
+        let positions = self.positions.select(Axis(0), graph.original_nodes());
+        ScatterLayout::with_positions(graph, positions)
     }
 
     /// Translate and scale to match given target bounding box
-    pub fn transform(mut self, bbox: &BoundingBox) -> Self {
-        self.positions = stack![
-            Axis(1),
-            &(&self.positions.slice(s![.., 0]) - self.bbox().lower_left().x()) * bbox.width()
-                / self.bbox().width()
-                + bbox.lower_left().x(),
-            &(&self.positions.slice(s![.., 1]) - self.bbox().lower_left().y()) * bbox.height()
-                / self.bbox().height()
-                + bbox.lower_left().y()
-        ];
+    pub fn transform(mut self, bbox: &BoundingBox<D>) -> Self {
+        transform_into(self.positions.view_mut().into_dyn(), &self.bbox, bbox);
         self
     }
 }
 
+/// The location of the given row of V x D shaped positions.
+fn point<const D: usize>(positions: ArrayView2<Float>, node: usize) -> Point<D> {
+    Point(std::array::from_fn(|d| positions[[node, d]]))
+}
+
+/// Iterator over the nodes of a layout and their locations, see [`ScatterLayout::iter`].
+#[derive(Clone, Debug)]
+pub struct Coords<'a, const D: usize = 2> {
+    positions: ArrayView2<'a, Float>,
+    node: usize,
+}
+
+impl<const D: usize> Iterator for Coords<'_, D> {
+    type Item = (usize, Point<D>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node;
+        (node < self.positions.nrows()).then(|| {
+            self.node += 1;
+            (node, point(self.positions.view(), node))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.positions.nrows() - self.node;
+        (rest, Some(rest))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for Coords<'_, D> {}
+
+/// Owning iterator over the nodes of a layout and their locations.
+#[derive(Clone, Debug)]
+pub struct IntoCoords<const D: usize = 2> {
+    positions: Array2<Float>,
+    node: usize,
+}
+
+impl<const D: usize> Iterator for IntoCoords<D> {
+    type Item = (usize, Point<D>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node;
+        (node < self.positions.nrows()).then(|| {
+            self.node += 1;
+            (node, point(self.positions.view(), node))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.positions.nrows() - self.node;
+        (rest, Some(rest))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for IntoCoords<D> {}
+
+impl<'a, G: Graph, const D: usize> IntoIterator for &'a ScatterLayout<G, D> {
+    type Item = (usize, Point<D>);
+    type IntoIter = Coords<'a, D>;
+
+    fn into_iter(self) -> Coords<'a, D> {
+        self.iter()
+    }
+}
+
+impl<G: Graph, const D: usize> IntoIterator for ScatterLayout<G, D> {
+    type Item = (usize, Point<D>);
+    type IntoIter = IntoCoords<D>;
+
+    fn into_iter(self) -> IntoCoords<D> {
+        IntoCoords {
+            positions: self.positions,
+            node: 0,
+        }
+    }
+}
+
+impl<G: Graph> ScatterLayout<G> {
+    /// Drop the graph and keep only the coordinates and the bounding box.
+    pub fn into_positions(self) -> PositionsOnly {
+        PositionsOnly {
+            positions: self.positions,
+            bbox: self.bbox,
+        }
+    }
+
+    /// Apply the map to every node and update the bounding box.
+    fn map(mut self, f: impl Fn(Point) -> Point) -> Self {
+        map_points(self.positions.view_mut().into_dyn(), f);
+        self.bbox = bounding_box(self.positions.view().into_dyn()).unwrap();
+        self
+    }
+
+    /// Move all nodes by the given offset. Non-finite offsets leave the layout unchanged.
+    pub fn translate(self, dx: Float, dy: Float) -> Self {
+        if !(dx.is_finite() && dy.is_finite()) {
+            return self;
+        }
+        self.map(|p| Point([p.x() + dx, p.y() + dy]))
+    }
+
+    /// Scale the layout by the given factor around the center of its bounding box. Negative factors
+    /// mirror the layout through the center, non-finite ones leave it unchanged.
+    pub fn scale(self, factor: Float) -> Self {
+        if !factor.is_finite() {
+            return self;
+        }
+        let c = self.bbox.center();
+        self.map(|p| Point([c.x() + (p.x() - c.x()) * factor, c.y() + (p.y() - c.y()) * factor]))
+    }
+
+    /// Rotate the layout by theta radians counterclockwise around the center of its bounding box,
+    /// e.g. to normalize the orientation of layouts from different seeds. Non-finite angles leave
+    /// the layout unchanged.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    ///
+    /// let layout = ScatterLayout::new(vec![(0, 1)], arr2(&[[0., 0.], [4., 0.]])).unwrap();
+    /// let upright = layout.rotate(rs_plode::consts::FRAC_PI_2).translate(0., 2.);
+    /// assert!((upright.coord(1).y() - 4.).abs() < 1e-5);
+    /// assert!((upright.coord(1).x() - 2.).abs() < 1e-5);
+    /// ```
+    pub fn rotate(self, theta: Float) -> Self {
+        if !theta.is_finite() {
+            return self;
+        }
+        let rotate = rotation(self.bbox.center(), theta);
+        self.map(rotate)
+    }
+
+    /// Mirror the layout horizontally, i.e. negate the x coordinates, within its bounding box.
+    pub fn flip_x(self) -> Self {
+        let (lower, upper) = (self.bbox.lower_left().x(), self.bbox.upper_right().x());
+        self.map(|p| Point([lower + upper - p.x(), p.y()]))
+    }
+
+    /// Mirror the layout vertically, i.e. negate the y coordinates, within its bounding box.
+    pub fn flip_y(self) -> Self {
+        let (lower, upper) = (self.bbox.lower_left().y(), self.bbox.upper_right().y());
+        self.map(|p| Point([p.x(), lower + upper - p.y()]))
+    }
+
+    /// Translate and scale the layout into the given bounding box. Unlike
+    /// [`transform`](Self::transform), the aspect ratio can be preserved, in which case the layout
+    /// is centered along the axis it does not fill. Layouts without extent along an axis are
+    /// centered along it.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    /// use rs_plode::layout::{BoundingBox, Point};
+    ///
+    /// let layout = ScatterLayout::new(vec![(0, 1)], arr2(&[[0., 0.], [2., 1.]])).unwrap();
+    /// let square = BoundingBox(Point([0., 0.]), Point([100., 100.]));
+    /// let fitted = layout.fit_into(&square, true);
+    /// assert_eq!(fitted.view(), arr2(&[[0., 25.], [100., 75.]]));
+    /// ```
+    pub fn fit_into(self, bbox: &BoundingBox, preserve_aspect: bool) -> Self {
+        let fit = fit(&self.bbox, bbox, preserve_aspect);
+        self.map(fit)
+    }
+
+    /// Rotate, scale and translate the layout to match the other layout of the same nodes as closely
+    /// as possible (Procrustes analysis), e.g. to avoid jumps between the layouts of successive
+    /// versions of an evolving graph. Fails if the node counts differ.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    ///
+    /// let graph = vec![(0, 1), (1, 2)];
+    /// let layout = ScatterLayout::new(graph.clone(), arr2(&[[0., 0.], [1., 0.], [1., 2.]])).unwrap();
+    /// let reference = layout.clone().rotate(1.).scale(3.).translate(5., 5.);
+    /// let aligned = layout.align_to(&reference).unwrap();
+    /// assert!((&aligned.view() - &reference.view()).iter().all(|d| d.abs() < 1e-4));
+    /// ```
+    pub fn align_to<H: Graph>(self, other: &ScatterLayout<H>) -> Result<Self, String> {
+        if other.positions.nrows() != self.positions.nrows() {
+            return Err(format!(
+                "Cannot align a layout of {} nodes to one of {} nodes",
+                self.positions.nrows(),
+                other.positions.nrows()
+            ));
+        }
+        let align = procrustes(self.positions.view(), other.positions.view());
+        Ok(self.map(align))
+    }
+
+    /// Rotate and mirror the layout into a canonical orientation, such that layouts of the same graph
+    /// from different seeds can be compared visually.
+    ///
+    /// The principal axis of the node positions (PCA) is aligned with the x axis, around the
+    /// centroid of the nodes. The layout is then mirrored along each axis such that the nodes are
+    /// skewed towards positive coordinates, or, for symmetric layouts, such that the node of lowest
+    /// index off the axis lies on its positive side. Point symmetric layouts of equal spread in all
+    /// directions keep their rotation.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    /// use rs_plode::layout::{BoundingBox, Point};
+    ///
+    /// let graph = vec![(0, 1), (1, 2)];
+    /// let layout = ScatterLayout::new(graph.clone(), arr2(&[[0., 0.], [0., 1.], [0., 3.]])).unwrap();
+    /// let mirrored = ScatterLayout::new(graph, arr2(&[[5., 3.], [5., 2.], [5., 0.]])).unwrap();
+    /// let (a, b) = (layout.normalize_orientation(), mirrored.normalize_orientation());
+    /// assert!(a.bbox().width() > a.bbox().height());
+    ///
+    /// let unit = BoundingBox(Point([0., 0.]), Point([1., 1.]));
+    /// let (a, b) = (a.fit_into(&unit, true), b.fit_into(&unit, true));
+    /// assert!((&a.view() - &b.view()).iter().all(|d| d.abs() < 1e-4));
+    /// ```
+    pub fn normalize_orientation(self) -> Self {
+        let Some(centroid) = self.positions.mean_axis(Axis(0)) else {
+            return self;
+        };
+        let centered = &self.positions - &centroid;
+        let (xx, yy, xy) = centered.rows().into_iter().fold((0., 0., 0.), |(xx, yy, xy), p| {
+            (xx + p[0] * p[0], yy + p[1] * p[1], xy + p[0] * p[1])
+        });
+        let theta = 0.5 * Float::atan2(2. * xy, xx - yy);
+        let (sin, cos) = theta.sin_cos();
+        let aligned = |p: Point| Point([cos * p.x() + sin * p.y(), cos * p.y() - sin * p.x()]);
+        let rotated: Vec<Point> = centered.rows().into_iter().map(|p| aligned(Point([p[0], p[1]]))).collect();
+        let sx = canonical_sign(rotated.iter().map(Point::x));
+        let sy = canonical_sign(rotated.iter().map(Point::y));
+        let center = Point([centroid[0], centroid[1]]);
+        self.map(|p| {
+            let q = aligned(Point([p.x() - center.x(), p.y() - center.y()]));
+            Point([center.x() + sx * q.x(), center.y() + sy * q.y()])
+        })
+    }
+
+    /// The polar coordinates (r, theta) of each node around the given center, e.g. the center of
+    /// the bounding box. The angle is in radians within [-pi, pi], measured from the x axis.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    /// use rs_plode::layout::Point;
+    ///
+    /// let layout = ScatterLayout::new(vec![(0, 1)], arr2(&[[2., 0.], [0., 3.]])).unwrap();
+    /// let polar = layout.to_polar(Point([0., 0.]));
+    /// assert_eq!(polar[0], (2., 0.));
+    /// assert_eq!(polar[1], (3., rs_plode::consts::FRAC_PI_2));
+    /// ```
+    pub fn to_polar(&self, center: Point) -> Vec<(Float, Float)> {
+        (0..self.graph.nodes())
+            .map(|n| {
+                let (dx, dy) = (self.coord(n).x() - center.x(), self.coord(n).y() - center.y());
+                ((dx * dx + dy * dy).sqrt(), dy.atan2(dx))
+            })
+            .collect()
+    }
+
+    /// Move every node onto the nearest point of a square grid with the given cell size, e.g. for
+    /// technical diagrams with [orthogonal edges](crate::render::svg::RenderOptions::orthogonal_edges).
+    ///
+    /// Nodes are snapped by index. A node whose grid point is taken already moves to the nearest free
+    /// point of the closest ring of grid points around it instead, such that no two nodes overlap.
+    /// Cell sizes that are not positive leave the layout unchanged.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    ///
+    /// let layout = ScatterLayout::new(vec![(0, 1)], arr2(&[[12., 29.], [9., 33.]])).unwrap();
+    /// // both nodes are closest to (10, 30), the second one moves to the closest free point
+    /// let snapped = layout.snap_to_grid(10.);
+    /// assert_eq!(snapped.view(), arr2(&[[10., 30.], [10., 40.]]));
+    /// ```
+    pub fn snap_to_grid(mut self, cell_size: Float) -> Self {
+        if cell_size <= 0. {
+            return self;
+        }
+        let mut taken = std::collections::HashSet::new();
+        for n in 0..self.graph.nodes() {
+            let (x, y) = (self.positions[[n, 0]] / cell_size, self.positions[[n, 1]] / cell_size);
+            let nearest = (x.round() as i64, y.round() as i64);
+            let distance = |&(i, j): &(i64, i64)| (i as Float - x).powi(2) + (j as Float - y).powi(2);
+            let cell = (0..)
+                .find_map(|ring: i64| {
+                    (-ring..=ring)
+                        .flat_map(|i| (-ring..=ring).map(move |j| (i, j)))
+                        .filter(|&(i, j)| i64::max(i.abs(), j.abs()) == ring)
+                        .map(|(i, j)| (nearest.0 + i, nearest.1 + j))
+                        .filter(|cell| !taken.contains(cell))
+                        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+                })
+                .unwrap();
+            taken.insert(cell);
+            self.positions[[n, 0]] = cell.0 as Float * cell_size;
+            self.positions[[n, 1]] = cell.1 as Float * cell_size;
+        }
+        self.bbox = bounding_box(self.positions.view().into_dyn()).unwrap();
+        self
+    }
+}
+
+impl<G: Graph> ScatterLayout<G, 3> {
+    /// Drop the z coordinate, i.e. look at the layout from above, e.g. to render it with the 2D
+    /// backends.
+    pub fn project(&self) -> ScatterLayout<&G> {
+        ScatterLayout::new(&self.graph, self.positions.slice(s![.., ..2]).to_owned()).unwrap()
+    }
+}
+
+/// The node coordinates of a layout without the graph, see [`ScatterLayout::into_positions`].
+#[derive(Clone, Debug)]
+pub struct PositionsOnly {
+    positions: Array2<Float>,
+    bbox: BoundingBox,
+}
+
+impl PositionsOnly {
+    /// The number of nodes.
+    pub fn nodes(&self) -> usize {
+        self.positions.shape()[0]
+    }
+
+    /// The bounding box that encompasses all nodes.
+    pub fn bbox(&self) -> &BoundingBox {
+        &self.bbox
+    }
+
+    /// Get the location of a node.
+    pub fn coord(&self, node: usize) -> Point {
+        point(self.positions.view(), node)
+    }
+
+    /// The V x 2 shaped coordinates.
+    pub fn view(&self) -> ArrayView2<'_, Float> {
+        self.positions.view()
+    }
+
+    /// Take the V x 2 shaped coordinates.
+    pub fn into_array(self) -> Array2<Float> {
+        self.positions
+    }
+
+    /// Reattach a graph, e.g. to render the positions again.
+    pub fn with_graph<G: Graph>(self, graph: G) -> Result<ScatterLayout<G>, String> {
+        ScatterLayout::new(graph, self.positions)
+    }
+}
+
+/// Collect node locations, indexed by node, e.g. to attach a graph with
+/// [`with_graph`](PositionsOnly::with_graph) afterwards.
+///
+/// Panics if a coordinate is NaN or infinite.
+///
+/// ```
+/// use rs_plode::layout::scatter::PositionsOnly;
+/// use rs_plode::layout::Point;
+/// use rs_plode::Float;
+///
+/// let circle: PositionsOnly = (0..4).map(|n| Point([(n as Float).cos(), (n as Float).sin()])).collect();
+/// let layout = circle.with_graph(vec![(0, 1), (2, 3)]).unwrap();
+/// assert_eq!(layout.iter().len(), 4);
+/// ```
+impl FromIterator<Point> for PositionsOnly {
+    fn from_iter<I: IntoIterator<Item = Point>>(iter: I) -> Self {
+        let flat: Vec<Float> = iter.into_iter().flat_map(|p| p.0).collect();
+        let positions = Array2::from_shape_vec((flat.len() / 2, 2), flat).unwrap();
+        let bbox = match positions.is_empty() {
+            true => BoundingBox(Point([0.; 2]), Point([0.; 2])),
+            false => bounding_box(positions.view().into_dyn()).unwrap(),
+        };
+        Self { positions, bbox }
+    }
+}
+
+impl<G: Graph, const D: usize> fmt::Display for ScatterLayout<G, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let extent: Vec<String> = (0..D).map(|d| self.bbox().extent(d).to_string()).collect();
+        write!(
+            f,
+            "ScatterLayout with {} nodes in {} (extent {})",
+            self.graph.nodes(),
+            self.bbox(),
+            extent.join(" x ")
+        )
+    }
+}
+
+impl<G: Graph, const D: usize> fmt::Debug for ScatterLayout<G, D> {
+    /// Prints the summary, followed by a small ascii plot of the x and y coordinates for layouts
+    /// with up to 100 nodes.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self)?;
+        if self.graph.nodes() <= 100 {
+            write!(f, "{}", ascii_plot(self, 40, 20))?;
+        }
+        Ok(())
+    }
+}
+
+/// Plot node positions onto a character grid of given size. Nodes are drawn as `o`, cells holding
+/// multiple nodes as `#`. As in the svg output, the y axis points downwards.
+fn ascii_plot<G: Graph, const D: usize>(layout: &ScatterLayout<G, D>, columns: usize, rows: usize) -> String {
+    let bbox = layout.bbox();
+    let mut grid = vec![vec![' '; columns]; rows];
+    let cell = |value: Float, min: Float, extent: Float, cells: usize| -> usize {
+        if extent > 0. {
+            usize::min(((value - min) / extent * cells as Float) as usize, cells - 1)
+        } else {
+            cells / 2
+        }
+    };
+    for n in 0..layout.graph.nodes() {
+        let p = layout.coord(n);
+        let c = cell(p.x(), bbox.lower_left().x(), bbox.width(), columns);
+        let r = cell(p.y(), bbox.lower_left().y(), bbox.height(), rows);
+        grid[r][c] = if grid[r][c] == ' ' { 'o' } else { '#' };
+    }
+    let border = format!("+{}+\n", "-".repeat(columns));
+    let mut out = border.clone();
+    for row in grid {
+        out.push('|');
+        out.extend(row);
+        out.push_str("|\n");
+    }
+    out.push_str(&border);
+    out
+}
 
 /// A sequence of scatter layouts that represent the progress during layouting.
-pub struct ScatterLayoutSequence<G: Graph> {
-    positions: Array3<f32>,
+pub struct ScatterLayoutSequence<G: Graph, const D: usize = 2> {
+    positions: Array3<Float>,
     pub(crate) graph: G,
-    bbox: BoundingBox,
+    bbox: BoundingBox<D>,
+    telemetry: Option<Telemetry>,
 }
 
 
+impl<G: Graph, const D: usize> fmt::Display for ScatterLayoutSequence<G, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ScatterLayoutSequence with {} frames of {} nodes in {}",
+            self.frames(),
+            self.graph.nodes(),
+            self.bbox()
+        )
+    }
+}
+
 impl<G: Graph> ScatterLayoutSequence<G> {
-    pub fn new(graph: G, positions: Vec<Array2<f32>>) -> Result<Self, String> {
-        if positions.len() == 0 {
+    /// Create a 2D sequence from V x 2 shaped positions per frame.
+    pub fn new(graph: G, positions: Vec<Array2<Float>>) -> Result<Self, String> {
+        Self::with_positions(graph, positions)
+    }
+}
+
+impl<G: Graph, const D: usize> ScatterLayoutSequence<G, D> {
+    /// Create a sequence from V x D shaped positions per frame.
+    pub fn with_positions(graph: G, positions: Vec<Array2<Float>>) -> Result<Self, String> {
+        if positions.is_empty() {
             return Err("Need at least one step".to_string());
         }
 
@@ -121,91 +726,226 @@ impl<G: Graph> ScatterLayoutSequence<G> {
                 .collect::<Vec<_>>()
                 .as_slice())
             .map_err(|_| "Shape mismatch between individual frames.".to_string())?;
-
-        let bbox = BoundingBox(
-            Point(
-                *positions
-                    .slice(s![..,.., 0])
-                    .min()
-                    .map_err(|_| "Found NaN in positions".to_string())?,
-                *positions
-                    .slice(s![..,.., 1])
-                    .min()
-                    .map_err(|_| "Found NaN in positions".to_string())?,
-            ),
-            Point(
-                *positions
-                    .slice(s![..,.., 0])
-                    .max()
-                    .map_err(|_| "Found NaN in positions".to_string())?,
-                *positions
-                    .slice(s![..,.., 1])
-                    .max()
-                    .map_err(|_| "Found NaN in positions".to_string())?,
-            ),
-        );
-
-        if [
-            bbox.lower_left().x(),
-            bbox.lower_left().y(),
-            bbox.upper_right().x(),
-            bbox.upper_right().y(),
-        ]
-            .into_iter()
-            .any(f32::is_infinite)
-        {
-            return Err("Infinite size bounding box.".to_string());
-        }
+        let bbox = bounding_box(positions.view().into_dyn())?;
 
         Ok(Self {
             positions,
             graph,
             bbox,
+            telemetry: None,
         })
     }
 
+    /// Attach the per-iteration state of the engine that computed the sequence.
+    pub fn with_telemetry(mut self, telemetry: Telemetry) -> Self {
+        self.telemetry = Some(telemetry);
+        self
+    }
+
+    /// The per-iteration state of the engine, if recorded. It covers all iterations, even if frames
+    /// were dropped later on, e.g. by [`downsample`](Self::downsample).
+    pub fn telemetry(&self) -> Option<&Telemetry> {
+        self.telemetry.as_ref()
+    }
+
     /// The number of individual layout frames in the sequence.
     pub fn frames(&self) -> usize {
-        return self.positions.shape()[0];
+        self.positions.shape()[0]
     }
 
-    pub fn frame(&self, f: usize) -> ArrayView2<f32> {
-        return self.positions.slice(s![f,..,..]);
+    pub fn frame(&self, f: usize) -> ArrayView2<'_, Float> {
+        self.positions.slice(s![f,..,..])
+    }
+
+    /// Keep only the frames with given indices, in the given order.
+    pub fn select(self, indices: &[usize]) -> Result<Self, String> {
+        if let Some(&f) = indices.iter().find(|&&f| f >= self.frames()) {
+            return Err(format!("Frame {} out of range for {} frames", f, self.frames()));
+        }
+        let frames = indices.iter().map(|&f| self.frame(f).to_owned()).collect();
+        let telemetry = self.telemetry;
+        Ok(Self { telemetry, ..Self::with_positions(self.graph, frames)? })
+    }
+
+    /// Keep every nth frame, starting with the first one. The last frame is always kept.
+    pub fn every_nth(self, n: usize) -> Self {
+        let last = self.frames() - 1;
+        let mut indices: Vec<usize> = (0..=last).step_by(usize::max(n, 1)).collect();
+        if indices.last() != Some(&last) {
+            indices.push(last);
+        }
+        self.select(&indices).unwrap()
+    }
+
+    /// Keep (up to) n evenly spaced frames, always including the first and the last one.
+    pub fn downsample(self, n: usize) -> Self {
+        let last = self.frames() - 1;
+        if n >= self.frames() {
+            return self;
+        }
+        let mut indices: Vec<usize> = match n {
+            0 | 1 => vec![last],
+            _ => (0..n).map(|i| (i * last + (n - 1) / 2) / (n - 1)).collect(),
+        };
+        indices.dedup();
+        self.select(&indices).unwrap()
     }
 
     /// The bounding box that encompasses all nodes.
     /// Returns lower left and upper right corner.
-    pub fn bbox(&self) -> &BoundingBox {
-        return &self.bbox;
+    pub fn bbox(&self) -> &BoundingBox<D> {
+        &self.bbox
+    }
+
+    /// The bounding box that encompasses all nodes of a single frame.
+    pub fn frame_bbox(&self, f: usize) -> BoundingBox<D> {
+        bounding_box(self.frame(f).into_dyn()).unwrap()
+    }
+
+    /// The graph (or graph handle) the sequence was computed for.
+    pub fn graph(&self) -> &G {
+        &self.graph
     }
 
     /// Get the location of a node.
-    pub fn coord(&self, frame: usize, node: usize) -> Point {
-        return Point(self.positions[[frame, node, 0]], self.positions[[frame, node, 1]]);
+    pub fn coord(&self, frame: usize, node: usize) -> Point<D> {
+        Point(std::array::from_fn(|d| self.positions[[frame, node, d]]))
     }
 
     /// Translate and scale to match given target bounding box
-    pub fn transform(mut self, bbox: &BoundingBox) -> Self {
-        self.positions = stack![
-            Axis(2),
-            &(&self.positions.slice(s![..,.., 0]) - self.bbox().lower_left().x()) * bbox.width()
-                / self.bbox().width()
-                + bbox.lower_left().x(),
-            &(&self.positions.slice(s![..,.., 1]) - self.bbox().lower_left().y()) * bbox.height()
-                / self.bbox().height()
-                + bbox.lower_left().y()
-        ];
+    pub fn transform(mut self, bbox: &BoundingBox<D>) -> Self {
+        transform_into(self.positions.view_mut().into_dyn(), &self.bbox, bbox);
+        self
+    }
+
+    /// Move the lower left corner to the origin and scale all frames uniformly, such that the
+    /// longest side of the bounding box has the given extent.
+    pub fn normalize(mut self, extent: Float) -> Self {
+        let longest = (0..D).map(|d| self.bbox.extent(d)).fold(0., Float::max);
+        let scale = if longest > 0. { extent / longest } else { 1. };
+        let lower = self.bbox.lower_left();
+        for d in 0..D {
+            self.positions
+                .index_axis_mut(Axis(2), d)
+                .mapv_inplace(|v| (v - lower.get(d)) * scale);
+        }
+        self.bbox = BoundingBox(Point([0.; D]), Point(std::array::from_fn(|d| self.bbox.extent(d) * scale)));
         self
     }
 }
 
+impl<G: Graph> ScatterLayoutSequence<G> {
+    /// Apply the map to every node of every frame and update the bounding box.
+    fn map(mut self, f: impl Fn(Point) -> Point) -> Self {
+        map_points(self.positions.view_mut().into_dyn(), f);
+        self.bbox = bounding_box(self.positions.view().into_dyn()).unwrap();
+        self
+    }
+
+    /// Move all nodes of all frames by the given offset, see [`ScatterLayout::translate`].
+    pub fn translate(self, dx: Float, dy: Float) -> Self {
+        if !(dx.is_finite() && dy.is_finite()) {
+            return self;
+        }
+        self.map(|p| Point([p.x() + dx, p.y() + dy]))
+    }
+
+    /// Scale all frames around the center of the bounding box of the sequence, see
+    /// [`ScatterLayout::scale`].
+    pub fn scale(self, factor: Float) -> Self {
+        if !factor.is_finite() {
+            return self;
+        }
+        let c = self.bbox.center();
+        self.map(|p| Point([c.x() + (p.x() - c.x()) * factor, c.y() + (p.y() - c.y()) * factor]))
+    }
+
+    /// Rotate all frames around the center of the bounding box of the sequence, see
+    /// [`ScatterLayout::rotate`].
+    pub fn rotate(self, theta: Float) -> Self {
+        if !theta.is_finite() {
+            return self;
+        }
+        let rotate = rotation(self.bbox.center(), theta);
+        self.map(rotate)
+    }
+
+    /// Mirror all frames horizontally within the bounding box of the sequence.
+    pub fn flip_x(self) -> Self {
+        let (lower, upper) = (self.bbox.lower_left().x(), self.bbox.upper_right().x());
+        self.map(|p| Point([lower + upper - p.x(), p.y()]))
+    }
+
+    /// Mirror all frames vertically within the bounding box of the sequence.
+    pub fn flip_y(self) -> Self {
+        let (lower, upper) = (self.bbox.lower_left().y(), self.bbox.upper_right().y());
+        self.map(|p| Point([p.x(), lower + upper - p.y()]))
+    }
+
+    /// Translate and scale all frames alike, such that the bounding box of the sequence fits into
+    /// the given one, see [`ScatterLayout::fit_into`].
+    pub fn fit_into(self, bbox: &BoundingBox, preserve_aspect: bool) -> Self {
+        let fit = fit(&self.bbox, bbox, preserve_aspect);
+        self.map(fit)
+    }
+
+    /// Rotate, scale and translate each frame to match the previous one, see
+    /// [`ScatterLayout::align_to`], such that the animation shows the changes of the layout without
+    /// jumps of the whole graph. The first frame keeps its position.
+    pub fn align_frames(mut self) -> Self {
+        for f in 1..self.frames() {
+            let (previous, mut rest) = self.positions.view_mut().split_at(Axis(0), f);
+            let previous = previous.index_axis_move(Axis(0), f - 1);
+            let frame = rest.index_axis_mut(Axis(0), 0);
+            let align = procrustes(frame.view(), previous.view());
+            map_points(frame.into_dyn(), align);
+        }
+        self.bbox = bounding_box(self.positions.view().into_dyn()).unwrap();
+        self
+    }
+
+    /// Keep (up to) n of the most interesting frames, always including the first and the last one.
+    ///
+    /// Each transition between frames is scored by its share of the total node movement plus its
+    /// share of the total reduction of edge crossings, such that large energy drops and untangling
+    /// moments are kept, while the slow final convergence is skipped.
+    pub fn key_frames(self, n: usize) -> Self {
+        let last = self.frames() - 1;
+        if n >= self.frames() {
+            return self;
+        }
+        if n < 2 {
+            return self.select(&[last]).unwrap();
+        }
+        let movement: Vec<Float> = (1..=last)
+            .map(|f| {
+                let delta = &self.frame(f) - &self.frame(f - 1);
+                (&delta * &delta).sum_axis(Axis(1)).mapv(Float::sqrt).sum()
+            })
+            .collect();
+        let crossings: Vec<usize> = (0..=last).map(|f| metrics::crossings(&self.graph, self.frame(f))).collect();
+        let untangled: Vec<Float> = crossings.windows(2).map(|c| c[0].saturating_sub(c[1]) as Float).collect();
+        let share = |values: &[Float], f: usize| values[f - 1] / Float::max(values.iter().sum(), Float::MIN_POSITIVE);
+
+        let mut scored: Vec<(Float, usize)> = (1..last).map(|f| (share(&movement, f) + share(&untangled, f), f)).collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        let mut indices: Vec<usize> = scored.into_iter().take(n - 2).map(|(_, f)| f).collect();
+        indices.push(0);
+        indices.push(last);
+        indices.sort();
+        self.select(&indices).unwrap()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use ndarray::arr2;
 
     use crate::test::random_graph;
 
-    use super::ScatterLayout;
+    use super::{PositionsOnly, ScatterLayout, ScatterLayout3, ScatterLayoutSequence, ScatterLayoutSequence3};
+    use crate::layout::{BoundingBox, Point};
+    use crate::Float;
 
     #[test]
     fn fail_on_nan() {
@@ -230,6 +970,151 @@ mod test {
             .is_err());
     }
 
+    #[test]
+    fn subsample_sequence() {
+        let sequence = || {
+            let frames = (0..10).map(|f| arr2(&[[f as Float, 0.], [0., f as Float]])).collect();
+            ScatterLayoutSequence::new(random_graph(2, 2, 2), frames).unwrap()
+        };
+        let xs = |s: ScatterLayoutSequence<_>| (0..s.frames()).map(|f| s.coord(f, 0).x()).collect::<Vec<Float>>();
+        assert_eq!(xs(sequence().every_nth(4)), vec![0., 4., 8., 9.]);
+        assert_eq!(xs(sequence().downsample(4)), vec![0., 3., 6., 9.]);
+        assert_eq!(xs(sequence().downsample(20)).len(), 10);
+        assert!(sequence().select(&[10]).is_err());
+    }
+
+    #[test]
+    fn select_key_frames() {
+        // node 0 jumps between frames 2 and 3, and edges (0, 1) and (2, 3) stop crossing at frame 6
+        let frame = |f: usize| {
+            let x = if f < 3 { 0. } else { 10. };
+            let y = if f < 6 { 0. } else { 2. };
+            arr2(&[[x, y], [x, 1.], [x - 1., 0.5], [x + 1., 0.5]])
+        };
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize), (2, 3)], (0..10).map(frame).collect()).unwrap();
+        let selected = sequence.key_frames(4);
+        assert_eq!(selected.frames(), 4);
+        for (i, f) in [0, 3, 6, 9].into_iter().enumerate() {
+            assert_eq!(selected.frame(i), frame(f));
+        }
+    }
+
+    #[test]
+    fn detach_positions() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [4., 2.]])).unwrap();
+        let positions = layout.into_positions();
+        assert_eq!(positions.nodes(), 2);
+        assert_eq!(positions.bbox().width(), 4.);
+        assert_eq!(positions.coord(1).y(), 2.);
+        assert!(positions.clone().with_graph(vec![(0usize, 2usize)]).is_err());
+        assert_eq!(positions.into_array(), arr2(&[[0., 0.], [4., 2.]]));
+    }
+
+    #[test]
+    fn iterate_coordinates() {
+        let layout = ScatterLayout3::with_positions(vec![(0usize, 1usize)], arr2(&[[0., 1., 2.], [3., 4., 5.]])).unwrap();
+        assert_eq!(layout.iter().collect::<Vec<_>>(), vec![(0, Point([0., 1., 2.])), (1, Point([3., 4., 5.]))]);
+        assert_eq!((&layout).into_iter().size_hint(), (2, Some(2)));
+        assert_eq!(layout.positions(), layout.view());
+        assert_eq!(layout.clone().into_iter().map(|(_, p)| p).collect::<Vec<_>>(), layout.to_vec());
+
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [4., 2.]])).unwrap();
+        let positions: PositionsOnly = layout.iter().map(|(_, p)| Point([p.y(), p.x()])).collect();
+        assert_eq!(positions.bbox().height(), 4.);
+        assert_eq!(positions.into_array(), arr2(&[[0., 0.], [2., 4.]]));
+        assert_eq!(std::iter::empty().collect::<PositionsOnly>().nodes(), 0);
+    }
+
+    #[test]
+    fn geometric_transforms() {
+        let layout = || ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [4., 2.]])).unwrap();
+        assert_eq!(layout().translate(1., -1.).view(), arr2(&[[1., -1.], [5., 1.]]));
+        assert_eq!(layout().scale(2.).view(), arr2(&[[-2., -1.], [6., 3.]]));
+        assert_eq!(layout().scale(Float::NAN).view(), layout().view());
+        assert_eq!(layout().flip_x().view(), arr2(&[[4., 0.], [0., 2.]]));
+        assert_eq!(layout().flip_y().view(), arr2(&[[0., 2.], [4., 0.]]));
+        let rotated = layout().rotate(crate::consts::PI);
+        assert!((&rotated.view() - &arr2(&[[4., 2.], [0., 0.]])).iter().all(|d| d.abs() < 1e-5));
+        assert_eq!(rotated.bbox().width(), layout().bbox().width());
+
+        let target = BoundingBox(Point([0., 0.]), Point([8., 8.]));
+        assert_eq!(layout().fit_into(&target, false).view(), arr2(&[[0., 0.], [8., 8.]]));
+        assert_eq!(layout().fit_into(&target, true).view(), arr2(&[[0., 2.], [8., 6.]]));
+        // a single node is centered
+        let single = ScatterLayout::new(vec![(0usize, 0usize)], arr2(&[[3., 3.]])).unwrap();
+        assert_eq!(single.fit_into(&target, true).coord(0), Point([4., 4.]));
+
+        let frames = vec![arr2(&[[0., 0.], [2., 0.]]), arr2(&[[0., 0.], [4., 2.]])];
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], frames).unwrap();
+        let sequence = sequence.flip_x().translate(0., 1.).fit_into(&target, true);
+        assert_eq!(sequence.frame(0), arr2(&[[8., 2.], [4., 2.]]));
+        assert_eq!(sequence.bbox().lower_left(), Point([0., 2.]));
+    }
+
+    #[test]
+    fn canonical_orientation() {
+        let unit = BoundingBox(Point([0., 0.]), Point([1., 1.]));
+        let canonical = |layout: ScatterLayout<_>| layout.normalize_orientation().fit_into(&unit, true).view().to_owned();
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (1, 2), (2, 3), (3, 4), (4, 5)], arr2(&[[0., 0.], [3., 1.], [1., 4.], [6., 2.], [2., 2.], [5., 5.]])).unwrap();
+        let expected = canonical(layout.clone());
+        for theta in [0.5, 2., -2.5] {
+            for mirror in [false, true] {
+                let moved = layout.clone().rotate(theta).translate(10., -3.);
+                let moved = if mirror { moved.flip_y() } else { moved };
+                assert!((&canonical(moved) - &expected).iter().all(|d| d.abs() < 1e-4));
+            }
+        }
+        // the principal axis of a symmetric path is horizontal, with node 0 on the right
+        let path = ScatterLayout::new(vec![(0usize, 1usize), (1, 2)], arr2(&[[0., 2.], [1., 1.], [2., 0.]])).unwrap();
+        let path = path.normalize_orientation();
+        assert!(path.bbox().height() < 1e-5);
+        assert!(path.coord(0).x() > path.coord(2).x());
+    }
+
+    #[test]
+    fn procrustes_alignment() {
+        let graph = vec![(0usize, 1usize), (1, 2), (2, 3)];
+        let layout = ScatterLayout::new(graph.clone(), arr2(&[[0., 0.], [2., 1.], [3., 3.], [0., 4.]])).unwrap();
+        let moved = layout.clone().rotate(-2.).scale(0.5).translate(-7., 1.);
+        let aligned = moved.align_to(&layout).unwrap();
+        assert!((&aligned.view() - &layout.view()).iter().all(|d| d.abs() < 1e-4));
+        let single = ScatterLayout::new(vec![(0usize, 0usize)], arr2(&[[1., 1.]])).unwrap();
+        assert!(layout.clone().align_to(&single).is_err());
+
+        let frames = vec![layout.view().to_owned(), layout.clone().rotate(1.).translate(3., 3.).view().to_owned()];
+        let sequence = ScatterLayoutSequence::new(graph, frames).unwrap().align_frames();
+        assert!((&sequence.frame(1) - &layout.view()).iter().all(|d| d.abs() < 1e-4));
+        assert!((sequence.bbox().width() - layout.bbox().width()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn three_dimensions() {
+        let layout = ScatterLayout3::with_positions(vec![(0usize, 1usize)], arr2(&[[0., 1., -2.], [3., -1., 2.]])).unwrap();
+        assert_eq!(layout.bbox().depth(), 4.);
+        assert_eq!(layout.coord(1).z(), 2.);
+        assert_eq!(layout.project().bbox().width(), 3.);
+        assert_eq!(layout.to_string(), "ScatterLayout with 2 nodes in [(0, -1, -2) .. (3, 1, 2)] (extent 3 x 2 x 4)");
+
+        let unit = BoundingBox(Point([0.; 3]), Point([1.; 3]));
+        assert_eq!(layout.transform(&unit).coord(0), Point([0., 1., 0.]));
+
+        assert!(ScatterLayout3::with_positions(vec![(0usize, 1usize)], arr2(&[[0., 1.], [3., -1.]])).is_err());
+        assert!(ScatterLayout3::with_positions(vec![(0usize, 1usize)], arr2(&[[0., 1., Float::NAN], [3., -1., 0.]])).is_err());
+        assert!(ScatterLayoutSequence3::with_positions(vec![(0usize, 1usize)], vec![]).is_err());
+    }
+
+    #[test]
+    fn pretty_print() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [4., 2.]])).unwrap();
+        assert_eq!(
+            layout.to_string(),
+            "ScatterLayout with 2 nodes in [(0, 0) .. (4, 2)] (extent 4 x 2)"
+        );
+        let debug = format!("{:?}", layout);
+        assert_eq!(debug.lines().skip(1).map(|l| l.matches('o').count()).sum::<usize>(), 2);
+        assert_eq!(debug.lines().count(), 1 + 20 + 2);
+    }
+
     #[test]
     fn success() {
         ScatterLayout::new(random_graph(2, 2, 2), arr2(&[[0., 0.], [1., 1.]])).unwrap();
@@ -0,0 +1,120 @@
+//! Serde support for layouts and sequences.
+//!
+//! The graph is stored alongside the positions using its own serde implementation. The node count,
+//! frame count and bounding box are written for convenience of other consumers, but recomputed
+//! from the positions when deserializing.
+use ndarray::{Array2, Axis};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::layout::BoundingBox;
+use crate::{Float, Graph};
+
+use super::{ScatterLayout, ScatterLayoutSequence};
+
+fn rows(positions: ndarray::ArrayView2<Float>) -> Vec<[Float; 2]> {
+    positions.axis_iter(Axis(0)).map(|p| [p[0], p[1]]).collect()
+}
+
+fn array(rows: Vec<[Float; 2]>) -> Array2<Float> {
+    let nodes = rows.len();
+    Array2::from_shape_vec((nodes, 2), rows.into_iter().flatten().collect()).unwrap()
+}
+
+#[derive(Serialize)]
+struct LayoutRef<'a, G> {
+    graph: &'a G,
+    nodes: usize,
+    bbox: &'a BoundingBox,
+    positions: Vec<[Float; 2]>,
+}
+
+#[derive(Deserialize)]
+struct LayoutData<G> {
+    graph: G,
+    positions: Vec<[Float; 2]>,
+}
+
+impl<G: Graph + Serialize> Serialize for ScatterLayout<G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        LayoutRef {
+            graph: &self.graph,
+            nodes: self.graph.nodes(),
+            bbox: &self.bbox,
+            positions: rows(self.positions.view()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, G: Graph + Deserialize<'de>> Deserialize<'de> for ScatterLayout<G> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = LayoutData::<G>::deserialize(deserializer)?;
+        ScatterLayout::new(data.graph, array(data.positions)).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize)]
+struct SequenceRef<'a, G> {
+    graph: &'a G,
+    nodes: usize,
+    frames: usize,
+    bbox: &'a BoundingBox,
+    positions: Vec<Vec<[Float; 2]>>,
+}
+
+#[derive(Deserialize)]
+struct SequenceData<G> {
+    graph: G,
+    positions: Vec<Vec<[Float; 2]>>,
+}
+
+impl<G: Graph + Serialize> Serialize for ScatterLayoutSequence<G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SequenceRef {
+            graph: &self.graph,
+            nodes: self.graph.nodes(),
+            frames: self.frames(),
+            bbox: &self.bbox,
+            positions: (0..self.frames()).map(|f| rows(self.frame(f))).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, G: Graph + Deserialize<'de>> Deserialize<'de> for ScatterLayoutSequence<G> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SequenceData::<G>::deserialize(deserializer)?;
+        let frames = data.positions.into_iter().map(array).collect();
+        ScatterLayoutSequence::new(data.graph, frames).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use crate::layout::scatter::{ScatterLayout, ScatterLayoutSequence};
+
+    #[test]
+    fn roundtrip_json() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.5], [2., 1.]])).unwrap();
+        let json = serde_json::to_string(&layout).unwrap();
+        assert!(json.contains("\"nodes\":2") && json.contains("\"bbox\":[[0.0,0.5],[2.0,1.0]]"));
+        let restored: ScatterLayout<Vec<(usize, usize)>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.coord(1).x(), 2.);
+
+        let frames = vec![arr2(&[[0., 0.], [1., 1.]]), arr2(&[[2., 0.], [1., 3.]])];
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], frames).unwrap();
+        let json = serde_json::to_string(&sequence).unwrap();
+        assert!(json.contains("\"frames\":2"));
+        let restored: ScatterLayoutSequence<Vec<(usize, usize)>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.coord(1, 1).y(), 3.);
+    }
+
+    #[test]
+    fn reject_invalid_positions() {
+        let json = r#"{"graph": [[0, 1]], "positions": [[0.0, 0.0]]}"#;
+        assert!(serde_json::from_str::<ScatterLayout<Vec<(usize, usize)>>>(json).is_err());
+    }
+}
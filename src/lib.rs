@@ -1,48 +1,209 @@
 extern crate core;
 
+use engines::observer::Observer;
+
+pub mod analysis;
 pub mod engines;
+pub mod gallery;
+pub mod graph;
+pub mod io;
 pub mod layout;
+pub mod metrics;
 #[cfg(feature = "petgraph")]
 pub mod petgraph;
+#[cfg(feature = "config")]
+pub mod pipeline;
 pub mod render;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+
+/// The scalar type of all coordinates, forces and weights: `f32`, or `f64` with the `f64` feature,
+/// which avoids the rounding errors of large coordinates at twice the memory.
+#[cfg(not(feature = "f64"))]
+pub type Float = f32;
+#[cfg(feature = "f64")]
+pub type Float = f64;
+
+/// Mathematical constants of the [`Float`] type.
+#[cfg(not(feature = "f64"))]
+pub use std::f32::consts;
+#[cfg(feature = "f64")]
+pub use std::f64::consts;
 
 /// The algorithm that defines and computes the layout.
+///
+/// Layouts cannot fail, hence engines panic on malformed graphs, whose edges reference missing
+/// nodes or which have not one weight per edge. Check graphs from untrusted sources with
+/// [`validate`] first.
 pub trait Engine: Sized {
     type Layout<G: Graph>: Sized;
     type LayoutSequence<G: Graph>: Sized;
 
     fn compute<G: Graph>(self, graph: G) -> Self::Layout<G>;
+
+    /// Compute the layout, notifying the observer about every iteration.
+    ///
+    /// The observer may stop the engine early, in which case the layout reached so far is returned.
+    fn compute_observed<G: Graph, O: Observer>(self, graph: G, observer: O) -> Self::Layout<G>;
+
     fn animate<G: Graph>(self, graph: G) -> Self::LayoutSequence<G>;
 }
 
 /// Trait that needs to be implemented for graphs to support layouting.
 pub trait Graph: Sized {
-    /// The type of the used edge iterator.
-    type Edges: Iterator<Item=(usize, usize)>;
+    /// The type of the used edge iterator, which may borrow from the graph.
+    type Edges<'a>: Iterator<Item=(usize, usize)> where Self: 'a;
 
     /// The number of nodes of the graph.
     fn nodes(&self) -> usize;
 
     /// Get the pairs of (source, target) nodes.
-    fn edges(&self) -> Self::Edges;
+    ///
+    /// Engines call this in every iteration, hence implementations should iterate the edges lazily
+    /// rather than collect them.
+    fn edges(&self) -> Self::Edges<'_>;
+
+    /// Get the weight of each edge, in the same order as yielded by `edges()`.
+    ///
+    /// Unweighted graphs return `None`, which engines treat as unit weights.
+    fn edge_weights(&self) -> Option<Vec<Float>> {
+        None
+    }
+
+    /// Whether the (source, target) pairs returned by `edges()` are directed.
+    fn is_directed(&self) -> bool {
+        false
+    }
+
+    /// The number of edges yielded by `edges()`, if known without iterating them.
+    ///
+    /// Engines and exporters use it to preallocate buffers. Defaults to `None`.
+    fn edge_count_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// The label of the given node, which renderers show unless other labels are configured.
+    ///
+    /// Defaults to `None`, i.e. the renderers fall back to `node {n}`. Petgraph graphs label their
+    /// nodes by their weights, see `petgraph::NodeWeight`.
+    fn node_label(&self, node: usize) -> Option<String> {
+        let _ = node;
+        None
+    }
 
     fn layout<E: Engine>(self, engine: E) -> E::Layout<Self> {
         engine.compute(self)
     }
 
+    fn layout_observed<E: Engine, O: Observer>(self, engine: E, observer: O) -> E::Layout<Self> {
+        engine.compute_observed(self, observer)
+    }
+
     fn animate<E: Engine>(self, engine: E) -> E::LayoutSequence<Self> {
         engine.animate(self)
     }
 }
 
 impl<T> Graph for &T where T: Graph {
-    type Edges = T::Edges;
+    type Edges<'a> = T::Edges<'a> where Self: 'a;
     fn nodes(&self) -> usize { (*self).nodes() }
-    fn edges(&self) -> T::Edges { (*self).edges() }
+    fn edges(&self) -> T::Edges<'_> { (*self).edges() }
+    fn edge_weights(&self) -> Option<Vec<Float>> { (*self).edge_weights() }
+    fn is_directed(&self) -> bool { (*self).is_directed() }
+    fn edge_count_hint(&self) -> Option<usize> { (*self).edge_count_hint() }
+    fn node_label(&self, node: usize) -> Option<String> { (*self).node_label(node) }
     fn layout<E: Engine>(self, engine: E) -> E::Layout<Self> { engine.compute(self) }
+    fn layout_observed<E: Engine, O: Observer>(self, engine: E, observer: O) -> E::Layout<Self> { engine.compute_observed(self, observer) }
     fn animate<E: Engine>(self, engine: E) -> E::LayoutSequence<Self> { engine.animate(self) }
 }
 
+/// The edge iterator of graphs that store their edges in a slice.
+pub type EdgeIter<'a> = std::iter::Copied<std::slice::Iter<'a, (usize, usize)>>;
+
+/// An edge list is a graph whose node count is defined by the largest node id it references.
+impl Graph for Vec<(usize, usize)> {
+    type Edges<'a> = EdgeIter<'a>;
+
+    fn nodes(&self) -> usize {
+        // number of nodes is defined by the largest node id we know from the edge list.
+        let mut n: usize = 0;
+        for (s, t) in self {
+            n = usize::max(n, *s);
+            n = usize::max(n, *t);
+        }
+        n + 1
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        self.iter().copied()
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+/// Check that all edges of the graph reference existing nodes, and that weighted graphs have one
+/// weight per edge.
+///
+/// Returns an error naming the first offending edge index and node id, which is more helpful than
+/// the out of bounds panic a malformed `Graph` implementation would otherwise cause downstream.
+pub fn validate(graph: &impl Graph) -> Result<(), String> {
+    let nodes = graph.nodes();
+    for (i, (s, t)) in graph.edges().enumerate() {
+        if let Some(n) = [s, t].into_iter().find(|&n| n >= nodes) {
+            return Err(format!(
+                "Edge {} ({}, {}) references node {}, but the graph has only {} nodes",
+                i, s, t, n, nodes
+            ));
+        }
+    }
+    if let Some(weights) = graph.edge_weights() {
+        let edges = graph.edges().count();
+        if weights.len() != edges {
+            return Err(format!("Got {} weights for {} edges", weights.len(), edges));
+        }
+    }
+    Ok(())
+}
+
+/// Extension trait for graphs that carry a weight on each edge.
+///
+/// Wrap a weighted graph into [`Weighted`] to have engines take the weights into account.
+pub trait WeightedGraph: Graph {
+    /// The type of the used weighted edge iterator, which may borrow from the graph.
+    type WeightedEdges<'a>: Iterator<Item=(usize, usize, Float)> where Self: 'a;
+
+    /// Get the triples of (source, target, weight).
+    fn weighted_edges(&self) -> Self::WeightedEdges<'_>;
+}
+
+impl<T> WeightedGraph for &T where T: WeightedGraph {
+    type WeightedEdges<'a> = T::WeightedEdges<'a> where Self: 'a;
+    fn weighted_edges(&self) -> T::WeightedEdges<'_> { (*self).weighted_edges() }
+}
+
+/// Adapter that exposes the edge weights of a [`WeightedGraph`] through [`Graph::edge_weights`].
+#[derive(Clone, Debug)]
+pub struct Weighted<G: WeightedGraph>(pub G);
+
+impl<G: WeightedGraph> Graph for Weighted<G> {
+    type Edges<'a> = G::Edges<'a> where Self: 'a;
+    fn nodes(&self) -> usize { self.0.nodes() }
+    fn edges(&self) -> G::Edges<'_> { self.0.edges() }
+    fn is_directed(&self) -> bool { self.0.is_directed() }
+    fn edge_count_hint(&self) -> Option<usize> { self.0.edge_count_hint() }
+    fn node_label(&self, node: usize) -> Option<String> { self.0.node_label(node) }
+    fn edge_weights(&self) -> Option<Vec<Float>> {
+        Some(self.0.weighted_edges().map(|(_, _, w)| w).collect())
+    }
+}
+
+impl<G: WeightedGraph> WeightedGraph for Weighted<G> {
+    type WeightedEdges<'a> = G::WeightedEdges<'a> where Self: 'a;
+    fn weighted_edges(&self) -> G::WeightedEdges<'_> { self.0.weighted_edges() }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -55,39 +216,41 @@ mod test {
     #[derive(Clone, Debug)]
     struct L<G: Graph>(G);
 
-    impl Graph for Vec<(usize, usize)> {
-        type Edges = std::vec::IntoIter<(usize, usize)>;
+    impl Graph for Vec<(u32, u32)> {
+        type Edges<'a> = std::iter::Map<std::slice::Iter<'a, (u32, u32)>, fn(&(u32, u32)) -> (usize, usize)>;
 
         fn nodes(&self) -> usize {
             // number of nodes is defined by the largest node id we know from the edge list.
             let mut n: usize = 0;
             for (s, t) in self {
-                n = usize::max(n, *s);
-                n = usize::max(n, *t);
+                n = usize::max(n, *s as usize);
+                n = usize::max(n, *t as usize);
             }
             n + 1
         }
 
-        fn edges(&self) -> Self::Edges {
-            return self.clone().into_iter();
+        fn edges(&self) -> Self::Edges<'_> {
+            self.iter().map(|(s, t)| (*s as usize, *t as usize))
         }
     }
 
-    impl Graph for Vec<(u32, u32)> {
-        type Edges = std::vec::IntoIter<(usize, usize)>;
+    impl Graph for Vec<(usize, usize, Float)> {
+        type Edges<'a> = std::iter::Map<std::slice::Iter<'a, (usize, usize, Float)>, fn(&(usize, usize, Float)) -> (usize, usize)>;
 
         fn nodes(&self) -> usize {
-            // number of nodes is defined by the largest node id we know from the edge list.
-            let mut n: usize = 0;
-            for (s, t) in self {
-                n = usize::max(n, *s as usize);
-                n = usize::max(n, *t as usize);
-            }
-            n + 1
+            self.iter().map(|(s, t, _)| usize::max(*s, *t) + 1).max().unwrap_or(0)
         }
 
-        fn edges(&self) -> Self::Edges {
-            return self.iter().map(|(s, t)| (*s as usize, *t as usize)).collect::<Vec<(usize, usize)>>().into_iter();
+        fn edges(&self) -> Self::Edges<'_> {
+            self.iter().map(|(s, t, _)| (*s, *t))
+        }
+    }
+
+    impl WeightedGraph for Vec<(usize, usize, Float)> {
+        type WeightedEdges<'a> = std::iter::Copied<std::slice::Iter<'a, (usize, usize, Float)>>;
+
+        fn weighted_edges(&self) -> Self::WeightedEdges<'_> {
+            self.iter().copied()
         }
     }
 
@@ -96,11 +259,15 @@ mod test {
         type LayoutSequence<G: Graph> = (G, Vec<L<G>>);
 
         fn compute<G: Graph>(self, graph: G) -> Self::Layout<G> {
-            return L(graph);
+            L(graph)
+        }
+
+        fn compute_observed<G: Graph, O: Observer>(self, graph: G, _: O) -> Self::Layout<G> {
+            L(graph)
         }
 
         fn animate<G: Graph>(self, graph: G) -> Self::LayoutSequence<G> {
-            return (graph, Vec::new());
+            (graph, Vec::new())
         }
     }
 
@@ -120,90 +287,65 @@ mod test {
         layout_by_value(graph);
     }
 
+    #[test]
+    fn validate_edges() {
+        struct Liar;
+
+        impl Graph for Liar {
+            type Edges<'a> = std::vec::IntoIter<(usize, usize)>;
+            fn nodes(&self) -> usize { 2 }
+            fn edges(&self) -> Self::Edges<'_> { vec![(0, 1), (1, 2)].into_iter() }
+        }
+
+        assert!(validate(&vec![(0usize, 1usize), (1, 2)]).is_ok());
+        assert_eq!(
+            validate(&Liar),
+            Err("Edge 1 (1, 2) references node 2, but the graph has only 2 nodes".to_string())
+        );
+
+        struct Unweighed;
+
+        impl Graph for Unweighed {
+            type Edges<'a> = std::vec::IntoIter<(usize, usize)>;
+            fn nodes(&self) -> usize { 2 }
+            fn edges(&self) -> Self::Edges<'_> { vec![(0, 1)].into_iter() }
+            fn edge_weights(&self) -> Option<Vec<Float>> { Some(vec![1., 2.]) }
+        }
+
+        assert_eq!(validate(&Unweighed), Err("Got 2 weights for 1 edges".to_string()));
+        let panic = std::panic::catch_unwind(|| Unweighed.layout(engines::fruchterman_reingold::FruchtermanReingold::default())).unwrap_err();
+        assert!(panic.downcast_ref::<String>().unwrap().contains("Got 2 weights for 1 edges"));
+    }
+
+    #[test]
+    fn weighted_adapter_forwards_weights() {
+        let graph: Vec<(usize, usize, Float)> = vec![(0, 1, 2.), (1, 2, 0.5)];
+        assert_eq!(graph.edge_weights(), None);
+        assert_eq!(Weighted(&graph).edge_weights(), Some(vec![2., 0.5]));
+        assert_eq!(Weighted(&graph).nodes(), 3);
+    }
+
+    #[test]
+    fn edge_count_hints() {
+        let graph: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 0)];
+        assert_eq!(graph.edge_count_hint(), Some(3));
+        assert_eq!(Graph::edge_count_hint(&&graph), Some(3));
+        assert_eq!(std::rc::Rc::new(graph).edge_count_hint(), Some(3));
+        let weighted: Vec<(usize, usize, Float)> = vec![(0, 1, 2.)];
+        assert_eq!(Weighted(&weighted).edge_count_hint(), None);
+    }
+
 
     /// Create a random graph with given amout of edges and up to given amout of nodes.
+    ///
+    /// May contain self loops and duplicate edges, unlike [`crate::graph::generators::random`].
     pub fn random_graph(nodes: usize, edges: usize, seed: u64) -> impl Graph {
         let mut rng = StdRng::seed_from_u64(seed);
         (0..edges).map(|_| (rng.gen_range(0..nodes), rng.gen_range(0..nodes))).collect::<Vec<(usize, usize)>>()
     }
 
     /// Some predefined regular graphs helpful for testing and demonstration.
-    #[rustfmt::skip]
     pub fn defined_graphs() -> Vec<(&'static str, impl Graph)> {
-        let graphs: Vec<(&'static str, &'static [(u32, u32)])> = vec![
-            ("triangle", &[(0, 1), (1, 2), (2, 0)]),
-            ("square", &[(0, 1), (1, 2), (2, 3), (3, 0)]),
-            ("pentagon", &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]),
-            ("tetrahedron", &[(0, 1), (1, 2), (2, 0), (0, 3), (1, 3), (2, 3)]),
-            ("custom", &[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (1, 2), (2, 4), (2, 5), (4, 5)]),
-            ("cube", &[
-                // plane 1
-                (0, 1), (1, 2), (2, 3), (3, 0),
-                // plane 1
-                (4, 5), (5, 6), (6, 7), (7, 4),
-                // plane connections
-                (0, 4), (1, 5), (2, 6), (3, 7),
-            ],
-            ),
-            (
-                "tree",
-                &[
-                    // root->level1
-                    (0, 1), (0, 2),
-                    // level1->level2
-                    (1, 3), (1, 4), (1, 5), (2, 6), (2, 7),
-                    // level2->level3
-                    (3, 8), (4, 9), (4, 10), (6, 11), (6, 12), (6, 13), (7, 14),
-                    // level3->level4
-                    (14, 15), (14, 16), (14, 17), (14, 18), (14, 19)
-                ],
-            ),
-            (
-                "prism",
-                &[
-                    // plane 1
-                    (0, 1), (1, 2), (2, 0),
-                    // plane 2
-                    (3, 4), (4, 5), (5, 3),
-                    // connections
-                    (0, 3), (1, 4), (2, 5),
-                ],
-            ),
-            (
-                "pentagram",
-                &[
-                    // pentagon
-                    (0, 1), (1, 2), (2, 3), (3, 4), (4, 0),
-                    // diagonals
-                    (0, 2), (1, 3), (2, 4), (3, 0), (4, 1),
-                ],
-            ),
-            (
-                "disconnected-components",
-                &[
-                    // triangle 1
-                    (0, 1), (1, 2), (2, 0),
-                    // triangle 2
-                    (3, 4), (4, 5), (5, 3),
-                ],
-            ),
-            (
-                "triangulated-triangle",
-                &[
-                    // outer edge 1
-                    (0, 1), (1, 2), (2, 3),
-                    // outer edge 2
-                    (3, 4), (4, 5), (5, 6),
-                    // outer edge 3
-                    (6, 7), (7, 8), (8, 0),
-                    // cut edges
-                    (1, 8), (2, 4), (5, 7),
-                    // connections to center
-                    (1, 9), (2, 9), (4, 9), (5, 9), (7, 9), (8, 9),
-                ],
-            ),
-        ];
-        let v = graphs.iter().map(|&tpl| { (tpl.0, Vec::from(tpl.1)) }).collect();
-        v
+        crate::gallery::demo_graphs()
     }
 }
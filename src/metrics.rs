@@ -0,0 +1,139 @@
+//! Quality metrics of layouts.
+use ndarray::ArrayView2;
+
+use crate::{Float, Graph};
+
+/// Count the crossings of edges drawn as straight lines between the given positions.
+pub fn crossings(graph: &impl Graph, positions: ArrayView2<Float>) -> usize {
+    let mut edges: Vec<(usize, usize)> = Vec::with_capacity(graph.edge_count_hint().unwrap_or(0));
+    edges.extend(graph.edges());
+    let mut count = 0;
+    for (i, &e) in edges.iter().enumerate() {
+        for &f in &edges[i + 1..] {
+            if cross(positions, e, f) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Whether two edges drawn as straight lines cross. Edges sharing a node never cross.
+pub(crate) fn cross(positions: ArrayView2<Float>, (a, b): (usize, usize), (c, d): (usize, usize)) -> bool {
+    if a == c || a == d || b == c || b == d {
+        return false;
+    }
+    let p = |n: usize| (positions[[n, 0]], positions[[n, 1]]);
+    let orientation = |a: (Float, Float), b: (Float, Float), c: (Float, Float)| -> Float {
+        (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+    };
+    let (pa, pb, pc, pd) = (p(a), p(b), p(c), p(d));
+    orientation(pa, pb, pc) * orientation(pa, pb, pd) < 0. && orientation(pc, pd, pa) * orientation(pc, pd, pb) < 0.
+}
+
+/// The mean euclidean length of all edges.
+pub fn mean_edge_length(graph: &impl Graph, positions: ArrayView2<Float>) -> Float {
+    let lengths: Vec<Float> = graph
+        .edges()
+        .map(|(u, v)| {
+            let (dx, dy) = (positions[[u, 0]] - positions[[v, 0]], positions[[u, 1]] - positions[[v, 1]]);
+            (dx * dx + dy * dy).sqrt()
+        })
+        .collect();
+    lengths.iter().sum::<Float>() / usize::max(lengths.len(), 1) as Float
+}
+
+/// The estimated width of a character relative to the font size, as labels are not measured.
+const CHAR_WIDTH: Float = 0.6;
+
+/// Two elements of a drawing that overlap, see [`collisions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Collision {
+    /// The circles of two nodes overlap.
+    Nodes(usize, usize),
+    /// The labels of two nodes overlap.
+    Labels(usize, usize),
+    /// The label of a node overlaps the circle of another node.
+    LabelNode { label: usize, node: usize },
+}
+
+/// List the overlapping nodes and labels of a drawing, such that automated pipelines can detect
+/// unreadable outputs and retry with different parameters.
+///
+/// Nodes are circles with the given radii, indexed by node, nodes without radius use the last one.
+/// Labels are centered on their node, with an estimated width of 0.6 font sizes per character.
+/// Nodes without label, i.e. beyond the end of `labels`, have none.
+///
+/// ```
+/// use ndarray::arr2;
+/// use rs_plode::metrics::{collisions, Collision};
+///
+/// let positions = arr2(&[[0., 0.], [50., 0.], [200., 0.]]);
+/// let labels = ["a very long label".to_string(), "b".to_string(), "c".to_string()];
+/// assert_eq!(
+///     collisions(positions.view(), &[30.], &labels, 16.),
+///     vec![Collision::Nodes(0, 1), Collision::Labels(0, 1), Collision::LabelNode { label: 0, node: 1 }],
+/// );
+/// ```
+pub fn collisions(positions: ArrayView2<Float>, radii: &[Float], labels: &[String], font_size: Float) -> Vec<Collision> {
+    let nodes = positions.shape()[0];
+    let radius = |n: usize| radii.get(n).or(radii.last()).copied().unwrap_or(0.);
+    // half width and half height of each label box
+    let label = |n: usize| labels.get(n).map(|l| (l.chars().count() as Float * CHAR_WIDTH * font_size / 2., font_size / 2.));
+    let delta = |a: usize, b: usize| ((positions[[a, 0]] - positions[[b, 0]]).abs(), (positions[[a, 1]] - positions[[b, 1]]).abs());
+    // whether the label box of node a overlaps the circle of node b, via the box point closest to b
+    let covers = |a: usize, b: usize| {
+        label(a).is_some_and(|(w, h)| {
+            let (dx, dy) = delta(a, b);
+            (dx - w).max(0.).powi(2) + (dy - h).max(0.).powi(2) < radius(b).powi(2)
+        })
+    };
+
+    let mut result = Vec::new();
+    for a in 0..nodes {
+        for b in a + 1..nodes {
+            let (dx, dy) = delta(a, b);
+            if dx * dx + dy * dy < (radius(a) + radius(b)).powi(2) {
+                result.push(Collision::Nodes(a, b));
+            }
+            if let (Some((wa, ha)), Some((wb, hb))) = (label(a), label(b)) {
+                if dx < wa + wb && dy < ha + hb {
+                    result.push(Collision::Labels(a, b));
+                }
+            }
+            if covers(a, b) {
+                result.push(Collision::LabelNode { label: a, node: b });
+            }
+            if covers(b, a) {
+                result.push(Collision::LabelNode { label: b, node: a });
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{collisions, crossings, mean_edge_length, Collision};
+
+    #[test]
+    fn crossing_diagonals() {
+        let graph: Vec<(usize, usize)> = vec![(0, 2), (1, 3), (0, 1)];
+        let square = arr2(&[[0., 0.], [1., 0.], [1., 1.], [0., 1.]]);
+        assert_eq!(crossings(&graph, square.view()), 1);
+        assert_eq!(mean_edge_length(&vec![(0usize, 1usize)], square.view()), 1.);
+    }
+
+    #[test]
+    fn overlapping_labels() {
+        let positions = arr2(&[[0., 0.], [0., 100.], [60., 108.], [0., 300.]]);
+        let labels: Vec<String> = ["x", "long label", "long label"].iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            collisions(positions.view(), &[10.], &labels, 16.),
+            vec![Collision::Labels(1, 2)],
+        );
+        assert_eq!(collisions(positions.view(), &[10., 10., 10., 195.], &[], 16.), vec![Collision::Nodes(1, 3), Collision::Nodes(2, 3)]);
+    }
+}
@@ -1,26 +1,436 @@
-use crate::Graph;
-use petgraph::csr::IndexType;
+use crate::{Float, Graph, WeightedGraph};
+use petgraph::csr::{self, Csr, IndexType};
+use petgraph::graph::{EdgeReference, EdgeReferences, NodeIndex};
+use petgraph::graphmap::{AllEdges, GraphMap, NodeTrait};
+use petgraph::matrix_graph::{self, MatrixGraph, Nullable};
 use petgraph::prelude::EdgeRef;
+use petgraph::stable_graph::{self, StableGraph};
+use petgraph::visit::{IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
 use petgraph::EdgeType;
 
+/// Node weights of petgraph graphs, which provide the [`Graph::node_label`] of their node.
+///
+/// Strings, chars, numbers and bools label their node, `()` leaves it to the renderer's default.
+/// Implement it for custom node weights to label nodes by them, the default is no label.
+///
+/// ```
+/// use rs_plode::petgraph::NodeWeight;
+/// use rs_plode::Graph;
+///
+/// #[derive(Clone)]
+/// struct City { name: &'static str }
+///
+/// impl NodeWeight for City {
+///     fn label(&self) -> Option<String> {
+///         Some(self.name.to_string())
+///     }
+/// }
+///
+/// let mut graph = petgraph::Graph::<City, ()>::new();
+/// graph.add_node(City { name: "Berlin" });
+/// assert_eq!(graph.node_label(0), Some("Berlin".to_string()));
+/// ```
+pub trait NodeWeight {
+    /// The label of the node carrying this weight.
+    fn label(&self) -> Option<String> {
+        None
+    }
+}
+
+impl NodeWeight for () {}
+
+impl<T: NodeWeight + ?Sized> NodeWeight for &T {
+    fn label(&self) -> Option<String> {
+        (**self).label()
+    }
+}
+
+macro_rules! display_node_weight {
+    ($($weight:ty),* $(,)?) => {$(
+        impl NodeWeight for $weight {
+            fn label(&self) -> Option<String> {
+                Some(self.to_string())
+            }
+        }
+    )*};
+}
+
+display_node_weight!(str, String, char, bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// The edge iterator of petgraph graphs.
+pub type PetgraphEdges<'a, E, Ix> = std::iter::Map<EdgeReferences<'a, E, Ix>, fn(EdgeReference<'a, E, Ix>) -> (usize, usize)>;
+
+/// The weighted edge iterator of petgraph graphs.
+pub type PetgraphWeightedEdges<'a, E, Ix> =
+    std::iter::Map<EdgeReferences<'a, E, Ix>, fn(EdgeReference<'a, E, Ix>) -> (usize, usize, Float)>;
+
 impl<N, E, Ty, Ix> Graph for petgraph::Graph<N, E, Ty, Ix>
 where
     Ty: EdgeType,
     Ix: IndexType,
     E: Clone,
-    N: Clone,
+    N: Clone + NodeWeight,
 {
-    type Edges = std::vec::IntoIter<(usize, usize)>;
+    type Edges<'a> = PetgraphEdges<'a, E, Ix> where Self: 'a;
 
     fn nodes(&self) -> usize {
         petgraph::Graph::node_count(self)
     }
 
-    fn edges(&self) -> Self::Edges {
-        let v: Vec<(usize, usize)> = self
-            .edge_references()
-            .map(|edge| (edge.source().index(), edge.target().index()))
-            .collect();
-        v.into_iter()
+    fn edges(&self) -> Self::Edges<'_> {
+        self.edge_references().map(|edge| (edge.source().index(), edge.target().index()))
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(petgraph::Graph::edge_count(self))
+    }
+
+    fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    fn node_label(&self, node: usize) -> Option<String> {
+        self.node_weight(NodeIndex::new(node)).and_then(NodeWeight::label)
+    }
+}
+
+
+impl<N, E, Ty, Ix> WeightedGraph for petgraph::Graph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    E: Clone + Into<Float>,
+    N: Clone + NodeWeight,
+{
+    type WeightedEdges<'a> = PetgraphWeightedEdges<'a, E, Ix> where Self: 'a;
+
+    fn weighted_edges(&self) -> Self::WeightedEdges<'_> {
+        self.edge_references()
+            .map(|edge| (edge.source().index(), edge.target().index(), edge.weight().clone().into()))
+    }
+}
+
+/// The edges of petgraph graphs with holes in their node indices, renumbered lazily.
+pub struct RenumberedEdges<I> {
+    edges: I,
+    /// the consecutive number of each node index, if there are holes
+    dense: Option<Vec<Option<usize>>>,
+}
+
+impl<I> RenumberedEdges<I> {
+    /// Renumber the edges of a graph with the given node indices and their upper bound. Without
+    /// holes, the edges are passed through without allocating the dense numbering.
+    fn new(edges: I, indices: impl Iterator<Item = usize>, count: usize, bound: usize) -> Self {
+        let dense = (count != bound).then(|| {
+            let mut dense = vec![None; bound];
+            for (n, index) in indices.enumerate() {
+                dense[index] = Some(n);
+            }
+            dense
+        });
+        Self { edges, dense }
+    }
+}
+
+impl<I: Iterator<Item = (usize, usize)>> Iterator for RenumberedEdges<I> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(dense) = &self.dense else {
+            return self.edges.next();
+        };
+        self.edges.find_map(|(u, v)| Some((dense[u]?, dense[v]?)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges.size_hint()
+    }
+}
+
+/// The edge iterator of stable graphs.
+pub type StableGraphEdges<'a, E, Ix> = RenumberedEdges<
+    std::iter::Map<stable_graph::EdgeReferences<'a, E, Ix>, fn(stable_graph::EdgeReference<'a, E, Ix>) -> (usize, usize)>,
+>;
+
+/// Stable graphs keep the indices of nodes when others are removed. Layout node `n` is the `n`-th
+/// node of `node_indices()`, i.e. the nodes are renumbered consecutively, skipping the holes of
+/// removed nodes.
+impl<N, E, Ty, Ix> Graph for StableGraph<N, E, Ty, Ix>
+where
+    N: NodeWeight,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Edges<'a> = StableGraphEdges<'a, E, Ix> where Self: 'a;
+
+    fn nodes(&self) -> usize {
+        self.node_count()
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        RenumberedEdges::new(
+            self.edge_references().map(|edge| (edge.source().index(), edge.target().index())),
+            self.node_indices().map(|n| n.index()),
+            self.node_count(),
+            self.node_bound(),
+        )
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edge_count())
+    }
+
+    fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    fn node_label(&self, node: usize) -> Option<String> {
+        let index = match self.node_count() == self.node_bound() {
+            true => NodeIndex::new(node),
+            false => self.node_indices().nth(node)?,
+        };
+        self.node_weight(index).and_then(NodeWeight::label)
+    }
+}
+
+/// The edge iterator of graph maps.
+pub struct GraphMapEdges<'a, N, E, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    graph: &'a GraphMap<N, E, Ty>,
+    edges: AllEdges<'a, N, E, Ty>,
+}
+
+impl<N: NodeTrait, E, Ty: EdgeType> Iterator for GraphMapEdges<'_, N, E, Ty> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (u, v, _) = self.edges.next()?;
+        Some((self.graph.to_index(u), self.graph.to_index(v)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges.size_hint()
+    }
+}
+
+/// Graph maps identify nodes by their weight. Layout node `n` is the `n`-th node of `nodes()`,
+/// i.e. the nodes are numbered in insertion order.
+impl<N, E, Ty> Graph for GraphMap<N, E, Ty>
+where
+    N: NodeTrait + NodeWeight,
+    Ty: EdgeType,
+{
+    type Edges<'a> = GraphMapEdges<'a, N, E, Ty> where Self: 'a;
+
+    fn nodes(&self) -> usize {
+        self.node_count()
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        GraphMapEdges { graph: self, edges: self.all_edges() }
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edge_count())
+    }
+
+    fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    fn node_label(&self, node: usize) -> Option<String> {
+        GraphMap::nodes(self).nth(node)?.label()
+    }
+}
+
+/// The edge iterator of compressed sparse row graphs.
+pub struct CsrEdges<'a, N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    graph: &'a Csr<N, E, Ty, Ix>,
+    edges: csr::EdgeReferences<'a, E, Ty, Ix>,
+}
+
+impl<N, E, Ty: EdgeType, Ix: IndexType> Iterator for CsrEdges<'_, N, E, Ty, Ix> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let directed = Ty::is_directed();
+        self.edges
+            .by_ref()
+            .map(|edge| (edge.source(), edge.target()))
+            .find(|&(u, v)| directed || u <= v || !self.graph.contains_edge(v, u))
+            .map(|(u, v)| (u.index(), v.index()))
+    }
+}
+
+/// Compressed sparse row graphs have consecutive node indices. Undirected ones store every edge in
+/// both directions, which is yielded once.
+impl<N, E, Ty, Ix> Graph for Csr<N, E, Ty, Ix>
+where
+    N: NodeWeight,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Edges<'a> = CsrEdges<'a, N, E, Ty, Ix> where Self: 'a;
+
+    fn nodes(&self) -> usize {
+        self.node_count()
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        CsrEdges { graph: self, edges: self.edge_references() }
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edge_count())
+    }
+
+    fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    fn node_label(&self, node: usize) -> Option<String> {
+        (node < self.node_count()).then(|| self[Ix::new(node)].label())?
+    }
+}
+
+/// The edge iterator of matrix graphs.
+pub type MatrixGraphEdges<'a, E, Ty, Null, Ix> = RenumberedEdges<
+    std::iter::Map<
+        matrix_graph::EdgeReferences<'a, Ty, Null, Ix>,
+        fn((NodeIndex<Ix>, NodeIndex<Ix>, &'a E)) -> (usize, usize),
+    >,
+>;
+
+/// Matrix graphs keep the indices of nodes when others are removed. Layout node `n` is the `n`-th
+/// node of `node_identifiers()`, i.e. the nodes are renumbered consecutively, skipping the holes
+/// of removed nodes.
+impl<N, E, Ty, Null, Ix> Graph for MatrixGraph<N, E, Ty, Null, Ix>
+where
+    N: NodeWeight,
+    Ty: EdgeType,
+    Null: Nullable<Wrapped = E>,
+    Ix: IndexType,
+{
+    type Edges<'a> = MatrixGraphEdges<'a, E, Ty, Null, Ix> where Self: 'a;
+
+    fn nodes(&self) -> usize {
+        self.node_count()
+    }
+
+    fn edges(&self) -> Self::Edges<'_> {
+        RenumberedEdges::new(
+            self.edge_references().map(|(u, v, _)| (u.index(), v.index())),
+            self.node_identifiers().map(|n| n.index()),
+            self.node_count(),
+            self.node_bound(),
+        )
+    }
+
+    fn edge_count_hint(&self) -> Option<usize> {
+        Some(self.edge_count())
+    }
+
+    fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    fn node_label(&self, node: usize) -> Option<String> {
+        self.node_identifiers().nth(node).and_then(|n| self.node_weight(n).label())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use petgraph::csr::Csr;
+    use petgraph::graphmap::{DiGraphMap, UnGraphMap};
+    use petgraph::matrix_graph::{DiMatrix, UnMatrix};
+    use petgraph::stable_graph::StableGraph;
+    use petgraph::Undirected;
+
+    use crate::Graph;
+
+    fn edges(graph: &impl Graph) -> Vec<(usize, usize)> {
+        graph.edges().collect()
+    }
+
+    #[test]
+    fn stable_graph_skips_holes() {
+        let mut graph = StableGraph::<&str, ()>::new();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|n| graph.add_node(n));
+        graph.extend_with_edges([(a, b), (b, c), (c, d), (d, a)]);
+        assert_eq!(edges(&graph), vec![(0, 1), (1, 2), (2, 3), (3, 0)]);
+        graph.remove_node(b);
+        assert_eq!(Graph::nodes(&graph), 3);
+        assert_eq!(edges(&graph), vec![(1, 2), (2, 0)]);
+        assert!(crate::validate(&graph).is_ok());
+        assert!(Graph::is_directed(&graph));
+    }
+
+    #[test]
+    fn graph_map_in_insertion_order() {
+        let graph = DiGraphMap::<u32, ()>::from_edges([(30, 10), (10, 20), (20, 20)]);
+        assert_eq!(Graph::nodes(&graph), 3);
+        assert_eq!(edges(&graph), vec![(0, 1), (1, 2), (2, 2)]);
+        assert!(Graph::is_directed(&graph));
+        let graph = UnGraphMap::<char, ()>::from_edges([('x', 'y'), ('y', 'z')]);
+        assert_eq!(edges(&graph), vec![(0, 1), (1, 2)]);
+        assert!(!Graph::is_directed(&graph));
+    }
+
+    #[test]
+    fn csr_yields_undirected_edges_once() {
+        let mut graph: Csr<(), (), Undirected> = Csr::with_nodes(3);
+        graph.add_edge(0, 1, ());
+        graph.add_edge(2, 1, ());
+        graph.add_edge(2, 2, ());
+        assert_eq!(edges(&graph), vec![(0, 1), (1, 2), (2, 2)]);
+        assert_eq!(Graph::edge_count_hint(&graph), Some(3));
+        assert!(!Graph::is_directed(&graph));
+        let directed: Csr = Csr::from_sorted_edges(&[(0, 1), (1, 0), (1, 2)]).unwrap();
+        assert_eq!(edges(&directed), vec![(0, 1), (1, 0), (1, 2)]);
+        assert!(Graph::is_directed(&directed));
+    }
+
+    #[test]
+    fn matrix_graph_skips_holes() {
+        let mut graph = DiMatrix::<(), ()>::new();
+        let [a, b, c] = [(); 3].map(|_| graph.add_node(()));
+        graph.extend_with_edges([(a, b), (b, c), (c, a)]);
+        graph.remove_node(a);
+        assert_eq!(Graph::nodes(&graph), 2);
+        assert_eq!(edges(&graph), vec![(0, 1)]);
+        assert!(Graph::is_directed(&graph));
+        // undirected matrices store the lower triangle
+        let graph = UnMatrix::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        assert_eq!(edges(&graph), vec![(1, 0), (2, 1)]);
+        assert!(!Graph::is_directed(&graph));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn directed_graphs_render_arrowheads() {
+        use ndarray::arr2;
+        use petgraph::graph::{DiGraph, UnGraph};
+
+        use crate::layout::scatter::ScatterLayout;
+        use crate::render::svg::RenderSVG;
+
+        let positions = arr2(&[[0., 0.], [100., 100.]]);
+        let directed = DiGraph::<(), ()>::from_edges([(0u32, 1u32)]);
+        assert!(Graph::is_directed(&directed));
+        let document = ScatterLayout::new(directed, positions.clone()).unwrap().render(svg::Document::new()).unwrap().to_string();
+        assert!(document.contains("<marker") && document.contains("marker-end"));
+
+        let undirected = UnGraph::<(), ()>::from_edges([(0u32, 1u32)]);
+        assert!(!Graph::is_directed(&undirected));
+        let document = ScatterLayout::new(undirected, positions).unwrap().render(svg::Document::new()).unwrap().to_string();
+        assert!(!document.contains("marker-end"));
     }
 }
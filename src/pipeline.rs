@@ -0,0 +1,321 @@
+//! Layout pipelines read from TOML or YAML configuration files, such that layout settings can be
+//! version-controlled and run via the `rs-plode run` command or the API.
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::engines::fruchterman_reingold::FruchtermanReingold;
+use crate::engines::lattice::{Grid, Hexagonal, Spiral};
+use crate::engines::termination::Termination;
+use crate::io::edgelist::EdgeListGraph;
+use crate::layout::scatter::ScatterLayout;
+use crate::render::dot::RenderDOT;
+use crate::render::scene::RenderScene;
+use crate::render::svg::{RenderOptions, RenderSVG};
+use crate::{Float, Graph};
+
+/// A graph read from an edge list, laid out by an engine and written to several outputs.
+///
+/// Relative paths are resolved against the directory of the configuration file. The format of an
+/// output is given by its extension (`svg`, `json`, `dot` and, with the `raster` feature, `png`)
+/// unless set explicitly. All settings but the input and the outputs are optional.
+///
+/// ```toml
+/// input = "roads.csv"
+///
+/// [engine]
+/// kind = "fruchterman-reingold" # or grid, spiral, hexagonal
+/// k = 150
+/// seed = 7
+/// iterations = 300
+///
+/// [style]
+/// fill = "steelblue"
+/// labels = true # the node names of the edge list
+///
+/// [[output]]
+/// path = "out/roads.svg"
+///
+/// [[output]]
+/// path = "out/roads.positions"
+/// format = "json"
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pipeline {
+    config: Config,
+    base: PathBuf,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    input: PathBuf,
+    #[serde(default)]
+    engine: EngineConfig,
+    #[serde(default)]
+    style: StyleConfig,
+    #[serde(rename = "output")]
+    outputs: Vec<OutputConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case", deny_unknown_fields)]
+enum EngineConfig {
+    FruchtermanReingold {
+        #[serde(default = "default_k")]
+        k: Float,
+        #[serde(default)]
+        seed: u64,
+        #[serde(default = "default_iterations")]
+        iterations: usize,
+        #[serde(default)]
+        smoothing: Float,
+    },
+    Grid {
+        #[serde(default = "default_k")]
+        k: Float,
+        columns: Option<usize>,
+    },
+    Spiral {
+        #[serde(default = "default_k")]
+        k: Float,
+    },
+    Hexagonal {
+        #[serde(default = "default_k")]
+        k: Float,
+    },
+}
+
+fn default_k() -> Float {
+    150.
+}
+
+fn default_iterations() -> usize {
+    200
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self::FruchtermanReingold {
+            k: default_k(),
+            seed: 0,
+            iterations: default_iterations(),
+            smoothing: 0.,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleConfig {
+    radius: Option<Float>,
+    fill: Option<String>,
+    stroke: Option<String>,
+    background: Option<String>,
+    edge_width: Option<Float>,
+    labels: Option<bool>,
+    font_size: Option<Float>,
+    curved_edges: Option<Float>,
+    clip_edges: Option<bool>,
+    stylesheet: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutputConfig {
+    path: PathBuf,
+    format: Option<String>,
+    /// the resolution of png outputs
+    #[cfg_attr(not(feature = "raster"), allow(dead_code))]
+    width: Option<u32>,
+    #[cfg_attr(not(feature = "raster"), allow(dead_code))]
+    height: Option<u32>,
+}
+
+impl Pipeline {
+    /// Read the pipeline from a `.toml`, `.yaml` or `.yml` file.
+    pub fn from_config(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let pipeline = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml(&source),
+            Some("yaml") | Some("yml") => Self::from_yaml(&source),
+            _ => return Err(format!("Unknown configuration format of {}, expected toml or yaml", path.display())),
+        }
+        .map_err(|e| format!("Invalid configuration {}: {}", path.display(), e))?;
+        Ok(pipeline.base(path.parent().unwrap_or(Path::new(""))))
+    }
+
+    /// Parse a TOML configuration, whose relative paths are resolved against the working directory.
+    pub fn from_toml(source: &str) -> Result<Self, String> {
+        Self::new(toml::from_str(source).map_err(|e| e.to_string())?)
+    }
+
+    /// Parse a YAML configuration, whose relative paths are resolved against the working directory.
+    pub fn from_yaml(source: &str) -> Result<Self, String> {
+        Self::new(serde_yaml::from_str(source).map_err(|e| e.to_string())?)
+    }
+
+    fn new(config: Config) -> Result<Self, String> {
+        for output in &config.outputs {
+            format(output)?;
+        }
+        Ok(Self { config, base: PathBuf::new() })
+    }
+
+    /// Set the directory relative input and output paths are resolved against.
+    pub fn base(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.base = directory.into();
+        self
+    }
+
+    /// Read the input graph and lay it out with the configured engine.
+    pub fn layout(&self) -> Result<ScatterLayout<EdgeListGraph>, String> {
+        let graph = EdgeListGraph::read(self.base.join(&self.config.input))?;
+        Ok(match self.config.engine {
+            EngineConfig::FruchtermanReingold { k, seed, iterations, smoothing } => graph.layout(
+                FruchtermanReingold::new(k, seed)
+                    .until(Termination::Iterations(iterations))
+                    .smoothing(smoothing),
+            ),
+            EngineConfig::Grid { k, columns: Some(columns) } => graph.layout(Grid::new(k).columns(columns)),
+            EngineConfig::Grid { k, columns: None } => graph.layout(Grid::new(k)),
+            EngineConfig::Spiral { k } => graph.layout(Spiral::new(k)),
+            EngineConfig::Hexagonal { k } => graph.layout(Hexagonal::new(k)),
+        })
+    }
+
+    /// The svg render options given by the style section, labelling nodes with their names.
+    pub fn render_options(&self, graph: &EdgeListGraph) -> RenderOptions {
+        let style = &self.config.style;
+        let mut options = RenderOptions::default();
+        if let Some(radius) = style.radius {
+            options = options.node_radius(radius);
+        }
+        if let Some(fill) = &style.fill {
+            options = options.fill(fill);
+        }
+        if let Some(stroke) = &style.stroke {
+            options = options.stroke(stroke);
+        }
+        if let Some(background) = &style.background {
+            options = options.background(background);
+        }
+        if let Some(width) = style.edge_width {
+            options = options.edge_width(width);
+        }
+        if let Some(size) = style.font_size {
+            options = options.font_size(size);
+        }
+        if let Some(curvature) = style.curved_edges {
+            options = options.curved_edges(curvature);
+        }
+        if let Some(clip) = style.clip_edges {
+            options = options.clip_edges(clip);
+        }
+        if let Some(stylesheet) = style.stylesheet {
+            options = options.stylesheet(stylesheet);
+        }
+        if let Some(labels) = style.labels {
+            options = options.labels(labels).node_labels(graph.names().to_vec());
+        }
+        options
+    }
+
+    /// Lay out the graph and write all outputs, creating missing directories. Returns the written
+    /// paths.
+    pub fn run(&self) -> Result<Vec<PathBuf>, String> {
+        let layout = self.layout()?;
+        let options = self.render_options(layout.graph());
+        let mut paths = Vec::with_capacity(self.config.outputs.len());
+        for output in &self.config.outputs {
+            let path = self.base.join(&output.path);
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let write = |content: String| std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e));
+            match format(output)? {
+                "svg" => write(layout.clone().to_document(&options)?.to_string())?,
+                "json" => write(layout.to_json()?)?,
+                "dot" => write(layout.to_dot()?)?,
+                #[cfg(feature = "raster")]
+                "png" => {
+                    use crate::render::png::{RasterOptions, RenderPNG};
+
+                    let raster = RasterOptions::default().resolution(output.width.unwrap_or(800), output.height.unwrap_or(800));
+                    let raster = match self.config.style.radius {
+                        Some(radius) => raster.node_radius(radius as f32),
+                        None => raster,
+                    };
+                    layout
+                        .clone()
+                        .render_png(&raster)?
+                        .save_png(&path)
+                        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?
+                }
+                _ => unreachable!("formats are validated on construction"),
+            }
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// The format of the output, given explicitly or by the extension of its path.
+fn format(output: &OutputConfig) -> Result<&str, String> {
+    let format = match &output.format {
+        Some(format) => format.as_str(),
+        None => output.path.extension().and_then(|e| e.to_str()).unwrap_or(""),
+    };
+    let supported = ["svg", "json", "dot", #[cfg(feature = "raster")] "png"];
+    match supported.contains(&format) {
+        true => Ok(format),
+        false => Err(format!("Unsupported output format '{}' of {}, expected one of {}", format, output.path.display(), supported.join(", "))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Pipeline;
+    use crate::render::svg::RenderSVG;
+    use crate::Graph;
+
+    #[test]
+    fn run_toml_and_yaml_configs() {
+        let directory = std::env::temp_dir().join(format!("rs-plode-pipeline-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("graph.csv"), "a,b\nb,c\nc,a\n").unwrap();
+        let toml = "input = \"graph.csv\"\n\
+                    [engine]\nkind = \"grid\"\nk = 10\ncolumns = 3\n\
+                    [style]\nfill = \"crimson\"\nlabels = true\n\
+                    [[output]]\npath = \"out/graph.svg\"\n\
+                    [[output]]\npath = \"out/graph.txt\"\nformat = \"dot\"\n";
+        std::fs::write(directory.join("pipeline.toml"), toml).unwrap();
+
+        let pipeline = Pipeline::from_config(directory.join("pipeline.toml")).unwrap();
+        let layout = pipeline.layout().unwrap();
+        assert_eq!(layout.graph().nodes(), 3);
+        assert_eq!(layout.coord(2).x(), 20.);
+        let paths = pipeline.run().unwrap();
+        assert_eq!(paths, vec![directory.join("out/graph.svg"), directory.join("out/graph.txt")]);
+        let svg = std::fs::read_to_string(&paths[0]).unwrap();
+        assert!(svg.contains("crimson") && svg.contains("b\n"));
+        assert!(std::fs::read_to_string(&paths[1]).unwrap().starts_with("graph {"));
+
+        let unlabelled = Pipeline::from_toml(&toml.replace("labels = true", "labels = false")).unwrap().base(&directory);
+        let options = unlabelled.render_options(layout.graph());
+        assert!(!layout.clone().to_document(&options).unwrap().to_string().contains("<text"));
+
+        let yaml = "input: graph.csv\nengine:\n  kind: fruchterman-reingold\n  seed: 3\n  iterations: 20\noutput:\n  - path: graph.json\n";
+        let pipeline = Pipeline::from_yaml(yaml).unwrap().base(&directory);
+        assert!(std::fs::read_to_string(&pipeline.run().unwrap()[0]).unwrap().contains("\"positions\""));
+
+        // typos and unsupported outputs are rejected early
+        assert!(Pipeline::from_yaml("input: graph.csv\nstyle:\n  fil: red\noutput: []\n").is_err());
+        assert!(Pipeline::from_toml("input = \"graph.csv\"\noutput = []\n[engine]\nkind = \"fruchterman-reingold\"\niteration = 300\n").unwrap_err().contains("iteration"));
+        assert!(Pipeline::from_toml("input = \"graph.csv\"\noutput = []\n[engine]\nkind = \"fruchterman-reingold\"\niterations = 300\n").is_ok());
+        assert!(Pipeline::from_toml("input = \"graph.csv\"\n[[output]]\npath = \"graph.pdf\"\n").is_err());
+        assert!(Pipeline::from_config(directory.join("graph.csv")).is_err());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
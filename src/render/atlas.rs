@@ -0,0 +1,161 @@
+use std::fmt::Write;
+
+use super::json_string;
+use crate::layout::scatter::ScatterLayout;
+use crate::{validate, Float, Graph};
+
+/// Options for the sprite atlas export of layouts.
+#[derive(Clone, Debug)]
+pub struct AtlasOptions {
+    width: u32,
+    height: u32,
+    sprite_size: u32,
+    sprites: Vec<String>,
+    y_up: bool,
+}
+
+impl Default for AtlasOptions {
+    fn default() -> Self {
+        Self {
+            width: 1024,
+            height: 1024,
+            sprite_size: 32,
+            sprites: Vec::new(),
+            y_up: true,
+        }
+    }
+}
+
+impl AtlasOptions {
+    /// Set the size of the target area in pixels, which the layout is fitted into.
+    pub fn resolution(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Set the edge length of the square node sprites in pixels. A margin of half the sprite size is
+    /// kept around the layout.
+    pub fn sprite_size(mut self, size: u32) -> Self {
+        self.sprite_size = size;
+        self
+    }
+
+    /// Set the sprite name of each node, indexed by node. Nodes without name use `"node"`.
+    pub fn sprites(mut self, sprites: Vec<String>) -> Self {
+        self.sprites = sprites;
+        self
+    }
+
+    /// Whether the y axis points up, as in unity and bevy (default), or down as in svg and most
+    /// image formats.
+    pub fn y_up(mut self, y_up: bool) -> Self {
+        self.y_up = y_up;
+        self
+    }
+}
+
+/// Export of layouts as json description for game engines.
+///
+/// The document lists every node with its sprite name, its pixel position `x`, `y` within the
+/// configured resolution, its normalized position `u`, `v` in `[0, 1]`, its degree and a `z` order,
+/// where nodes with higher degree are drawn on top. Edges are listed as pairs of node ids.
+pub trait RenderAtlas {
+    /// Serialize self into an atlas json document, returning a string indicating a failure.
+    fn to_atlas(&self, options: &AtlasOptions) -> Result<String, String>;
+}
+
+impl<G: Graph> RenderAtlas for ScatterLayout<G> {
+    fn to_atlas(&self, options: &AtlasOptions) -> Result<String, String> {
+        validate(&self.graph)?;
+        let nodes = self.graph.nodes();
+        let mut edges: Vec<(usize, usize)> = Vec::with_capacity(self.graph.edge_count_hint().unwrap_or(0));
+        edges.extend(self.graph.edges());
+        let mut degree = vec![0usize; nodes];
+        for &(u, v) in &edges {
+            degree[u] += 1;
+            degree[v] += 1;
+        }
+        // rank nodes by degree, ties broken by id to keep the order deterministic
+        let mut order: Vec<usize> = (0..nodes).collect();
+        order.sort_by_key(|&n| (degree[n], n));
+        let mut z = vec![0; nodes];
+        for (rank, &n) in order.iter().enumerate() {
+            z[n] = rank;
+        }
+
+        let bbox = self.bbox();
+        let margin = options.sprite_size as Float / 2.;
+        let (w, h) = (options.width as Float - 2. * margin, options.height as Float - 2. * margin);
+        let scale = match (bbox.width() > 0., bbox.height() > 0.) {
+            (true, true) => Float::min(w / bbox.width(), h / bbox.height()),
+            (true, false) => w / bbox.width(),
+            (false, true) => h / bbox.height(),
+            (false, false) => 1.,
+        };
+        let offset = (
+            margin + (w - bbox.width() * scale) / 2.,
+            margin + (h - bbox.height() * scale) / 2.,
+        );
+
+        let mut json = String::new();
+        writeln!(
+            json,
+            "{{\n  \"version\": 1,\n  \"width\": {},\n  \"height\": {},\n  \"sprite_size\": {},\n  \"y_up\": {},\n  \"nodes\": [",
+            options.width, options.height, options.sprite_size, options.y_up
+        )
+        .unwrap();
+        for n in 0..nodes {
+            let p = self.coord(n);
+            let x = offset.0 + (p.x() - bbox.lower_left().x()) * scale;
+            let y = offset.1 + (p.y() - bbox.lower_left().y()) * scale;
+            let y = if options.y_up { options.height as Float - y } else { y };
+            let sprite = options.sprites.get(n).map_or("node", String::as_str);
+            writeln!(
+                json,
+                "    {{\"id\": {}, \"sprite\": {}, \"x\": {}, \"y\": {}, \"u\": {}, \"v\": {}, \"z\": {}, \"degree\": {}}}{}",
+                n,
+                json_string(sprite),
+                x,
+                y,
+                x / options.width as Float,
+                y / options.height as Float,
+                z[n],
+                degree[n],
+                if n + 1 < nodes { "," } else { "" }
+            )
+            .unwrap();
+        }
+        let edges: Vec<String> = edges.iter().map(|(u, v)| format!("[{}, {}]", u, v)).collect();
+        writeln!(json, "  ],\n  \"edges\": [{}]\n}}", edges.join(", ")).unwrap();
+        Ok(json)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{AtlasOptions, RenderAtlas};
+    use crate::layout::scatter::ScatterLayout;
+    use crate::Float;
+
+    #[test]
+    fn atlas_json() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (1, 2)], arr2(&[[0., 0.], [50., 0.], [100., 100.]])).unwrap();
+        let options = AtlasOptions::default()
+            .resolution(120, 120)
+            .sprite_size(20)
+            .sprites(vec!["hub \"a\"".to_string()]);
+        let json = layout.to_atlas(&options).unwrap();
+        // node 0 sits in the lower left corner, which is at the bottom for y up
+        let (u, v) = (10. as Float / 120., 110. as Float / 120.);
+        assert!(json.contains(&format!("{{\"id\": 0, \"sprite\": \"hub \\\"a\\\"\", \"x\": 10, \"y\": 110, \"u\": {}, \"v\": {}, \"z\": 0, \"degree\": 1}}", u, v)));
+        // the center node has the highest degree and is drawn on top
+        assert!(json.contains(&format!("\"sprite\": \"node\", \"x\": 60, \"y\": 110, \"u\": 0.5, \"v\": {}, \"z\": 2, \"degree\": 2}}", v)));
+        assert!(json.contains("\"edges\": [[0, 1], [1, 2]]"));
+
+        let json = layout.to_atlas(&options.y_up(false)).unwrap();
+        assert!(json.contains("\"x\": 10, \"y\": 10,"));
+    }
+}
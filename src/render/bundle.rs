@@ -0,0 +1,89 @@
+//! Writing all common output formats of a layout at once.
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use super::png::{rasterize, RasterOptions};
+use super::scene::RenderScene;
+use super::svg::{RenderOptions, RenderSVG};
+use crate::layout::scatter::ScatterLayout;
+use crate::{validate, Graph};
+
+/// Options for [`export_bundle`], combining the options of the individual formats.
+#[derive(Clone, Debug, Default)]
+pub struct BundleOptions {
+    svg: RenderOptions,
+    raster: RasterOptions,
+}
+
+impl BundleOptions {
+    /// Set the options of the svg document.
+    pub fn svg(mut self, options: RenderOptions) -> Self {
+        self.svg = options;
+        self
+    }
+
+    /// Set the options of the png preview.
+    pub fn raster(mut self, options: RasterOptions) -> Self {
+        self.raster = options;
+        self
+    }
+}
+
+/// Write the layout as svg document, png preview and json data (see [`RenderScene::to_json`]) next
+/// to each other, returning the written paths.
+///
+/// The extensions `.svg`, `.png` and `.json` are appended to the base path, which thus must not
+/// have one itself. Missing parent directories are created.
+///
+/// ```no_run
+/// use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+/// use rs_plode::render::bundle::{export_bundle, BundleOptions};
+/// use rs_plode::Graph;
+///
+/// let layout = vec![(0, 1), (1, 2), (2, 0)].layout(FruchtermanReingold::default());
+/// // writes docs/triangle.svg, docs/triangle.png and docs/triangle.json
+/// export_bundle(layout, "docs/triangle", &BundleOptions::default()).unwrap();
+/// ```
+pub fn export_bundle<G: Graph>(layout: ScatterLayout<G>, basepath: impl AsRef<Path>, options: &BundleOptions) -> Result<Vec<PathBuf>, String> {
+    validate(&layout.graph)?;
+    let basepath = basepath.as_ref();
+    let path = |extension: &str| {
+        let mut path = OsString::from(basepath);
+        path.push(extension);
+        PathBuf::from(path)
+    };
+    let paths = vec![path(".svg"), path(".png"), path(".json")];
+    if let Some(parent) = basepath.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    std::fs::write(&paths[2], layout.to_json()?).map_err(|e| e.to_string())?;
+    rasterize(&layout.graph, layout.bbox(), |n| layout.coord(n), &options.raster)?
+        .save_png(&paths[1])
+        .map_err(|e| e.to_string())?;
+    svg::save(&paths[0], &layout.to_document(&options.svg)?).map_err(|e| e.to_string())?;
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{export_bundle, BundleOptions};
+    use crate::layout::scatter::ScatterLayout;
+    use crate::render::png::RasterOptions;
+
+    #[test]
+    fn write_all_formats() {
+        let directory = std::env::temp_dir().join(format!("rs-plode-bundle-{}", std::process::id()));
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [100., 50.]])).unwrap();
+        let options = BundleOptions::default().raster(RasterOptions::default().resolution(64, 32));
+        let paths = export_bundle(layout, directory.join("graph.v2"), &options).unwrap();
+        let names: Vec<String> = paths.iter().map(|p| p.file_name().unwrap().to_string_lossy().into_owned()).collect();
+        assert_eq!(names, vec!["graph.v2.svg", "graph.v2.png", "graph.v2.json"]);
+        assert!(std::fs::read_to_string(&paths[0]).unwrap().contains("<svg"));
+        assert_eq!(&std::fs::read(&paths[1]).unwrap()[1..4], b"PNG");
+        assert!(std::fs::read_to_string(&paths[2]).unwrap().contains("\"positions\": [0, 0, 100, 50]"));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::Write;
+
+use crate::layout::scatter::ScatterLayout;
+use crate::{validate, Graph};
+
+/// Serialization to the Graphviz DOT language.
+///
+/// Node positions are written as pinned `pos` attributes in points, such that the layout is kept
+/// by the Graphviz tools, e.g. `neato -n2 -Tpdf layout.dot`.
+pub trait RenderDOT {
+    /// Serialize self into a DOT document, returning a string indicating a failure.
+    fn to_dot(&self) -> Result<String, String>;
+}
+
+impl<G: Graph> RenderDOT for ScatterLayout<G> {
+    fn to_dot(&self) -> Result<String, String> {
+        validate(&self.graph)?;
+        let (keyword, connector) = if self.graph.is_directed() {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let weights = self.graph.edge_weights();
+
+        // roughly 40 bytes per node and 20 per edge
+        let edges = self.graph.edge_count_hint().unwrap_or(0);
+        let mut dot = String::with_capacity(40 * self.graph.nodes() + 20 * edges);
+        writeln!(dot, "{} {{", keyword).unwrap();
+        for n in 0..self.graph.nodes() {
+            let p = self.coord(n);
+            writeln!(dot, "  {} [pos=\"{},{}!\", pin=true];", n, p.x(), p.y()).unwrap();
+        }
+        for (i, (u, v)) in self.graph.edges().enumerate() {
+            match weights.as_ref().and_then(|w| w.get(i)) {
+                Some(w) => writeln!(dot, "  {} {} {} [weight={}];", u, connector, v, w).unwrap(),
+                None => writeln!(dot, "  {} {} {};", u, connector, v).unwrap(),
+            }
+        }
+        dot.push_str("}\n");
+        Ok(dot)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::RenderDOT;
+    use crate::layout::scatter::ScatterLayout;
+
+    #[test]
+    fn layout_to_dot() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.5], [100., 0.]])).unwrap();
+        assert_eq!(
+            layout.to_dot().unwrap(),
+            "graph {\n  0 [pos=\"0,0.5!\", pin=true];\n  1 [pos=\"100,0!\", pin=true];\n  0 -- 1;\n}\n"
+        );
+    }
+}
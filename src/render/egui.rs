@@ -0,0 +1,161 @@
+//! Interactive viewing of layouts inside [egui](https://github.com/emilk/egui) applications.
+//!
+//! A [`LayoutView`] keeps the pan and zoom state between frames of the application. Dragging pans
+//! the view, scrolling or pinching zooms around the pointer and double clicking resets the view.
+//!
+//! ```no_run
+//! use rs_plode::engines::fruchterman_reingold::FruchtermanReingold;
+//! use rs_plode::render::egui::LayoutView;
+//!
+//! // step a live engine, one iteration per repaint of the application
+//! let graph = vec![(0usize, 1usize), (1, 2), (2, 0)];
+//! let mut frames = FruchtermanReingold::default().frames(&graph);
+//! let mut current = frames.next().unwrap().unwrap();
+//! let mut view = LayoutView::default();
+//! # let ctx = egui::Context::default();
+//! egui::CentralPanel::default().show(&ctx, |ui| {
+//!     if let Some(Ok(next)) = frames.next() {
+//!         current = next;
+//!         ui.ctx().request_repaint();
+//!     }
+//!     view.show(ui, &current);
+//! });
+//! ```
+use egui::{Color32, Pos2, Rect, Response, Sense, Stroke, Ui, Vec2};
+
+use crate::layout::scatter::{ScatterLayout, ScatterLayoutSequence};
+use crate::layout::{BoundingBox, Point};
+use crate::Graph;
+
+/// The pan and zoom state of a widget drawing layouts, see the [module](self) documentation.
+#[derive(Clone, Debug)]
+pub struct LayoutView {
+    /// offset of the drawing in screen points
+    pan: Vec2,
+    zoom: f32,
+    node_radius: f32,
+    fill: Color32,
+    stroke: Stroke,
+}
+
+impl Default for LayoutView {
+    fn default() -> Self {
+        Self {
+            pan: Vec2::ZERO,
+            zoom: 1.,
+            node_radius: 5.,
+            fill: Color32::WHITE,
+            stroke: Stroke::new(1., Color32::GRAY),
+        }
+    }
+}
+
+impl LayoutView {
+    /// Set the node radius in screen points, independent of the zoom.
+    pub fn node_radius(mut self, radius: f32) -> Self {
+        self.node_radius = radius;
+        self
+    }
+
+    /// Set the fill color of nodes.
+    pub fn fill(mut self, color: Color32) -> Self {
+        self.fill = color;
+        self
+    }
+
+    /// Set the stroke of edges and node outlines.
+    pub fn stroke(mut self, stroke: Stroke) -> Self {
+        self.stroke = stroke;
+        self
+    }
+
+    /// Reset pan and zoom, such that the layout fits into the available space.
+    pub fn reset(&mut self) {
+        self.pan = Vec2::ZERO;
+        self.zoom = 1.;
+    }
+
+    /// Draw the layout into all available space of the ui.
+    pub fn show<G: Graph>(&mut self, ui: &mut Ui, layout: &ScatterLayout<G>) -> Response {
+        let graph = layout.graph();
+        self.draw(ui, layout.bbox(), graph, |n| layout.coord(n))
+    }
+
+    /// Draw a frame of the sequence. The view is fit to the bounding box of all frames, such that it
+    /// stays steady while stepping through them.
+    pub fn show_frame<G: Graph>(&mut self, ui: &mut Ui, sequence: &ScatterLayoutSequence<G>, frame: usize) -> Response {
+        let frame = usize::min(frame, sequence.frames() - 1);
+        self.draw(ui, sequence.bbox(), sequence.graph(), |n| sequence.coord(frame, n))
+    }
+
+    fn draw(&mut self, ui: &mut Ui, bbox: &BoundingBox, graph: &impl Graph, coord: impl Fn(usize) -> Point) -> Response {
+        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+        let rect = response.rect;
+        if response.double_clicked() {
+            self.reset();
+        }
+        self.pan += response.drag_delta();
+        if let Some(pointer) = response.hover_pos() {
+            let factor = ui.input(|i| i.zoom_delta() * (i.smooth_scroll_delta.y / 200.).exp());
+            if factor != 1. {
+                // keep the point under the pointer in place
+                let anchor = pointer - rect.center() - self.pan;
+                self.pan -= anchor * (factor - 1.);
+                self.zoom *= factor;
+            }
+        }
+
+        let to_screen = |p: Point| screen(bbox, rect, self.node_radius, self.pan, self.zoom, p);
+        for (u, v) in graph.edges() {
+            painter.line_segment([to_screen(coord(u)), to_screen(coord(v))], self.stroke);
+        }
+        for n in 0..graph.nodes() {
+            let center = to_screen(coord(n));
+            painter.circle(center, self.node_radius, self.fill, self.stroke);
+        }
+        response
+    }
+}
+
+/// Map a layout point into the rect, fitting the bounding box with a margin of one node radius,
+/// before panning and zooming around the center of the rect.
+fn screen(bbox: &BoundingBox, rect: Rect, margin: f32, pan: Vec2, zoom: f32, p: Point) -> Pos2 {
+    let available = rect.shrink(margin);
+    let scale = f32::min(available.width() / bbox.width() as f32, available.height() / bbox.height() as f32);
+    let scale = if scale.is_finite() && scale > 0. { scale } else { 1. };
+    let center = bbox.center();
+    let offset = Vec2::new((p.x() - center.x()) as f32, (p.y() - center.y()) as f32) * scale * zoom;
+    rect.center() + pan + offset
+}
+
+#[cfg(test)]
+mod test {
+    use egui::{Pos2, Rect, Vec2};
+    use ndarray::arr2;
+
+    use super::{screen, LayoutView};
+    use crate::layout::scatter::ScatterLayout;
+    use crate::layout::Point;
+
+    #[test]
+    fn fit_pan_and_zoom() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [100., 50.]])).unwrap();
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(220., 220.));
+        let fit = |p: Point, pan: Vec2, zoom: f32| screen(layout.bbox(), rect, 10., pan, zoom, p);
+        assert_eq!(fit(layout.coord(0), Vec2::ZERO, 1.), Pos2::new(10., 60.));
+        assert_eq!(fit(layout.coord(1), Vec2::ZERO, 1.), Pos2::new(210., 160.));
+        assert_eq!(fit(layout.coord(1), Vec2::new(5., 0.), 2.), Pos2::new(315., 210.));
+
+        // a single node sits in the center
+        let single = ScatterLayout::new(Vec::<(usize, usize)>::new(), arr2(&[[3., 4.]])).unwrap();
+        assert_eq!(screen(single.bbox(), rect, 10., Vec2::ZERO, 1., single.coord(0)), Pos2::new(110., 110.));
+
+        // drawing inside a headless context paints one shape per edge and node
+        let ctx = egui::Context::default();
+        let mut view = LayoutView::default();
+        let output = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| view.show(ui, &layout));
+        });
+        assert!(output.shapes.len() >= 3);
+    }
+}
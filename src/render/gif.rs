@@ -0,0 +1,117 @@
+use gif::{Encoder, Frame, Repeat};
+
+use crate::layout::scatter::ScatterLayoutSequence;
+use crate::{validate, Graph};
+
+use super::png::{rasterize, RasterOptions};
+
+/// Options controlling animated gif output.
+#[derive(Clone, Debug)]
+pub struct GifOptions {
+    raster: RasterOptions,
+    delay: u16,
+    every_nth: usize,
+    repeat: bool,
+}
+
+impl Default for GifOptions {
+    fn default() -> Self {
+        Self {
+            raster: RasterOptions::default().resolution(400, 400),
+            delay: 5,
+            every_nth: 1,
+            repeat: true,
+        }
+    }
+}
+
+impl GifOptions {
+    /// Set the options used to rasterize each frame.
+    pub fn raster(mut self, raster: RasterOptions) -> Self {
+        self.raster = raster;
+        self
+    }
+
+    /// Set the delay between two frames in hundredths of a second.
+    pub fn delay(mut self, centiseconds: u16) -> Self {
+        self.delay = centiseconds;
+        self
+    }
+
+    /// Only render every nth frame of the sequence (plus the last one).
+    pub fn every_nth(mut self, n: usize) -> Self {
+        self.every_nth = n;
+        self
+    }
+
+    /// Whether the animation loops forever or stops at the last frame.
+    pub fn repeat(mut self, repeat: bool) -> Self {
+        self.repeat = repeat;
+        self
+    }
+}
+
+pub trait RenderGIF {
+    /// Encode self as animated gif, returning the file contents.
+    fn render_gif(self, options: &GifOptions) -> Result<Vec<u8>, String>;
+}
+
+impl<G: Graph> RenderGIF for ScatterLayoutSequence<G> {
+    fn render_gif(self, options: &GifOptions) -> Result<Vec<u8>, String> {
+        validate(&self.graph)?;
+        let sequence = self.every_nth(options.every_nth);
+        let (width, height) = (options.raster.width, options.raster.height);
+        let too_large = |_| format!("Image size {} x {} exceeds the gif limits", width, height);
+        let (w, h) = (u16::try_from(width).map_err(too_large)?, u16::try_from(height).map_err(too_large)?);
+
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = Encoder::new(&mut bytes, w, h, &[]).map_err(|e| e.to_string())?;
+            let repeat = if options.repeat { Repeat::Infinite } else { Repeat::Finite(0) };
+            encoder.set_repeat(repeat).map_err(|e| e.to_string())?;
+            for f in 0..sequence.frames() {
+                let pixmap = rasterize(&sequence.graph, sequence.bbox(), |n| sequence.coord(f, n), &options.raster)?;
+                let mut rgba: Vec<u8> = pixmap
+                    .pixels()
+                    .iter()
+                    .flat_map(|p| {
+                        let c = p.demultiply();
+                        [c.red(), c.green(), c.blue(), c.alpha()]
+                    })
+                    .collect();
+                let mut frame = Frame::from_rgba_speed(w, h, &mut rgba, 10);
+                frame.delay = options.delay;
+                encoder.write_frame(&frame).map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{GifOptions, RenderGIF};
+    use crate::layout::scatter::ScatterLayoutSequence;
+    use crate::render::png::RasterOptions;
+    use crate::Float;
+
+    #[test]
+    fn encode_animation() {
+        let frames = (0..5).map(|f| arr2(&[[0., 0.], [10. + f as Float, 10.]])).collect();
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], frames).unwrap();
+        let options = GifOptions::default()
+            .raster(RasterOptions::default().resolution(50, 50))
+            .every_nth(2);
+        let bytes = sequence.render_gif(&options).unwrap();
+        assert_eq!(&bytes[..6], b"GIF89a");
+
+        let mut decoder = gif::DecodeOptions::new().read_info(bytes.as_slice()).unwrap();
+        let mut frames = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        assert_eq!(frames, 3);
+    }
+}
@@ -0,0 +1,190 @@
+//! Standalone html players of layout sequences.
+//!
+//! Unlike SMIL animations, the player can be paused, stepped and scrubbed frame by frame, which
+//! helps to debug the behaviour of engines.
+use std::fmt::Write;
+
+use crate::layout::scatter::ScatterLayoutSequence;
+use crate::render::escape;
+use crate::{validate, Float, Graph};
+
+/// Options of the html player.
+#[derive(Clone, Debug)]
+pub struct PlayerOptions {
+    title: String,
+    fps: Float,
+    node_radius: Float,
+}
+
+impl Default for PlayerOptions {
+    fn default() -> Self {
+        Self {
+            title: "rs-plode layout".to_string(),
+            fps: 10.,
+            node_radius: 10.,
+        }
+    }
+}
+
+impl PlayerOptions {
+    /// Set the title of the html page.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the number of frames shown per second at normal speed.
+    pub fn fps(mut self, fps: Float) -> Self {
+        self.fps = Float::max(fps, 0.1);
+        self
+    }
+
+    /// Set the node radius in layout units.
+    pub fn node_radius(mut self, radius: Float) -> Self {
+        self.node_radius = radius;
+        self
+    }
+}
+
+pub trait RenderHTML {
+    /// Render self into a standalone html page, which plays the frames with play/pause, a scrubber
+    /// and a speed control. The space bar toggles playback, the arrow keys step frame by frame.
+    fn to_html(&self, options: &PlayerOptions) -> Result<String, String>;
+}
+
+impl<G: Graph> RenderHTML for ScatterLayoutSequence<G> {
+    fn to_html(&self, options: &PlayerOptions) -> Result<String, String> {
+        validate(&self.graph)?;
+        let r = options.node_radius;
+        let (lower, bbox) = (self.bbox().lower_left(), self.bbox());
+        let view_box = format!("{} {} {} {}", lower.x() - r, lower.y() - r, bbox.width() + 2. * r, bbox.height() + 2. * r);
+
+        let mut frames = String::with_capacity(20 * self.frames() * self.graph.nodes());
+        for f in 0..self.frames() {
+            let coordinates: Vec<String> = self.frame(f).iter().map(Float::to_string).collect();
+            writeln!(frames, "[{}],", coordinates.join(",")).unwrap();
+        }
+        let edges: Vec<String> = self.graph.edges().map(|(u, v)| format!("{},{}", u, v)).collect();
+        // the title goes last, such that it cannot inject placeholders
+        Ok(PLAYER
+            .replace("{view_box}", &view_box)
+            .replace("{radius}", &r.to_string())
+            .replace("{stroke}", &(r / 10.).to_string())
+            .replace("{fps}", &options.fps.to_string())
+            .replace("{last}", &(self.frames() - 1).to_string())
+            .replace("{edges}", &edges.join(","))
+            .replace("{frames}", &frames)
+            .replace("{title}", &escape(&options.title)))
+    }
+}
+
+const PLAYER: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+  body { font-family: sans-serif; margin: 1em; }
+  svg { width: 100%; height: 80vh; border: 1px solid #ccc; }
+  line { stroke: black; stroke-width: {stroke}; }
+  circle { fill: white; stroke: black; stroke-width: {stroke}; }
+  #controls { display: flex; gap: 1em; align-items: center; margin-top: 0.5em; }
+  #scrubber { flex-grow: 1; }
+</style>
+</head>
+<body>
+<svg id="layout" viewBox="{view_box}"><g id="edges"></g><g id="nodes"></g></svg>
+<div id="controls">
+  <button id="play">Pause</button>
+  <input id="scrubber" type="range" min="0" max="{last}" value="0">
+  <span id="frame"></span>
+  <select id="speed">
+    <option value="0.25">0.25x</option>
+    <option value="0.5">0.5x</option>
+    <option value="1" selected>1x</option>
+    <option value="2">2x</option>
+    <option value="4">4x</option>
+  </select>
+</div>
+<script>
+const edges = [{edges}];
+const frames = [
+{frames}];
+const fps = {fps};
+const ns = "http://www.w3.org/2000/svg";
+const create = (parent, tag, count) => Array.from({ length: count }, () => parent.appendChild(document.createElementNS(ns, tag)));
+const lines = create(document.getElementById("edges"), "line", edges.length / 2);
+const circles = create(document.getElementById("nodes"), "circle", frames[0].length / 2);
+circles.forEach((c) => c.setAttribute("r", {radius}));
+const play = document.getElementById("play");
+const scrubber = document.getElementById("scrubber");
+const speed = document.getElementById("speed");
+const label = document.getElementById("frame");
+let frame = 0, playing = true, elapsed = 0, previous = null;
+
+function show(f) {
+  frame = Math.max(0, Math.min(frames.length - 1, f));
+  const p = frames[frame];
+  lines.forEach((l, i) => {
+    const u = edges[2 * i], v = edges[2 * i + 1];
+    l.setAttribute("x1", p[2 * u]); l.setAttribute("y1", p[2 * u + 1]);
+    l.setAttribute("x2", p[2 * v]); l.setAttribute("y2", p[2 * v + 1]);
+  });
+  circles.forEach((c, n) => { c.setAttribute("cx", p[2 * n]); c.setAttribute("cy", p[2 * n + 1]); });
+  scrubber.value = frame;
+  label.textContent = `frame ${frame + 1} / ${frames.length}`;
+}
+
+function toggle(state) {
+  playing = state;
+  play.textContent = playing ? "Pause" : "Play";
+  if (playing && frame === frames.length - 1) { show(0); }
+}
+
+function tick(time) {
+  if (playing && previous !== null) {
+    elapsed += (time - previous) / 1000 * fps * Number(speed.value);
+    if (elapsed >= 1) {
+      show(frame + Math.floor(elapsed));
+      elapsed %= 1;
+      if (frame === frames.length - 1) { toggle(false); }
+    }
+  }
+  previous = time;
+  requestAnimationFrame(tick);
+}
+
+play.addEventListener("click", () => toggle(!playing));
+scrubber.addEventListener("input", () => { toggle(false); show(Number(scrubber.value)); });
+document.addEventListener("keydown", (e) => {
+  if (e.key === " ") { e.preventDefault(); toggle(!playing); }
+  if (e.key === "ArrowRight") { toggle(false); show(frame + 1); }
+  if (e.key === "ArrowLeft") { toggle(false); show(frame - 1); }
+});
+show(0);
+requestAnimationFrame(tick);
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{PlayerOptions, RenderHTML};
+    use crate::layout::scatter::ScatterLayoutSequence;
+
+    #[test]
+    fn player_page() {
+        let frames = vec![arr2(&[[0., 0.], [10., 0.]]), arr2(&[[0., 0.], [20., 5.]])];
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], frames).unwrap();
+        let html = sequence.to_html(&PlayerOptions::default().title("<fr> & co").node_radius(2.)).unwrap();
+        assert!(html.contains("<title>&lt;fr&gt; &amp; co</title>"));
+        assert!(html.contains(r#"viewBox="-2 -2 24 9""#));
+        assert!(html.contains("const edges = [0,1];"));
+        assert!(html.contains("[0,0,10,0],\n[0,0,20,5],\n"));
+        assert!(html.contains(r#"max="1""#));
+        assert!(!html.contains("{frames}"));
+    }
+}
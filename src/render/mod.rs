@@ -1,2 +1,36 @@
+pub mod atlas;
+#[cfg(all(feature = "svg", feature = "raster"))]
+pub mod bundle;
+pub mod dot;
+#[cfg(feature = "egui")]
+pub mod egui;
+#[cfg(feature = "gif")]
+pub mod gif;
+pub mod html;
+#[cfg(feature = "raster")]
+pub mod png;
+pub mod scene;
 #[cfg(feature = "svg")]
-pub mod svg;
\ No newline at end of file
+pub mod svg;
+pub mod terminal;
+
+/// Escape text for use in xml or html content, e.g. labels in svg documents, which the svg crate
+/// writes as they are.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Quote and escape a string for use in hand written json documents.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,178 @@
+use tiny_skia::{Color, FillRule, Paint, PathBuilder, Pixmap, Stroke, Transform};
+
+use crate::layout::scatter::ScatterLayout;
+use crate::layout::{BoundingBox, Point};
+use crate::{validate, Graph};
+
+/// Options controlling the rasterization of layouts.
+///
+/// Colors are given as RGBA bytes.
+#[derive(Clone, Debug)]
+pub struct RasterOptions {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    node_radius: f32,
+    edge_width: f32,
+    background: [u8; 4],
+    stroke: [u8; 4],
+    fill: [u8; 4],
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 800,
+            node_radius: 10.,
+            edge_width: 1.,
+            background: [255, 255, 255, 255],
+            stroke: [0, 0, 0, 255],
+            fill: [255, 255, 255, 255],
+        }
+    }
+}
+
+impl RasterOptions {
+    /// Set the resolution of the image in pixels.
+    pub fn resolution(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Set the node radius in pixels.
+    pub fn node_radius(mut self, radius: f32) -> Self {
+        self.node_radius = radius;
+        self
+    }
+
+    /// Set the width of edges and node outlines in pixels.
+    pub fn edge_width(mut self, width: f32) -> Self {
+        self.edge_width = width;
+        self
+    }
+
+    /// Set the background color. Use a zero alpha for a transparent background.
+    pub fn background(mut self, rgba: [u8; 4]) -> Self {
+        self.background = rgba;
+        self
+    }
+
+    /// Set the color of edges and node outlines.
+    pub fn stroke(mut self, rgba: [u8; 4]) -> Self {
+        self.stroke = rgba;
+        self
+    }
+
+    /// Set the fill color of nodes.
+    pub fn fill(mut self, rgba: [u8; 4]) -> Self {
+        self.fill = rgba;
+        self
+    }
+}
+
+pub trait RenderPNG {
+    /// Rasterize self into a new image, which can be encoded with [`Pixmap::encode_png`] or
+    /// written with [`Pixmap::save_png`].
+    fn render_png(self, options: &RasterOptions) -> Result<Pixmap, String>;
+}
+
+impl<G: Graph> RenderPNG for ScatterLayout<G> {
+    fn render_png(self, options: &RasterOptions) -> Result<Pixmap, String> {
+        validate(&self.graph)?;
+        rasterize(&self.graph, self.bbox(), |n| self.coord(n), options)
+    }
+}
+
+fn color([r, g, b, a]: [u8; 4]) -> Color {
+    Color::from_rgba8(r, g, b, a)
+}
+
+/// Draw the graph with the given node coordinates, mapping the bounding box onto the image such
+/// that the aspect ratio is preserved and all nodes are fully visible.
+pub(crate) fn rasterize<G: Graph>(
+    graph: &G,
+    bbox: &BoundingBox,
+    coord: impl Fn(usize) -> Point,
+    options: &RasterOptions,
+) -> Result<Pixmap, String> {
+    let mut pixmap = Pixmap::new(options.width, options.height)
+        .ok_or_else(|| format!("Invalid image size {} x {}", options.width, options.height))?;
+    pixmap.fill(color(options.background));
+
+    let margin = options.node_radius + options.edge_width;
+    let (w, h) = (options.width as f32 - 2. * margin, options.height as f32 - 2. * margin);
+    // pixels are single precision, regardless of the precision of the layout
+    let (width, height) = (bbox.width() as f32, bbox.height() as f32);
+    let scale = match (width > 0., height > 0.) {
+        (true, true) => f32::min(w / width, h / height),
+        (true, false) => w / width,
+        (false, true) => h / height,
+        (false, false) => 1.,
+    };
+    let offset = (margin + (w - width * scale) / 2., margin + (h - height * scale) / 2.);
+    let pixel = |n: usize| -> (f32, f32) {
+        let p = coord(n);
+        (
+            offset.0 + (p.x() - bbox.lower_left().x()) as f32 * scale,
+            offset.1 + (p.y() - bbox.lower_left().y()) as f32 * scale,
+        )
+    };
+
+    let mut stroke_paint = Paint::default();
+    stroke_paint.set_color(color(options.stroke));
+    stroke_paint.anti_alias = true;
+    let mut fill_paint = Paint::default();
+    fill_paint.set_color(color(options.fill));
+    fill_paint.anti_alias = true;
+    let stroke = Stroke {
+        width: options.edge_width,
+        ..Stroke::default()
+    };
+
+    let mut edges = PathBuilder::new();
+    for (u, v) in graph.edges() {
+        let (a, b) = (pixel(u), pixel(v));
+        edges.move_to(a.0, a.1);
+        edges.line_to(b.0, b.1);
+    }
+    if let Some(path) = edges.finish() {
+        pixmap.stroke_path(&path, &stroke_paint, &stroke, Transform::identity(), None);
+    }
+
+    for n in 0..graph.nodes() {
+        let (x, y) = pixel(n);
+        if let Some(circle) = PathBuilder::from_circle(x, y, options.node_radius) {
+            pixmap.fill_path(&circle, &fill_paint, FillRule::Winding, Transform::identity(), None);
+            pixmap.stroke_path(&circle, &stroke_paint, &stroke, Transform::identity(), None);
+        }
+    }
+
+    Ok(pixmap)
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{RasterOptions, RenderPNG};
+    use crate::layout::scatter::ScatterLayout;
+
+    #[test]
+    fn rasterize_layout() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [100., 0.]])).unwrap();
+        let options = RasterOptions::default()
+            .resolution(120, 40)
+            .node_radius(5.)
+            .fill([255, 0, 0, 255]);
+        let image = layout.render_png(&options).unwrap();
+        assert_eq!((image.width(), image.height()), (120, 40));
+
+        // node centers are filled red, the rest stays white
+        let red = image.pixel(6, 20).unwrap();
+        assert_eq!((red.red(), red.green(), red.blue()), (255, 0, 0));
+        let white = image.pixel(60, 5).unwrap();
+        assert_eq!((white.red(), white.green(), white.blue()), (255, 255, 255));
+        assert!(image.encode_png().is_ok());
+    }
+}
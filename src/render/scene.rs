@@ -0,0 +1,83 @@
+use std::fmt::Write;
+
+use crate::layout::scatter::ScatterLayout;
+use crate::layout::Point;
+use crate::{validate, Float, Graph};
+
+/// Export of layouts as plain data, e.g. for WebGL viewers and 3D tools.
+pub trait RenderScene {
+    /// Serialize self into a json document with flat `positions` (D coordinates per node) and
+    /// `edges` (two node indices per edge) arrays. For 3D layouts they map directly onto the
+    /// position attribute and the index of a three.js `BufferGeometry` drawn as `LineSegments`.
+    fn to_json(&self) -> Result<String, String>;
+
+    /// Serialize self into a Wavefront OBJ file, with one vertex per node and one line element per
+    /// edge. 2D layouts are placed in the z = 0 plane.
+    fn to_obj(&self) -> Result<String, String>;
+}
+
+fn coordinates<const D: usize>(p: Point<D>) -> String {
+    p.0.iter().map(Float::to_string).collect::<Vec<_>>().join(", ")
+}
+
+impl<G: Graph, const D: usize> RenderScene for ScatterLayout<G, D> {
+    fn to_json(&self) -> Result<String, String> {
+        validate(&self.graph)?;
+        let positions: Vec<String> = (0..self.graph.nodes()).map(|n| coordinates(self.coord(n))).collect();
+        let mut edges: Vec<String> = Vec::with_capacity(self.graph.edge_count_hint().unwrap_or(0));
+        edges.extend(self.graph.edges().map(|(u, v)| format!("{}, {}", u, v)));
+        Ok(format!(
+            "{{\n  \"version\": 1,\n  \"dimensions\": {},\n  \"nodes\": {},\n  \"directed\": {},\n  \"bbox\": {{\"min\": [{}], \"max\": [{}]}},\n  \"positions\": [{}],\n  \"edges\": [{}]\n}}\n",
+            D,
+            self.graph.nodes(),
+            self.graph.is_directed(),
+            coordinates(self.bbox().lower_left()),
+            coordinates(self.bbox().upper_right()),
+            positions.join(", "),
+            edges.join(", ")
+        ))
+    }
+
+    fn to_obj(&self) -> Result<String, String> {
+        validate(&self.graph)?;
+        if !(2..=3).contains(&D) {
+            return Err(format!("OBJ files hold 2D or 3D positions, not {}D", D));
+        }
+        let mut obj = String::new();
+        writeln!(obj, "# rs-plode layout with {} nodes", self.graph.nodes()).unwrap();
+        for n in 0..self.graph.nodes() {
+            let p = self.coord(n);
+            writeln!(obj, "v {} {} {}", p.x(), p.y(), if D == 3 { p.get(2) } else { 0. }).unwrap();
+        }
+        // obj indices are one based
+        for (u, v) in self.graph.edges() {
+            writeln!(obj, "l {} {}", u + 1, v + 1).unwrap();
+        }
+        Ok(obj)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::RenderScene;
+    use crate::layout::scatter::{ScatterLayout, ScatterLayout3};
+
+    #[test]
+    fn json_and_obj() {
+        let layout = ScatterLayout3::with_positions(vec![(0usize, 1usize), (1, 2)], arr2(&[[0., 0., 0.], [1., 2., 3.], [-1., 0.5, 0.]])).unwrap();
+        let json = layout.to_json().unwrap();
+        assert!(json.contains("\"positions\": [0, 0, 0, 1, 2, 3, -1, 0.5, 0]"));
+        assert!(json.contains("\"edges\": [0, 1, 1, 2]"));
+        assert!(json.contains("\"bbox\": {\"min\": [-1, 0, 0], \"max\": [1, 2, 3]}"));
+
+        let obj = layout.to_obj().unwrap();
+        assert!(obj.contains("v 1 2 3\n"));
+        assert!(obj.ends_with("l 1 2\nl 2 3\n"));
+
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 1.], [2., 3.]])).unwrap();
+        assert!(layout.to_json().unwrap().contains("\"dimensions\": 2,"));
+        assert!(layout.to_obj().unwrap().contains("v 2 3 0\n"));
+    }
+}
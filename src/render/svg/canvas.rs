@@ -0,0 +1,142 @@
+use svg::node::element::Group;
+use svg::{Document, Node};
+
+use super::{arc_bbox, background, draw_static, view_box, RenderOptions, ARROW_MARKER_ID};
+use crate::layout::scatter::ScatterLayout;
+use crate::layout::{BoundingBox, Point};
+use crate::{validate, Graph};
+
+/// Composes several layouts into a single svg document, each drawn in its own layer.
+///
+/// Layers are drawn in the order they were added, later ones on top, each with its own render
+/// options and translated by its offset. Unlike [`RenderSVG`](super::RenderSVG), which assumes to
+/// own the document, the canvas chooses the viewBox to show all layers, e.g. two graphs side by
+/// side, or a highlighted subgraph on top of the full layout.
+///
+/// The rules of [stylesheets](RenderOptions::stylesheet) apply to the whole document, hence layers
+/// that emit one should agree on it.
+///
+/// ```
+/// use ndarray::arr2;
+/// use rs_plode::layout::scatter::ScatterLayout;
+/// use rs_plode::layout::Point;
+/// use rs_plode::render::svg::{Canvas, RenderOptions};
+///
+/// let path = ScatterLayout::new(vec![(0usize, 1usize), (1, 2)], arr2(&[[0., 0.], [100., 0.], [200., 0.]])).unwrap();
+/// let highlight = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [100., 0.]])).unwrap();
+/// let document = Canvas::new()
+///     .layer(&path, &RenderOptions::default())
+///     .unwrap()
+///     .layer(&highlight, &RenderOptions::default().stroke("red").edge_width(3.))
+///     .unwrap()
+///     .layer_at(&path, &RenderOptions::default(), Point([0., 300.]))
+///     .unwrap()
+///     .to_document(&RenderOptions::default());
+/// assert_eq!(document.to_string().matches("<g class=\"layer\"").count(), 3);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Canvas {
+    layers: Vec<Group>,
+    /// the bounding box of all layers, after translation
+    bbox: Option<BoundingBox>,
+}
+
+impl Canvas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer drawing the layout in place.
+    pub fn layer<G: Graph>(self, layout: &ScatterLayout<G>, options: &RenderOptions) -> Result<Self, String> {
+        self.layer_at(layout, options, Point([0., 0.]))
+    }
+
+    /// Add a layer drawing the layout translated by the given offset.
+    pub fn layer_at<G: Graph>(mut self, layout: &ScatterLayout<G>, options: &RenderOptions, offset: Point) -> Result<Self, String> {
+        validate(&layout.graph)?;
+        let mut group = Group::new()
+            .set("class", "layer")
+            .set("transform", format!("translate({}, {})", offset.x(), offset.y()));
+        // every layer defines its own arrowhead, sized to its nodes
+        let marker_id = format!("{}-{}", ARROW_MARKER_ID, self.layers.len());
+        draw_static(&mut group, layout, options, None, &marker_id)?;
+        self.layers.push(group);
+
+        let bbox = arc_bbox(*layout.bbox(), &layout.graph, [|n| layout.coord(n)], options);
+        let shift = |p: Point| Point([p.x() + offset.x(), p.y() + offset.y()]);
+        let (lower, upper) = (shift(bbox.lower_left()), shift(bbox.upper_right()));
+        self.bbox = Some(match self.bbox {
+            None => BoundingBox(lower, upper),
+            Some(BoundingBox(a, b)) => BoundingBox(
+                Point([a.x().min(lower.x()), a.y().min(lower.y())]),
+                Point([b.x().max(upper.x()), b.y().max(upper.y())]),
+            ),
+        });
+        Ok(self)
+    }
+
+    /// The number of layers.
+    pub fn layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Create a document showing all layers, whose size and background are taken from the options.
+    pub fn to_document(&self, options: &RenderOptions) -> Document {
+        let mut document = options.document();
+        let Some(bbox) = self.bbox else {
+            return document;
+        };
+        let view_box = view_box(&bbox, 10);
+        document = document
+            .set("viewBox", view_box)
+            .set("preserveAspectRatio", "xMidYMid meet");
+        if let Some(background) = background(view_box, options) {
+            document.append(background);
+        }
+        for layer in &self.layers {
+            document.append(layer.clone());
+        }
+        document
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::Canvas;
+    use crate::layout::scatter::ScatterLayout;
+    use crate::layout::Point;
+    use crate::render::svg::RenderOptions;
+    use crate::Graph;
+
+    struct Directed(Vec<(usize, usize)>);
+
+    impl Graph for Directed {
+        type Edges<'a> = crate::EdgeIter<'a>;
+        fn nodes(&self) -> usize { self.0.nodes() }
+        fn edges(&self) -> Self::Edges<'_> { self.0.edges() }
+        fn is_directed(&self) -> bool { true }
+    }
+
+    #[test]
+    fn compose_layers() {
+        assert_eq!(Canvas::new().to_document(&RenderOptions::default()).to_string().matches("viewBox").count(), 0);
+
+        let layout = ScatterLayout::new(Directed(vec![(0, 1)]), arr2(&[[0., 0.], [1000., 0.]])).unwrap();
+        let canvas = Canvas::new()
+            .layer(&layout, &RenderOptions::default())
+            .unwrap()
+            .layer_at(&layout, &RenderOptions::default().node_radius(5.), Point([0., 1000.]))
+            .unwrap();
+        assert_eq!(canvas.layers(), 2);
+        let document = canvas.to_document(&RenderOptions::default().background("white")).to_string();
+        // the padded view covers both layers
+        assert!(document.contains("viewBox=\"-50 -50 1200 1200\""));
+        assert!(document.contains("transform=\"translate(0, 1000)\""));
+        // each layer has its own arrowhead
+        assert!(document.contains("id=\"arrow-0\"") && document.contains("marker-end=\"url(#arrow-1)\""));
+        assert!(document.contains("refX=\"15\""));
+        assert!(document.contains("<rect fill=\"white\""));
+    }
+}
@@ -0,0 +1,254 @@
+use crate::consts::{PI, TAU};
+
+use svg::node::element::{Group, Path, Text};
+use svg::{Document, Node};
+
+use super::{background, style_node, RenderOptions, RenderSVG, PALETTE};
+use crate::layout::scatter::ScatterLayout;
+use crate::render::escape;
+use crate::{validate, Float, Graph};
+
+/// Draws a layout of nodes on a circle as chord diagram, an alternative encoding of dense weighted
+/// graphs.
+///
+/// Every node becomes a segment of a ring, sized by the total weight of its edges, in the angular
+/// order of the layout around the center of its bounding box. Every edge becomes a ribbon between
+/// the segments of its nodes, whose width at both ends is proportional to its weight. Unweighted
+/// graphs use a weight of 1 per edge.
+///
+/// Ribbons take the color of their source node. Node segments and labels follow the
+/// [`RenderOptions`], besides the node fills, which default to a categorical palette.
+///
+/// ```
+/// use rs_plode::engines::shell::Shell;
+/// use rs_plode::render::svg::{ChordDiagram, RenderOptions, RenderSVG};
+/// use rs_plode::graph::AdjacencyGraph;
+/// use rs_plode::Graph;
+///
+/// let trade = AdjacencyGraph::from_edges(4, vec![(0, 1), (1, 2), (2, 0), (0, 3)])?.with_weights(vec![5., 2., 1., 3.])?;
+/// let layout = trade.layout(Shell::new(vec![]));
+/// let document = ChordDiagram::new(layout).to_document(&RenderOptions::default())?;
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct ChordDiagram<G: Graph> {
+    layout: ScatterLayout<G>,
+    gap: Float,
+    ring: Float,
+    opacity: Float,
+}
+
+impl<G: Graph> ChordDiagram<G> {
+    pub fn new(layout: ScatterLayout<G>) -> Self {
+        Self {
+            layout,
+            gap: 0.02,
+            ring: 0.08,
+            opacity: 0.7,
+        }
+    }
+
+    /// Set the angle between adjacent node segments in radians. Defaults to 0.02.
+    pub fn gap(mut self, angle: Float) -> Self {
+        self.gap = Float::max(angle, 0.);
+        self
+    }
+
+    /// Set the width of the node ring relative to the radius of the circle. Defaults to 0.08.
+    pub fn ring_width(mut self, width: Float) -> Self {
+        self.ring = Float::max(width, 0.);
+        self
+    }
+
+    /// Set the opacity of the ribbons. Defaults to 0.7.
+    pub fn opacity(mut self, opacity: Float) -> Self {
+        self.opacity = opacity.clamp(0., 1.);
+        self
+    }
+
+    /// The angular range of each node segment and of both ends of each edge.
+    #[allow(clippy::type_complexity)]
+    fn angles(&self, weights: &[Float]) -> (Vec<Option<(Float, Float)>>, Vec<[(Float, Float); 2]>) {
+        let graph = self.layout.graph();
+        let nodes = graph.nodes();
+        let polar = self.layout.to_polar(self.layout.bbox().center());
+        let edges: Vec<(usize, usize)> = graph.edges().collect();
+        let mut strength = vec![0.; nodes];
+        for (&(u, v), &w) in edges.iter().zip(weights) {
+            strength[u] += w;
+            strength[v] += w;
+        }
+        let mut order: Vec<usize> = (0..nodes).filter(|&n| strength[n] > 0.).collect();
+        order.sort_by(|&a, &b| polar[a].1.total_cmp(&polar[b].1));
+        let gap = Float::min(self.gap, PI / order.len() as Float);
+        let scale = (TAU - gap * order.len() as Float) / strength.iter().sum::<Float>();
+
+        // the ends of the edges at each node, the partners furthest ahead first
+        let mut ends: Vec<Vec<(Float, usize, usize)>> = vec![Vec::new(); nodes];
+        for (i, &(u, v)) in edges.iter().enumerate() {
+            let ahead = |from: usize, to: usize| match (polar[to].1 - polar[from].1).rem_euclid(TAU) {
+                offset if offset > 0. => offset,
+                _ => TAU,
+            };
+            ends[u].push((ahead(u, v), i, 0));
+            ends[v].push((ahead(v, u), i, 1));
+        }
+
+        let mut segments = vec![None; nodes];
+        let mut ribbons = vec![[(0., 0.); 2]; edges.len()];
+        let mut angle = order.first().map_or(0., |&n| polar[n].1 - strength[n] * scale / 2.);
+        for &n in &order {
+            segments[n] = Some((angle, angle + strength[n] * scale));
+            ends[n].sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+            for &(_, i, side) in &ends[n] {
+                let width = weights[i] * scale;
+                ribbons[i][side] = (angle, angle + width);
+                angle += width;
+            }
+            angle += gap;
+        }
+        (segments, ribbons)
+    }
+}
+
+impl<G: Graph> RenderSVG for ChordDiagram<G> {
+    type Canvas = Document;
+
+    fn render_onto(self, mut document: Document, options: &RenderOptions) -> Result<Document, String> {
+        let graph = self.layout.graph();
+        validate(graph)?;
+        let weights = graph.edge_weights().unwrap_or_else(|| vec![1.; graph.edges().count()]);
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.) {
+            return Err("Chord diagrams require finite, non-negative edge weights".to_string());
+        }
+        if weights.iter().sum::<Float>() <= 0. {
+            return Err("Chord diagrams require at least one edge of positive weight".to_string());
+        }
+
+        let center = self.layout.bbox().center();
+        let polar = self.layout.to_polar(center);
+        let radius = polar.iter().map(|&(r, _)| r).fold(0., Float::max);
+        let radius = if radius > 0. { radius } else { 100. };
+        let outer = radius * (1. + self.ring);
+        // leave room for labels around the ring
+        let extent = outer * 1.4;
+        let view_box = (center.x() - extent, center.y() - extent, 2. * extent, 2. * extent);
+        document = document
+            .set("viewBox", view_box)
+            .set("preserveAspectRatio", "xMidYMid meet");
+        if let Some(background) = background(view_box, options) {
+            document.append(background);
+        }
+
+        let point = |r: Float, angle: Float| (center.x() + r * angle.cos(), center.y() + r * angle.sin());
+        let fill = |n: usize| {
+            options
+                .custom_style()
+                .and_then(|style| style.node_fill(n))
+                .unwrap_or_else(|| PALETTE[n % PALETTE.len()].to_string())
+        };
+        let large = |(from, to): (Float, Float)| u8::from(to - from > PI);
+        let (segments, ribbons) = self.angles(&weights);
+
+        for (i, (u, _)) in graph.edges().enumerate() {
+            if weights[i] <= 0. {
+                continue;
+            }
+            let [a, b] = ribbons[i];
+            let (a0, a1, b0, b1) = (point(radius, a.0), point(radius, a.1), point(radius, b.0), point(radius, b.1));
+            let data = format!(
+                "M {} {} A {r} {r} 0 {} 1 {} {} Q {cx} {cy} {} {} A {r} {r} 0 {} 1 {} {} Q {cx} {cy} {} {} Z",
+                a0.0, a0.1, large(a), a1.0, a1.1, b0.0, b0.1, large(b), b1.0, b1.1, a0.0, a0.1,
+                r = radius,
+                cx = center.x(),
+                cy = center.y(),
+            );
+            document.append(
+                Path::new()
+                    .set("d", data)
+                    .set("fill", fill(u))
+                    .set("fill-opacity", self.opacity)
+                    .set("stroke", "none"),
+            );
+        }
+
+        for (n, segment) in segments.into_iter().enumerate() {
+            let Some(segment) = segment else { continue };
+            let (o0, o1, i0, i1) = (
+                point(outer, segment.0),
+                point(outer, segment.1),
+                point(radius, segment.0),
+                point(radius, segment.1),
+            );
+            let data = format!(
+                "M {} {} A {R} {R} 0 {l} 1 {} {} L {} {} A {r} {r} 0 {l} 0 {} {} Z",
+                o0.0, o0.1, o1.0, o1.1, i1.0, i1.1, i0.0, i0.1,
+                R = outer,
+                r = radius,
+                l = large(segment),
+            );
+            let mut group = Group::new().add(
+                Path::new()
+                    .set("d", data)
+                    .set("fill", fill(n))
+                    .set("stroke", options.stroke_color())
+                    .set("stroke-width", options.edge_width),
+            );
+            if options.labels {
+                let middle = (segment.0 + segment.1) / 2.;
+                let (x, y) = point(outer + 0.05 * radius, middle);
+                let mut text = Text::new()
+                    .set("x", x)
+                    .set("y", y)
+                    .set("fill", options.theme.text())
+                    .set("text-anchor", if middle.cos() >= 0. { "start" } else { "end" })
+                    .set("alignment-baseline", "central");
+                if let Some(size) = options.font_size {
+                    text.assign("font-size", size);
+                }
+                group.append(text.add(svg::node::Text::new(escape(&options.node_label(graph, n)))));
+            }
+            document.append(style_node(group, n, options));
+        }
+        Ok(document)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::ChordDiagram;
+    use crate::layout::scatter::ScatterLayout;
+    use crate::render::svg::{RenderOptions, RenderSVG};
+    use crate::{Float, Weighted};
+
+    #[test]
+    fn ribbons_proportional_to_weight() {
+        // four nodes on a circle, clockwise in screen coordinates, node 3 with a zero weight edge only
+        let graph = Weighted(vec![(0usize, 1usize, 3.), (0, 2, 1.), (1, 2, 0.), (2, 3, 0.)]);
+        let positions = arr2(&[[10., 0.], [0., 10.], [-10., 0.], [0., -10.]]);
+        let layout = ScatterLayout::new(graph, positions).unwrap();
+        let chord = ChordDiagram::new(layout.clone()).gap(0.);
+        let (segments, ribbons) = chord.angles(&[3., 1., 0., 0.]);
+        let span = |(from, to): (Float, Float)| to - from;
+        let unit = crate::consts::TAU / 8.;
+        assert!((span(segments[0].unwrap()) - 4. * unit).abs() < 1e-5);
+        assert!((span(segments[1].unwrap()) - 3. * unit).abs() < 1e-5);
+        assert!(segments[3].is_none());
+        assert!((span(ribbons[0][0]) - 3. * unit).abs() < 1e-5 && (span(ribbons[0][1]) - 3. * unit).abs() < 1e-5);
+        assert!((span(ribbons[1][0]) - unit).abs() < 1e-5);
+        // the segments tile the circle in the order of the layout
+        assert!((segments[1].unwrap().0 - segments[0].unwrap().1).abs() < 1e-5);
+        assert!((segments[2].unwrap().0 - segments[1].unwrap().1).abs() < 1e-5);
+
+        let document = ChordDiagram::new(layout).to_document(&RenderOptions::default()).unwrap().to_string();
+        // two ribbons and three segments
+        assert_eq!(document.matches(" Q ").count(), 4);
+        assert_eq!(document.matches("<g").count(), 3);
+        assert!(document.contains("node 2\n</text>"));
+
+        let negative = ScatterLayout::new(Weighted(vec![(0usize, 1usize, -1.)]), arr2(&[[0., 0.], [1., 0.]])).unwrap();
+        assert!(ChordDiagram::new(negative).to_document(&RenderOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::layout::Point;
+use crate::Float;
+
+/// For each edge, its index among the edges connecting the same pair of nodes in either direction
+/// and the number of such edges.
+pub(super) fn parallel_edges(edges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let key = |&(u, v): &(usize, usize)| (usize::min(u, v), usize::max(u, v));
+    let mut seen = std::collections::HashMap::new();
+    let index: Vec<usize> = edges
+        .iter()
+        .map(|e| {
+            let count = seen.entry(key(e)).or_insert(0);
+            *count += 1;
+            *count - 1
+        })
+        .collect();
+    edges.iter().zip(index).map(|(e, i)| (i, seen[&key(e)])).collect()
+}
+
+/// Shorten the straight edge from `a` to `b` by the radii of its nodes, such that it runs between
+/// their outlines. Edges between overlapping nodes collapse to the middle of the gap between the
+/// centers, self loops are kept.
+pub(super) fn clip((a, b): (Point, Point), (ra, rb): (Float, Float)) -> (Point, Point) {
+    let (dx, dy) = (b.x() - a.x(), b.y() - a.y());
+    let length = dx.hypot(dy);
+    if length == 0. {
+        return (a, b);
+    }
+    let at = |offset: Float| {
+        let t = offset / length;
+        Point([a.x() + t * dx, a.y() + t * dy])
+    };
+    if ra + rb >= length {
+        let middle = at((length + ra - rb) / 2.);
+        return (middle, middle);
+    }
+    (at(ra), at(length - rb))
+}
+
+/// The path data of an edge from `a` to `b`, the `parallel` one of `count` edges between the
+/// same nodes. Parallel edges fan out as quadratic Bézier curves, whose control points deviate from
+/// the straight line by `curvature` times the edge length per edge. Self loops are drawn as
+/// circular arcs on top of the node with given radius, growing for every further loop. `forward`
+/// tells whether the edge runs from the lower to the higher node id.
+pub(super) fn edge_path(
+    (a, b): (Point, Point),
+    (parallel, count): (usize, usize),
+    curvature: Float,
+    radius: Float,
+    forward: bool,
+) -> String {
+    if a == b {
+        // leave and enter the node boundary 30 degrees left and right of its top
+        let (left, right) = (-2. * crate::consts::FRAC_PI_3, -crate::consts::FRAC_PI_3);
+        let arc = 0.75 * radius * (1. + 0.5 * parallel as Float);
+        return format!(
+            "M {} {} A {} {} 0 1 1 {} {}",
+            a.x() + radius * left.cos(),
+            a.y() + radius * left.sin(),
+            arc,
+            arc,
+            a.x() + radius * right.cos(),
+            a.y() + radius * right.sin(),
+        );
+    }
+    // bend relative to the direction from the lower to the higher node, such that opposite edges
+    // between two nodes bend to different sides
+    let sign = if forward { 1. } else { -1. };
+    let offset = sign * curvature * (parallel as Float - (count - 1) as Float / 2.);
+    let (dx, dy) = (b.x() - a.x(), b.y() - a.y());
+    let control = ((a.x() + b.x()) / 2. - offset * dy, (a.y() + b.y()) / 2. + offset * dx);
+    format!("M {} {} Q {} {} {} {}", a.x(), a.y(), control.0, control.1, b.x(), b.y())
+}
+
+/// The path data of an edge from `a` to `b` drawn as axis-aligned polyline. It leaves `a` along the
+/// axis in which the nodes are further apart, turns half way and enters `b` along the same axis.
+pub(super) fn orthogonal_path((a, b): (Point, Point)) -> String {
+    let (dx, dy) = (b.x() - a.x(), b.y() - a.y());
+    let (first, second) = match dx.abs() >= dy.abs() {
+        true => {
+            let middle = a.x() + dx / 2.;
+            ((middle, a.y()), (middle, b.y()))
+        }
+        false => {
+            let middle = a.y() + dy / 2.;
+            ((a.x(), middle), (b.x(), middle))
+        }
+    };
+    format!("M {} {} L {} {} L {} {} L {} {}", a.x(), a.y(), first.0, first.1, second.0, second.1, b.x(), b.y())
+}
+
+/// The ends of an arc, undirected edges start at the left end, such that they run above the line.
+fn arc_ends((a, b): (Point, Point), directed: bool) -> (Point, Point) {
+    match !directed && (b.x(), b.y()) < (a.x(), a.y()) {
+        true => (b, a),
+        false => (a, b),
+    }
+}
+
+/// The point of the semicircle of an edge that is furthest from the straight line.
+pub(super) fn arc_apex(ends: (Point, Point), directed: bool) -> Point {
+    let (a, b) = arc_ends(ends, directed);
+    Point([(a.x() + b.x() + b.y() - a.y()) / 2., (a.y() + b.y() - b.x() + a.x()) / 2.])
+}
+
+/// The path data of an edge from `a` to `b` drawn as semicircle, turning clockwise, see
+/// [`arc_ends`].
+pub(super) fn arc_path(ends: (Point, Point), directed: bool) -> String {
+    let (a, b) = arc_ends(ends, directed);
+    let radius = ((b.x() - a.x()).powi(2) + (b.y() - a.y()).powi(2)).sqrt() / 2.;
+    format!("M {} {} A {} {} 0 0 1 {} {}", a.x(), a.y(), radius, radius, b.x(), b.y())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{arc_apex, arc_path, clip, edge_path, orthogonal_path, parallel_edges};
+    use crate::layout::Point;
+
+    #[test]
+    fn clip_to_outlines() {
+        let (a, b) = clip((Point([0., 0.]), Point([10., 0.])), (2., 3.));
+        assert_eq!((a.x(), a.y(), b.x(), b.y()), (2., 0., 7., 0.));
+        let (a, b) = clip((Point([0., 0.]), Point([0., 4.])), (3., 3.));
+        assert_eq!(a, b);
+        assert_eq!(a.y(), 2.);
+        let p = Point([1., 1.]);
+        assert_eq!(clip((p, p), (2., 2.)), (p, p));
+    }
+
+    #[test]
+    fn fan_out_parallel_edges() {
+        assert_eq!(parallel_edges(&[(0, 1), (1, 2), (1, 0), (2, 2), (2, 2)]), vec![(0, 2), (0, 1), (1, 2), (0, 2), (1, 2)]);
+
+        let ends = (Point([0., 0.]), Point([10., 0.]));
+        assert_eq!(edge_path(ends, (0, 1), 0.2, 5., true), "M 0 0 Q 5 0 10 0");
+        assert_eq!(edge_path(ends, (0, 2), 0.2, 5., true), "M 0 0 Q 5 -1 10 0");
+        assert_eq!(edge_path(ends, (1, 2), 0.2, 5., true), "M 0 0 Q 5 1 10 0");
+        // the opposite edge runs backwards, hence it bends to the other side to end up on top
+        let reversed = (Point([10., 0.]), Point([0., 0.]));
+        assert_eq!(edge_path(reversed, (1, 2), 0.2, 5., false), "M 10 0 Q 5 1 0 0");
+
+        let point = Point([0., 0.]);
+        assert!(edge_path((point, point), (0, 1), 0.2, 10., true).contains(" A 7.5 7.5 0 1 1 "));
+    }
+
+    #[test]
+    fn axis_aligned_edges() {
+        assert_eq!(orthogonal_path((Point([0., 0.]), Point([10., 4.]))), "M 0 0 L 5 0 L 5 4 L 10 4");
+        assert_eq!(orthogonal_path((Point([0., 0.]), Point([4., -10.]))), "M 0 0 L 0 -5 L 4 -5 L 4 -10");
+        assert_eq!(arc_path((Point([0., 0.]), Point([30., 40.])), true), "M 0 0 A 25 25 0 0 1 30 40");
+        assert_eq!(arc_path((Point([10., 0.]), Point([0., 0.])), true), "M 10 0 A 5 5 0 0 1 0 0");
+        assert_eq!(arc_path((Point([10., 0.]), Point([0., 0.])), false), "M 0 0 A 5 5 0 0 1 10 0");
+        assert_eq!(arc_apex((Point([10., 0.]), Point([0., 0.])), true), Point([5., 5.]));
+        assert_eq!(arc_apex((Point([10., 0.]), Point([0., 0.])), false), Point([5., -5.]));
+    }
+}
@@ -0,0 +1,49 @@
+use svg::node::element::{Group, Image, Path, Text};
+
+use crate::render::escape;
+use crate::Float;
+
+/// The shape drawn for a node instead of the default circle.
+///
+/// Glyphs are scaled to the node radius, such that they occupy the same space as the circles and
+/// stay consistent with the spacing of the layout.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Glyph {
+    /// Svg path data within the box from (-1, -1) to (1, 1), filled and stroked like circles.
+    Path(String),
+    /// A text glyph such as an emoji, centered on the node.
+    Emoji(String),
+    /// An image referenced by url or data uri, fitted into the nodes bounding square.
+    Image(String),
+}
+
+impl Glyph {
+    /// Append the glyph for a node with given radius to its group.
+    pub(super) fn append_to(&self, group: Group, radius: Float, stroke: &str, fill: &str) -> Group {
+        match self {
+            Glyph::Path(data) => group.add(
+                Path::new()
+                    .set("d", data.as_str())
+                    .set("transform", format!("scale({})", radius))
+                    .set("stroke", stroke)
+                    .set("stroke-width", 1. / radius)
+                    .set("fill", fill),
+            ),
+            Glyph::Emoji(text) => group.add(
+                Text::new()
+                    .set("font-size", 1.6 * radius)
+                    .set("text-anchor", "middle")
+                    .set("dominant-baseline", "central")
+                    .add(svg::node::Text::new(escape(text))),
+            ),
+            Glyph::Image(href) => group.add(
+                Image::new()
+                    .set("href", href.as_str())
+                    .set("x", -radius)
+                    .set("y", -radius)
+                    .set("width", 2. * radius)
+                    .set("height", 2. * radius),
+            ),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use crate::graph::SubGraphView;
+
+/// Provides the text of node labels, see [`RenderOptions::node_labels`](super::RenderOptions::node_labels).
+///
+/// Implemented for closures, for vectors and maps of displayable values indexed by node, for
+/// petgraph graphs with displayable node weights, and for subgraphs, which label their nodes with
+/// the original indices.
+pub trait Labels {
+    /// The label of the given node, `None` falls back to the default `node {n}`.
+    fn label(&self, node: usize) -> Option<String>;
+}
+
+impl<F: Fn(usize) -> Option<String>> Labels for F {
+    fn label(&self, node: usize) -> Option<String> {
+        self(node)
+    }
+}
+
+impl<T: Display> Labels for Vec<T> {
+    fn label(&self, node: usize) -> Option<String> {
+        self.get(node).map(ToString::to_string)
+    }
+}
+
+impl<T: Display> Labels for BTreeMap<usize, T> {
+    fn label(&self, node: usize) -> Option<String> {
+        self.get(&node).map(ToString::to_string)
+    }
+}
+
+impl Labels for SubGraphView {
+    fn label(&self, node: usize) -> Option<String> {
+        self.original(node).map(|n| format!("node {}", n))
+    }
+}
+
+#[cfg(feature = "petgraph")]
+impl<N, E, Ty, Ix> Labels for petgraph::Graph<N, E, Ty, Ix>
+where
+    N: Display,
+    Ty: petgraph::EdgeType,
+    Ix: petgraph::csr::IndexType,
+{
+    fn label(&self, node: usize) -> Option<String> {
+        self.node_weight(petgraph::graph::NodeIndex::new(node)).map(ToString::to_string)
+    }
+}
@@ -0,0 +1,907 @@
+use crate::layout::bundling::BundledLayout;
+use crate::layout::scatter::{ScatterLayout, ScatterLayoutSequence};
+use crate::layout::{BoundingBox, Point};
+use crate::render::escape;
+use crate::{validate, Float, Graph};
+use svg::node::element::path::Data;
+use svg::node::element::{
+    Animate, AnimateTransform, Circle, Group, Line, Marker, Path, Rectangle, Text,
+};
+#[cfg(feature = "html")]
+use svg::node::element::{Element, ForeignObject};
+use svg::{Document, Node};
+
+mod canvas;
+mod chord;
+mod curves;
+mod glyph;
+mod labels;
+mod options;
+#[cfg(feature = "rhai")]
+mod script;
+mod style;
+mod stylesheet;
+mod timing;
+
+pub use canvas::Canvas;
+pub use chord::ChordDiagram;
+pub use glyph::Glyph;
+pub use labels::Labels;
+pub use options::{DrawOrder, EdgeFade, FadeStyle, RenderOptions, Theme};
+#[cfg(feature = "rhai")]
+pub use script::ScriptStyle;
+pub use style::Style;
+pub use timing::{Easing, Timing};
+
+/// Categorical colors (Tableau 10), e.g. for nodes of different communities.
+pub(crate) const PALETTE: [&str; 10] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7", "#9c755f", "#bab0ac",
+];
+
+pub trait RenderSVG: Sized {
+    type Canvas;
+
+    /// Render self onto canvas using the given options (except for the document size, which is up
+    /// to the canvas) returning Ok in case of success or a string indicating the failure.
+    fn render_onto(self, canvas: Self::Canvas, options: &RenderOptions) -> Result<Self::Canvas, String>;
+
+    /// Render self onto canvas with default options.
+    fn render(self, canvas: Self::Canvas) -> Result<Self::Canvas, String> {
+        self.render_onto(canvas, &RenderOptions::default())
+    }
+
+    /// Render self into a new document created from the given options.
+    fn to_document(self, options: &RenderOptions) -> Result<Document, String>
+    where
+        Self: RenderSVG<Canvas = Document>,
+    {
+        self.render_onto(options.document(), options)
+    }
+}
+
+impl<G: Graph> RenderSVG for ScatterLayout<G> {
+    type Canvas = Document;
+
+    fn render_onto(self, document: Document, options: &RenderOptions) -> Result<Self::Canvas, String> {
+        render_static(&self, document, options, None)
+    }
+}
+
+impl<G: Graph> RenderSVG for BundledLayout<G> {
+    type Canvas = Document;
+
+    /// Render the nodes like the plain layout, and the edges along their bundled paths.
+    fn render_onto(self, document: Document, options: &RenderOptions) -> Result<Self::Canvas, String> {
+        render_static(self.layout(), document, options, Some(self.paths()))
+    }
+}
+
+/// Render a static layout, optionally drawing the edges along the given polylines.
+fn render_static<G: Graph>(
+    layout: &ScatterLayout<G>,
+    mut document: Document,
+    options: &RenderOptions,
+    paths: Option<&[Vec<Point>]>,
+) -> Result<Document, String> {
+    validate(&layout.graph)?;
+    let bbox = arc_bbox(*layout.bbox(), &layout.graph, [|n| layout.coord(n)], options);
+    let view_box = view_box(&bbox, 10);
+    document = document
+        .set("viewBox", view_box)
+        .set("preserveAspectRatio", "xMidYMid meet");
+    if let Some(background) = background(view_box, options) {
+        document.append(background);
+    }
+    draw_static(&mut document, layout, options, paths, ARROW_MARKER_ID)?;
+    Ok(document)
+}
+
+/// Draw the polar grid, definitions, edges and nodes of a static layout onto the container,
+/// referencing the arrowhead marker by the given id.
+fn draw_static<G: Graph>(
+    container: &mut impl Node,
+    layout: &ScatterLayout<G>,
+    options: &RenderOptions,
+    paths: Option<&[Vec<Point>]>,
+    marker_id: &str,
+) -> Result<(), String> {
+    if let Some(grid) = polar_grid(layout.bbox(), options) {
+        container.append(grid);
+    }
+    let directed = layout.graph.is_directed();
+    let offset = match paths {
+        Some(_) => options.base_radius(30.),
+        None => options.arrow_offset(30.),
+    };
+    if let Some(definitions) = stylesheet::definitions(directed.then(|| arrow_marker(marker_id, offset, options)), options) {
+        container.append(definitions);
+    }
+    let strokes = options.edge_strokes(&layout.graph, |n| layout.coord(n));
+    let edges: Vec<(usize, usize)> = layout.graph.edges().collect();
+    let parallel = curves::parallel_edges(&edges);
+    for (i, &(u, v)) in edges.iter().enumerate() {
+        let mut path = Path::new();
+        match (paths, options.curvature) {
+            (Some(paths), _) => {
+                let (first, rest) = paths[i].split_first().ok_or("Empty edge path")?;
+                let data = rest.iter().fold(Data::new().move_to((first.x(), first.y())), |data, p| data.line_to((p.x(), p.y())));
+                path.assign("d", data)
+            }
+            (None, _) if options.orthogonal && u != v => {
+                path.assign("d", curves::orthogonal_path((layout.coord(u), layout.coord(v))))
+            }
+            (None, _) if options.arcs && u != v => path.assign("d", curves::arc_path((layout.coord(u), layout.coord(v)), directed)),
+            (None, None) => {
+                let (a, b) = straight_edge((layout.coord(u), layout.coord(v)), (u, v), 30., options);
+                path.assign("d", Data::new().move_to((a.x(), a.y())).line_to((b.x(), b.y())))
+            }
+            (None, Some(curvature)) => path.assign(
+                "d",
+                curves::edge_path((layout.coord(u), layout.coord(v)), parallel[i], curvature, options.radius(u, 30.), u <= v),
+            ),
+        }
+        if directed {
+            path.assign("marker-end", format!("url(#{})", marker_id));
+        }
+        style_edge(&mut path, i, (u, v), options, &strokes);
+
+        container.append(path);
+    }
+
+    for n in options.node_order(&layout.graph) {
+        let group = Group::new().set(
+            "transform",
+            format!("translate({}, {})", layout.coord(n).x(), layout.coord(n).y()),
+        );
+        let radius = options.radius(n, 30.);
+        let group = match options.node_glyph(n) {
+            Some(glyph) => glyph.append_to(group, radius, options.stroke_color(), &options.node_fill(n)),
+            None => group.add(node_circle(Circle::new().set("r", radius), n, options)),
+        };
+        let group = label(style_node(group, n, options), &layout.graph, n, radius, options);
+
+        container.append(group);
+    }
+    Ok(())
+}
+
+impl<G: Graph> ScatterLayoutSequence<G> {
+    /// Render the sequence with explicit animation timing.
+    ///
+    /// The timing needs exactly one key time per frame, or none for evenly spaced key times.
+    pub fn render_with_timing(
+        self,
+        mut document: Document,
+        options: &RenderOptions,
+        timing: &Timing,
+    ) -> Result<Document, String> {
+        validate(&self.graph)?;
+        let timing = &timing.for_frames(self.frames());
+        let corners = [self.bbox().lower_left(), self.bbox().upper_right()];
+        if corners.iter().any(|p| p.x().abs().max(p.y().abs()) > NORMALIZE_ABOVE) {
+            return self.normalize(NORMALIZED_EXTENT).render_with_timing(document, options, timing);
+        }
+        if timing.key_times().len() != self.frames() {
+            return Err(format!(
+                "Timing has {} key times but the sequence has {} frames",
+                timing.key_times().len(),
+                self.frames()
+            ));
+        }
+
+        let node_group = |n: usize, pos: Point| -> Group {
+            let group = Group::new().set("transform", format!("translate({}, {})", pos.x(), pos.y()));
+            // 1cm corresponds to ~38 user units
+            let size = options.node_size(n);
+            let radius = options.radius(n, 38.);
+            let r = match (options.has_radius(), size == 1.) {
+                (true, _) => radius.to_string(),
+                (false, true) => "1cm".to_string(),
+                (false, false) => format!("{}cm", size),
+            };
+            let group = match options.node_glyph(n) {
+                Some(glyph) => glyph.append_to(group, radius, options.stroke_color(), &options.node_fill(n)),
+                None => group.add(node_circle(Circle::new().set("r", r), n, options)),
+            };
+            label(style_node(group, n, options), &self.graph, n, radius, options)
+        };
+
+        let (schedule, key_times, dur) = timing.schedule()?;
+        let attributes = timing.attributes(schedule.len());
+        let animate = |attribute: &str, values: String| {
+            let mut animate = Animate::new()
+                .set("attributeType", "XML")
+                .set("begin", timing.begin.as_str())
+                .set("dur", dur.as_str())
+                .set("keyTimes", key_times.as_str())
+                .set("attributeName", attribute)
+                .set("values", values);
+            for (name, value) in &attributes {
+                animate.assign(*name, value.as_str());
+            }
+            animate
+        };
+
+        // translate/transform all layouts to match the last layouts bounding box.
+        let bbox = self.bbox();
+        // let layouts: Vec<ScatterLayout<_>> =
+        //     layouts.into_iter().map(|l| l.transform(&bbox)).collect();
+
+        let sequence = &self;
+        let frames = schedule.iter().map(|&f| move |n| sequence.coord(f, n));
+        let view_box = view_box(&arc_bbox(*bbox, &self.graph, frames, options), 10);
+        document = document
+            .set("viewBox", view_box)
+            .set("preserveAspectRatio", "xMidYMid meet");
+        if let Some(background) = background(view_box, options) {
+            document.append(background);
+        }
+        if let Some(grid) = polar_grid(self.bbox(), options) {
+            document.append(grid);
+        }
+        if options.auto_zoom && self.frames() > 1 {
+            let boxes: Vec<String> = schedule
+                .iter()
+                .map(|&f| {
+                    let (x, y, w, h) = self::view_box(&self.frame_bbox(f), 10);
+                    format!("{} {} {} {}", x, y, w, h)
+                })
+                .collect();
+            document.append(animate("viewBox", boxes.join(";")));
+        }
+        if timing.fade_in > 0. {
+            document.append(
+                Animate::new()
+                    .set("attributeName", "opacity")
+                    .set("values", "0;1")
+                    .set("begin", timing.begin.as_str())
+                    .set("dur", format!("{}s", timing.fade_in))
+                    .set("fill", "freeze"),
+            );
+        }
+
+        let directed = self.graph.is_directed();
+        // the animated nodes use a radius of 1cm by default, which corresponds to ~38 user units.
+        let marker = directed.then(|| arrow_marker(ARROW_MARKER_ID, options.arrow_offset(38.), options));
+        if let Some(definitions) = stylesheet::definitions(marker, options) {
+            document.append(definitions);
+        }
+
+        let last = self.frames() - 1;
+        let strokes = options.edge_strokes(&self.graph, |n| self.coord(last, n));
+        if options.curvature.is_some() || options.orthogonal || options.arcs {
+            let edges: Vec<(usize, usize)> = self.graph.edges().collect();
+            let parallel = curves::parallel_edges(&edges);
+            for (i, &(u, v)) in edges.iter().enumerate() {
+                let mut path = Path::new();
+                if directed {
+                    path.assign("marker-end", format!("url(#{})", ARROW_MARKER_ID));
+                }
+                style_edge(&mut path, i, (u, v), options, &strokes);
+                let data: Vec<String> = schedule
+                    .iter()
+                    .map(|&s| {
+                        let ends = (self.coord(s, u), self.coord(s, v));
+                        match options.curvature {
+                            _ if options.orthogonal && u != v => curves::orthogonal_path(ends),
+                            _ if options.arcs && u != v => curves::arc_path(ends, directed),
+                            Some(curvature) => curves::edge_path(ends, parallel[i], curvature, options.radius(u, 38.), u <= v),
+                            None => format!("M {} {} L {} {}", ends.0.x(), ends.0.y(), ends.1.x(), ends.1.y()),
+                        }
+                    })
+                    .collect();
+                path.assign("d", data[0].as_str());
+                path.append(animate("d", data.join(";")));
+                document.append(path);
+            }
+        } else {
+            for (i, (u, v)) in self.graph.edges().enumerate() {
+                let mut line = Line::new();
+                if directed {
+                    line.assign("marker-end", format!("url(#{})", ARROW_MARKER_ID));
+                }
+                style_edge(&mut line, i, (u, v), options, &strokes);
+
+                let ends: Vec<(Point, Point)> = schedule
+                    .iter()
+                    .map(|&s| straight_edge((self.coord(s, u), self.coord(s, v)), (u, v), 38., options))
+                    .collect();
+                let values = |coord: fn(&(Point, Point)) -> Float| -> String {
+                    ends.iter().map(|e| coord(e).to_string()).collect::<Vec<String>>().join(";")
+                };
+                line.append(animate("x1", values(|e| e.0.x())));
+                line.append(animate("y1", values(|e| e.0.y())));
+                line.append(animate("x2", values(|e| e.1.x())));
+                line.append(animate("y2", values(|e| e.1.y())));
+                document.append(line);
+            }
+        }
+
+        for n in options.node_order(&self.graph) {
+            let mut master = node_group(n, Point([0., 0.]));
+
+            if self.frames() > 1 {
+                let trajectory: String = schedule
+                    .iter()
+                    .map(|&s| format!("{} {}", self.coord(s, n).x(), self.coord(s, n).y()))
+                    .collect::<Vec<String>>()
+                    .join(";");
+                let mut transform = AnimateTransform::new()
+                    .set("attributeName", "transform")
+                    .set("type", "translate")
+                    .set("begin", timing.begin.as_str())
+                    .set("dur", dur.as_str())
+                    .set("keyTimes", key_times.as_str())
+                    .set("values", trajectory);
+                for (name, value) in &attributes {
+                    transform.assign(*name, value.as_str());
+                }
+                master.append(transform);
+            }
+
+            document.append(master);
+        }
+
+        Ok(document)
+    }
+}
+
+impl<G: Graph> RenderSVG for ScatterLayoutSequence<G>
+{
+    type Canvas = Document;
+
+    /// Render the sequence with the animation timing of the options, evenly spaced over 10s by default.
+    fn render_onto(self, document: Document, options: &RenderOptions) -> Result<Self::Canvas, String> {
+        let timing = options.animation.clone().unwrap_or_default();
+        self.render_with_timing(document, options, &timing)
+    }
+}
+
+/// Add the label of node `n` of the graph to its group, either the html snippet from the options
+/// fitted into the node circle of given radius, or the text label.
+fn label(group: Group, graph: &impl Graph, n: usize, radius: Float, options: &RenderOptions) -> Group {
+    #[cfg(feature = "html")]
+    if let Some(html) = options.html.get(&n) {
+        let side = radius * Float::sqrt(2.);
+        let mut div = Element::new("div");
+        div.assign("xmlns", "http://www.w3.org/1999/xhtml");
+        div.assign(
+            "style",
+            format!("width: 100%; height: 100%; overflow: hidden; color: {}", options.theme.text()),
+        );
+        div.append(svg::node::Text::new(html.as_str()));
+        return group.add(
+            ForeignObject::new()
+                .set("x", -side / 2.)
+                .set("y", -side / 2.)
+                .set("width", side)
+                .set("height", side)
+                .add(div),
+        );
+    }
+    #[cfg(not(feature = "html"))]
+    let _ = radius;
+    if !options.labels {
+        return group;
+    }
+    let mut text = Text::new().set("class", stylesheet::LABEL_CLASS);
+    stylesheet::present(&mut text, stylesheet::label_properties(options), Vec::new(), options);
+    group.add(text.add(svg::node::Text::new(escape(&options.node_label(graph, n)))))
+}
+
+/// Set the css class of a node group, including the one from the style hooks, if any.
+fn style_node(group: Group, n: usize, options: &RenderOptions) -> Group {
+    let custom = options.custom_style().and_then(|style| style.node_class(n));
+    group.set("class", stylesheet::classes(stylesheet::NODE_CLASS, custom))
+}
+
+/// Style the circle of node `n`, with the fill from the style hooks, if any.
+fn node_circle(mut circle: Circle, n: usize, options: &RenderOptions) -> Circle {
+    let fill = options.node_fill(n);
+    let specific = match fill != options.fill_color() {
+        true => vec![("fill", fill)],
+        false => Vec::new(),
+    };
+    stylesheet::present(&mut circle, stylesheet::node_properties(options), specific, options);
+    circle
+}
+
+/// Apply the edge stroke, width, dash pattern and css class from the style hooks, if any, and the
+/// faded strokes.
+fn style_edge(
+    edge: &mut impl Node,
+    index: usize,
+    endpoints: (usize, usize),
+    options: &RenderOptions,
+    strokes: &Option<(&'static str, Vec<Float>)>,
+) {
+    let mut specific = Vec::new();
+    let mut custom = None;
+    if let Some(style) = options.custom_style() {
+        if let Some(color) = style.edge_stroke(index, endpoints) {
+            specific.push(("stroke", color));
+        }
+        if let Some(width) = style.edge_width(index, endpoints) {
+            specific.push(("stroke-width", width.to_string()));
+        }
+        if let Some(dash) = style.edge_dash(index, endpoints) {
+            specific.push(("stroke-dasharray", dash));
+        }
+        custom = style.edge_class(index, endpoints);
+    }
+    if let Some((attribute, factors)) = strokes {
+        specific.push((*attribute, factors[index].to_string()));
+    }
+    edge.assign("class", stylesheet::classes(stylesheet::EDGE_CLASS, custom));
+    stylesheet::present(edge, stylesheet::edge_properties(options), specific, options);
+}
+
+const ARROW_MARKER_ID: &str = "arrow";
+
+/// Animations with coordinates beyond this magnitude are normalized before rendering, as some
+/// viewers clip huge numbers in SMIL `values`.
+const NORMALIZE_ABOVE: Float = 1e5;
+
+/// The extent of the longest side of normalized animations, in user units.
+const NORMALIZED_EXTENT: Float = 1000.;
+
+/// The ends of the straight edge between the nodes at the given positions, clipped to the node
+/// circles if the options say so.
+fn straight_edge(ends: (Point, Point), (u, v): (usize, usize), default_radius: Float, options: &RenderOptions) -> (Point, Point) {
+    match options.clips_edges() {
+        true => curves::clip(ends, (options.radius(u, default_radius), options.radius(v, default_radius))),
+        false => ends,
+    }
+}
+
+/// Define an arrowhead marker with the given id, whose tip ends `offset` user units before the
+/// end of the edge, such that it touches the boundary of a node with radius `offset`.
+fn arrow_marker(id: &str, offset: Float, options: &RenderOptions) -> Marker {
+    Marker::new()
+        .set("id", id)
+        .set("viewBox", "0 0 10 10")
+        .set("refX", 10. + offset)
+        .set("refY", 5)
+        .set("markerUnits", "userSpaceOnUse")
+        .set("markerWidth", 10)
+        .set("markerHeight", 10)
+        .set("orient", "auto")
+        .add({
+            let mut path = Path::new()
+                .set("class", stylesheet::ARROWHEAD_CLASS)
+                .set("d", Data::new().move_to((0, 0)).line_to((10, 5)).line_to((0, 10)).close());
+            stylesheet::present(&mut path, stylesheet::arrowhead_properties(options), Vec::new(), options);
+            path
+        })
+}
+
+/// Define a rectangle filling the whole viewBox with the background color, if any.
+fn background(view_box: (Float, Float, Float, Float), options: &RenderOptions) -> Option<Rectangle> {
+    options.background_color().map(|color| {
+        Rectangle::new()
+            .set("x", view_box.0)
+            .set("y", view_box.1)
+            .set("width", view_box.2)
+            .set("height", view_box.3)
+            .set("fill", color)
+    })
+}
+
+/// Define the polar grid of the options around the center of the bounding box, if any.
+fn polar_grid(bbox: &BoundingBox, options: &RenderOptions) -> Option<Group> {
+    let (rings, spokes) = options.polar_grid?;
+    let center = bbox.center();
+    let radius = (bbox.width().powi(2) + bbox.height().powi(2)).sqrt() / 2.;
+    let mut group = Group::new()
+        .set("fill", "none")
+        .set("stroke", options.stroke_color())
+        .set("stroke-opacity", 0.3)
+        .set("stroke-width", 0.5);
+    for ring in 1..=rings {
+        group.append(
+            Circle::new()
+                .set("cx", center.x())
+                .set("cy", center.y())
+                .set("r", radius * ring as Float / rings as Float),
+        );
+    }
+    for spoke in 0..spokes {
+        let angle = crate::consts::TAU * spoke as Float / spokes as Float;
+        group.append(
+            Line::new()
+                .set("x1", center.x())
+                .set("y1", center.y())
+                .set("x2", center.x() + radius * angle.cos())
+                .set("y2", center.y() + radius * angle.sin()),
+        );
+    }
+    Some(group)
+}
+
+/// Grow the bounding box to include the semicircles of all edges in the given frames, if drawn as
+/// [arcs](RenderOptions::arc_edges).
+fn arc_bbox<F: Fn(usize) -> Point>(
+    bbox: BoundingBox,
+    graph: &impl Graph,
+    frames: impl IntoIterator<Item = F>,
+    options: &RenderOptions,
+) -> BoundingBox {
+    if !options.arcs || options.orthogonal {
+        return bbox;
+    }
+    let directed = graph.is_directed();
+    let mut bbox = bbox;
+    for coord in frames {
+        for (u, v) in graph.edges().filter(|&(u, v)| u != v) {
+            let apex = curves::arc_apex((coord(u), coord(v)), directed);
+            bbox = BoundingBox(
+                Point([Float::min(bbox.0.x(), apex.x()), Float::min(bbox.0.y(), apex.y())]),
+                Point([Float::max(bbox.1.x(), apex.x()), Float::max(bbox.1.y(), apex.y())]),
+            );
+        }
+    }
+    bbox
+}
+
+/// Define a viewBox tuple from giving bounding box and padding percentage.
+fn view_box(bbox: &BoundingBox, padding: usize) -> (Float, Float, Float, Float) {
+    let frac = padding as Float / 100.;
+
+    let height = Float::max(bbox.height() * (1. + 2. * frac), 400.);
+    let width = Float::max(bbox.width() * (1. + 2. * frac), 400.);
+
+    let shiftx = Float::max(0., height - bbox.height() * (1. + frac)) / 2.;
+    let shifty = Float::max(0., width - bbox.width() * (1. + frac)) / 2.;
+
+    (
+        bbox.lower_left().x() - shiftx,
+        bbox.lower_left().y() - shifty,
+        width,
+        height,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+    use svg::Document;
+
+    use super::{DrawOrder, Easing, EdgeFade, FadeStyle, Glyph, RenderOptions, RenderSVG, Style, Theme, Timing};
+    use crate::engines::arc::{ArcDiagram, Order};
+    use crate::layout::bundling::EdgeBundling;
+    use crate::layout::scatter::{ScatterLayout, ScatterLayoutSequence};
+    use crate::layout::Point;
+    use crate::{Float, Graph, Weighted};
+
+    struct Directed(Vec<(usize, usize)>);
+
+    impl Graph for Directed {
+        type Edges<'a> = crate::EdgeIter<'a>;
+        fn nodes(&self) -> usize { self.0.nodes() }
+        fn edges(&self) -> Self::Edges<'_> { self.0.edges() }
+        fn is_directed(&self) -> bool { true }
+    }
+
+    #[test]
+    fn arrows_only_for_directed_graphs() {
+        let positions = arr2(&[[0., 0.], [100., 100.]]);
+        let directed = ScatterLayout::new(Directed(vec![(0, 1)]), positions.clone()).unwrap();
+        let undirected = ScatterLayout::new(vec![(0usize, 1usize)], positions).unwrap();
+
+        let directed = directed.render(Document::new()).unwrap().to_string();
+        let undirected = undirected.render(Document::new()).unwrap().to_string();
+        assert!(directed.contains("<marker") && directed.contains("marker-end"));
+        assert!(!undirected.contains("<marker") && !undirected.contains("marker-end"));
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn html_node_content() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [100., 100.]])).unwrap();
+        let options = RenderOptions::default().html(1, "<b>hub</b>");
+        let document = layout.to_document(&options).unwrap().to_string();
+        assert_eq!(document.matches("<foreignObject").count(), 1);
+        assert!(document.contains("<b>hub</b>") && document.contains("node 0"));
+        assert!(!document.contains("node 1"));
+    }
+
+    #[test]
+    fn glyphs_by_category() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (1, 2)], arr2(&[[0., 0.], [100., 0.], [0., 100.]])).unwrap();
+        let options = RenderOptions::default()
+            .categories(vec![0, 1])
+            .glyph(0, Glyph::Image("house.png".to_string()))
+            .glyph(2, Glyph::Emoji("x".to_string()));
+        let document = layout.to_document(&options).unwrap().to_string();
+        assert_eq!(document.matches("<image").count(), 1);
+        // node 1 has a category without glyph and node 2 none at all
+        assert_eq!(document.matches("<circle").count(), 2);
+    }
+
+    #[test]
+    fn draw_order() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (1, 2), (1, 3)], arr2(&[[0., 0.], [10., 0.], [0., 10.], [10., 10.]])).unwrap();
+        let order = |options: RenderOptions| {
+            let document = layout.clone().to_document(&options).unwrap().to_string();
+            let mut labels: Vec<(usize, usize)> = (0..4).map(|n| (document.find(&format!("node {}\n", n)).unwrap(), n)).collect();
+            labels.sort();
+            labels.into_iter().map(|(_, n)| n).collect::<Vec<_>>()
+        };
+        assert_eq!(order(RenderOptions::default()), vec![0, 1, 2, 3]);
+        assert_eq!(order(RenderOptions::default().draw_order(DrawOrder::Degree)), vec![0, 2, 3, 1]);
+        assert_eq!(order(RenderOptions::default().draw_order(DrawOrder::Key(vec![3., 2., 1.]))), vec![3, 2, 1, 0]);
+        assert_eq!(order(RenderOptions::default().draw_order(DrawOrder::Degree).z_index(0, 1).z_index(2, -1)), vec![2, 3, 1, 0]);
+        // edges stay below all nodes
+        let document = layout.clone().to_document(&RenderOptions::default().draw_order(DrawOrder::Degree)).unwrap().to_string();
+        assert!(document.rfind("<path").unwrap() < document.find("<circle").unwrap());
+    }
+
+    #[test]
+    fn fade_edges() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (1, 2)], arr2(&[[0., 0.], [10., 0.], [10., 100.]])).unwrap();
+        let document = layout.clone().to_document(&RenderOptions::default()).unwrap().to_string();
+        assert!(!document.contains("stroke-opacity"));
+        let document = layout.clone().to_document(&RenderOptions::default().edge_fade(EdgeFade::Length, FadeStyle::Opacity)).unwrap().to_string();
+        assert!(document.contains("stroke-opacity=\"1\"") && document.contains("stroke-opacity=\"0.15\""));
+
+        let weighted: Vec<(usize, usize, Float)> = vec![(0, 1, 1.), (1, 2, 3.), (0, 2, 2.)];
+        let layout = ScatterLayout::new(Weighted(&weighted), arr2(&[[0., 0.], [10., 0.], [10., 100.]])).unwrap();
+        let sequence = ScatterLayoutSequence::new(Weighted(&weighted), vec![layout.view().to_owned()]).unwrap();
+        let document = sequence.to_document(&RenderOptions::default().edge_fade(EdgeFade::Weight, FadeStyle::Width)).unwrap().to_string();
+        let middle: Float = 0.15 + (1. - 0.15) * 0.5;
+        assert!(document.contains("stroke-width=\"0.15\"") && document.contains(&format!("stroke-width=\"{}\"", middle)));
+    }
+
+    #[test]
+    fn scale_nodes() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [100., 0.]])).unwrap();
+        let options = RenderOptions::default().node_sizes(vec![2.]);
+        let document = layout.clone().to_document(&options).unwrap().to_string();
+        assert!(document.contains("r=\"60\"") && document.contains("r=\"30\""));
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], vec![layout.view().to_owned()]).unwrap();
+        let document = sequence.to_document(&options).unwrap().to_string();
+        assert!(document.contains("r=\"2cm\"") && document.contains("r=\"1cm\""));
+    }
+
+    #[test]
+    fn custom_labels() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (1, 2)], arr2(&[[0., 0.], [100., 0.], [0., 100.]])).unwrap();
+        let options = RenderOptions::default().node_labels(|n: usize| if n == 1 { None } else { Some(format!("n{}", n)) });
+        let document = layout.clone().to_document(&options).unwrap().to_string();
+        assert!(document.contains("n0\n") && document.contains("node 1\n") && document.contains("n2\n"));
+        let markup = RenderOptions::default().node_labels(|_: usize| Some(String::from("a<b & c")));
+        let document = layout.clone().to_document(&markup).unwrap().to_string();
+        assert!(document.contains("a&lt;b &amp; c\n") && !document.contains("a<b"));
+
+        #[cfg(feature = "petgraph")]
+        {
+            let mut graph = petgraph::Graph::<&str, ()>::new();
+            let (a, b) = (graph.add_node("alpha"), graph.add_node("beta"));
+            graph.add_edge(a, b, ());
+            let layout = ScatterLayout::new(graph.clone(), arr2(&[[0., 0.], [100., 0.]])).unwrap();
+            let document = layout.clone().to_document(&RenderOptions::default()).unwrap().to_string();
+            assert!(document.contains("alpha\n") && document.contains("beta\n"));
+            // configured labels take precedence over the node weights
+            let document = layout.to_document(&options).unwrap().to_string();
+            assert!(document.contains("n0\n") && document.contains("beta\n"));
+        }
+    }
+
+    #[test]
+    fn style_hooks() {
+        struct Hooks;
+
+        impl Style for Hooks {
+            fn node_fill(&self, node: usize) -> Option<String> {
+                (node == 1).then(|| "orange".to_string())
+            }
+
+            fn node_class(&self, node: usize) -> Option<String> {
+                Some(format!("community-{}", node % 2))
+            }
+
+            fn edge_width(&self, index: usize, _: (usize, usize)) -> Option<Float> {
+                (index == 0).then_some(4.)
+            }
+
+            fn edge_dash(&self, _: usize, (u, v): (usize, usize)) -> Option<String> {
+                (u == 1 && v == 2).then(|| "5 3".to_string())
+            }
+        }
+
+        let edges = vec![(0usize, 1usize), (1, 2)];
+        let positions = arr2(&[[0., 0.], [100., 0.], [0., 100.]]);
+        let options = RenderOptions::default().style(Hooks);
+        let layout = ScatterLayout::new(edges.clone(), positions.clone()).unwrap();
+        let sequence = ScatterLayoutSequence::new(edges, vec![positions]).unwrap();
+        for document in [layout.to_document(&options).unwrap(), sequence.to_document(&options).unwrap()] {
+            let document = document.to_string();
+            assert_eq!(document.matches("fill=\"orange\"").count(), 1);
+            assert_eq!(document.matches("class=\"node community-0\"").count(), 2);
+            assert_eq!(document.matches("stroke-width=\"4\"").count(), 1);
+            assert_eq!(document.matches("stroke-dasharray=\"5 3\"").count(), 1);
+        }
+    }
+
+    #[test]
+    fn stylesheet_instead_of_inline_styles() {
+        let positions = arr2(&[[0., 0.], [100., 0.]]);
+        let options = RenderOptions::default().stroke("navy").font_size(12.).stylesheet(true);
+        let options = options.style(crate::analysis::community::Partition::new(vec![0, 1]));
+        let layout = ScatterLayout::new(Directed(vec![(0, 1)]), positions).unwrap();
+        let document = layout.to_document(&options).unwrap().to_string();
+        assert!(document.contains(".edge { fill: none; stroke: navy; stroke-width: 1; }"));
+        assert!(document.contains(".label { fill: black; text-anchor: middle; alignment-baseline: central; font-size: 12; }"));
+        assert!(document.contains("<path class=\"arrowhead\""));
+        assert!(!document.contains("stroke=\"navy\""));
+        // styles of individual nodes override the rules
+        assert!(document.contains("<circle r=\"30\" style=\"fill: #4e79a7\"/>"));
+    }
+
+    #[test]
+    fn render_subgraph() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (1, 2), (2, 3)], arr2(&[[0., 0.], [100., 0.], [200., 0.], [300., 0.]])).unwrap();
+        let sub = layout.subgraph(&[2, 3]).unwrap();
+        let options = RenderOptions::default().node_labels(sub.graph().clone());
+        let document = sub.to_document(&options).unwrap().to_string();
+        assert!(document.contains("node 2") && document.contains("node 3") && !document.contains("node 0"));
+        assert!(document.contains("d=\"M200,0 L300,0\""));
+    }
+
+    #[test]
+    fn polar_grid() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[-30., -40.], [30., 40.]])).unwrap();
+        let document = layout.clone().to_document(&RenderOptions::default()).unwrap().to_string();
+        assert!(!document.contains("<line"));
+        let document = layout.to_document(&RenderOptions::default().polar_grid(2, 4)).unwrap().to_string();
+        assert!(document.contains("cx=\"0\" cy=\"0\" r=\"25\"") && document.contains("r=\"50\""));
+        assert_eq!(document.matches("<line").count(), 4);
+        assert!(document.contains("x2=\"50\" y1=\"0\" y2=\"0\""));
+    }
+
+    #[test]
+    fn curved_edges_and_loops() {
+        let edges = vec![(0usize, 1usize), (1, 0), (1, 1)];
+        let positions = arr2(&[[0., 0.], [100., 0.]]);
+        let options = RenderOptions::default().curved_edges(0.2);
+        let layout = ScatterLayout::new(edges.clone(), positions.clone()).unwrap();
+        let document = layout.to_document(&options).unwrap().to_string();
+        assert!(document.contains("d=\"M 0 0 Q 50 -10 100 0\"") && document.contains("d=\"M 100 0 Q 50 10 0 0\""));
+        assert_eq!(document.matches(" A 22.5 22.5 0 1 1 ").count(), 1);
+
+        let sequence = ScatterLayoutSequence::new(edges, vec![positions.clone(), positions]).unwrap();
+        let document = sequence.to_document(&options).unwrap().to_string();
+        assert_eq!(document.matches("attributeName=\"d\"").count(), 3);
+        assert!(!document.contains("<line"));
+    }
+
+    #[test]
+    fn orthogonal_edges() {
+        let edges = vec![(0usize, 1usize), (1, 2), (2, 2)];
+        let positions = arr2(&[[0., 0.], [100., 40.], [90., 100.]]);
+        let options = RenderOptions::default().orthogonal_edges(true);
+        let layout = ScatterLayout::new(edges.clone(), positions.clone()).unwrap();
+        let document = layout.to_document(&options).unwrap().to_string();
+        assert!(document.contains("d=\"M 0 0 L 50 0 L 50 40 L 100 40\""));
+        assert!(document.contains("d=\"M 100 40 L 100 70 L 90 70 L 90 100\""));
+        // self loops are no polylines
+        assert!(document.contains("d=\"M90,100 L90,100\""));
+
+        let sequence = ScatterLayoutSequence::new(edges, vec![positions.clone(), positions]).unwrap();
+        let document = sequence.to_document(&options).unwrap().to_string();
+        assert_eq!(document.matches("attributeName=\"d\"").count(), 3);
+        assert!(document.contains("M 0 0 L 50 0 L 50 40 L 100 40;M 0 0 L 50 0 L 50 40 L 100 40"));
+    }
+
+    #[test]
+    fn clip_edges_to_outlines() {
+        let positions = arr2(&[[0., 0.], [100., 0.], [110., 0.]]);
+        let options = RenderOptions::default().node_radius(10.).clip_edges(true);
+        let layout = ScatterLayout::new(Directed(vec![(0, 1), (1, 2)]), positions.clone()).unwrap();
+        let document = layout.to_document(&options).unwrap().to_string();
+        assert!(document.contains("d=\"M10,0 L90,0\""));
+        // overlapping nodes hide their edge, arrowheads end at the outline
+        assert!(document.contains("d=\"M105,0 L105,0\""));
+        assert!(document.contains("refX=\"10\""));
+
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize), (1, 2)], vec![positions.clone(), positions * 2.]).unwrap();
+        let document = sequence.to_document(&options).unwrap().to_string();
+        assert!(document.contains("values=\"90;190\"") && document.contains("values=\"105;210\""));
+    }
+
+    #[test]
+    fn arc_edges() {
+        let layout = (vec![(0usize, 1usize), (2, 1)]).layout(ArcDiagram::new().order(Order::Index).spacing(1000.));
+        let document = layout.to_document(&RenderOptions::default().arc_edges(true)).unwrap().to_string();
+        assert!(document.contains("d=\"M 0 0 A 500 500 0 0 1 1000 0\"") && document.contains("d=\"M 1000 0 A 500 500 0 0 1 2000 0\""));
+        // the view includes the arcs above the line
+        assert!(document.contains("viewBox=\"-25 -600 2400 600\""));
+    }
+
+    #[test]
+    fn bundled_edges() {
+        let positions = arr2(&[[0., 0.], [100., 0.], [0., 10.], [100., 10.]]);
+        let layout = ScatterLayout::new(vec![(0usize, 1usize), (2, 3)], positions).unwrap();
+        let bundled = EdgeBundling::default().cycles(2).bundle(layout);
+        let document = bundled.to_document(&RenderOptions::default()).unwrap().to_string();
+        // four segments per edge
+        assert_eq!(document.matches(" L").count(), 8);
+        assert_eq!(document.matches("<circle").count(), 4);
+    }
+
+    #[test]
+    fn animation_options() {
+        let frames = vec![arr2(&[[0., 0.], [10., 0.]]), arr2(&[[0., 0.], [20., 0.]]), arr2(&[[0., 0.], [30., 0.]])];
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], frames).unwrap();
+        let timing = Timing::default().frame_dur(2.).easing(Easing::Discrete).repeat_count("indefinite").freeze(false);
+        let document = sequence.to_document(&RenderOptions::default().animation(timing)).unwrap().to_string();
+        // four edge coordinates and two node translations
+        assert_eq!(document.matches("dur=\"4s\"").count(), 6);
+        assert_eq!(document.matches("repeatCount=\"indefinite\"").count(), 6);
+        assert_eq!(document.matches("calcMode=\"discrete\"").count(), 6);
+        assert_eq!(document.matches("fill=\"remove\"").count(), 6);
+        assert!(document.contains("keyTimes=\"0;0.5;1\""));
+    }
+
+    #[test]
+    fn custom_style() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [100., 0.]])).unwrap();
+        let options = RenderOptions::default()
+            .node_radius(5.)
+            .fill("red")
+            .stroke("blue")
+            .background("green")
+            .edge_width(3.)
+            .labels(false);
+        let document = layout.clone().to_document(&options).unwrap().to_string();
+        assert!(document.contains("r=\"5\"") && document.contains("fill=\"red\""));
+        assert!(document.contains("stroke=\"blue\" stroke-width=\"3\""));
+        assert!(document.contains("fill=\"green\""));
+        assert!(!document.contains("node 0") && !document.contains("black"));
+
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], vec![layout.view().to_owned()]).unwrap();
+        let document = sequence.to_document(&options.labels(true).font_size(8.)).unwrap().to_string();
+        assert!(document.contains("r=\"5\"") && !document.contains("cm"));
+        assert!(document.contains("font-size=\"8\""));
+    }
+
+    #[test]
+    fn normalize_large_animations() {
+        let frames = vec![arr2(&[[0., 0.], [1e7, 5e6]]), arr2(&[[-1e7, 0.], [1e7, 1e6]])];
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], frames).unwrap();
+        let document = sequence.to_document(&RenderOptions::default()).unwrap().to_string();
+        assert!(document.contains("values=\"500 0;0 0\""));
+        assert!(document.contains("values=\"1000 250;1000 50\""));
+        assert!(!document.contains("e6") && !document.contains("00000"));
+    }
+
+    #[test]
+    fn zoom_into_frames() {
+        let frames = vec![arr2(&[[0., 0.], [1000., 2000.]]), arr2(&[[0., 0.], [500., 100.]])];
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], frames).unwrap();
+        assert_eq!(sequence.frame_bbox(1).upper_right(), Point([500., 100.]));
+        let document = sequence.to_document(&RenderOptions::default().auto_zoom(true)).unwrap().to_string();
+        assert!(document.contains("attributeName=\"viewBox\""));
+        assert!(document.contains("values=\"-100 -50 1200 2400;-145 -25 600 400\""));
+    }
+
+    #[test]
+    fn hold_and_fade_in() {
+        let frames = vec![arr2(&[[0., 0.], [10., 0.]]), arr2(&[[0., 0.], [20., 0.]])];
+        let sequence = ScatterLayoutSequence::new(vec![(0usize, 1usize)], frames).unwrap();
+        let timing = Timing::uniform(2).dur("4s").hold(1., 3.).fade_in(0.5);
+        let document = sequence.render_with_timing(Document::new(), &RenderOptions::default(), &timing).unwrap().to_string();
+        assert!(document.contains("attributeName=\"opacity\"") && document.contains("dur=\"0.5s\""));
+        assert!(document.contains("dur=\"8s\" fill=\"freeze\" keyTimes=\"0;0.125;0.625;1\""));
+        assert!(document.contains("values=\"10;10;20;20\""));
+    }
+
+    #[test]
+    fn document_from_options() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [100., 100.]])).unwrap();
+        let options = RenderOptions::default().size("300px", "200px").theme(Theme::Dark);
+        let document = layout.to_document(&options).unwrap().to_string();
+        assert!(document.contains("width=\"300px\"") && document.contains("height=\"200px\""));
+        assert!(document.contains(Theme::Dark.background().unwrap()));
+    }
+}
@@ -0,0 +1,589 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+use svg::Document;
+
+use super::{Glyph, Labels, Style, Timing};
+use crate::layout::scatter::ScatterLayout;
+use crate::layout::Point;
+use crate::metrics::{self, Collision};
+use crate::{Float, Graph};
+
+/// Color scheme used when rendering.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    /// Black strokes and text on white nodes, transparent background.
+    #[default]
+    Light,
+    /// Light strokes and text on dark nodes and a dark background.
+    Dark,
+}
+
+impl Theme {
+    /// The background color, if any.
+    pub fn background(&self) -> Option<&'static str> {
+        match self {
+            Theme::Light => None,
+            Theme::Dark => Some("#1e1e1e"),
+        }
+    }
+
+    /// The color of edges and node outlines.
+    pub fn stroke(&self) -> &'static str {
+        match self {
+            Theme::Light => "black",
+            Theme::Dark => "#e0e0e0",
+        }
+    }
+
+    /// The fill color of nodes.
+    pub fn fill(&self) -> &'static str {
+        match self {
+            Theme::Light => "white",
+            Theme::Dark => "#2d2d2d",
+        }
+    }
+
+    /// The color of node labels.
+    pub fn text(&self) -> &'static str {
+        self.stroke()
+    }
+}
+
+/// The order in which nodes are drawn on top of the edges. Later nodes cover earlier ones.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum DrawOrder {
+    /// By node index.
+    #[default]
+    Index,
+    /// By degree, such that hubs are drawn on top.
+    Degree,
+    /// By a user defined key per node, indexed by node. Nodes without key use 0.
+    Key(Vec<Float>),
+}
+
+/// De-emphasis of edges, which keeps dense graphs readable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgeFade {
+    /// All edges are drawn alike.
+    #[default]
+    None,
+    /// Longer edges are drawn fainter.
+    Length,
+    /// Edges with lower weight are drawn fainter. Has no effect on unweighted graphs.
+    Weight,
+}
+
+/// The stroke property used to fade edges, see [`RenderOptions::edge_fade`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FadeStyle {
+    #[default]
+    Opacity,
+    Width,
+}
+
+/// A user provided hook shared between clones of the render options.
+struct Shared<T: ?Sized>(Rc<T>);
+
+impl<T: ?Sized> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("..")
+    }
+}
+
+/// The opacity or width factor of the most faded edge.
+const MIN_FADE: Float = 0.15;
+
+/// Options controlling the generated svg document.
+///
+/// ```
+/// use rs_plode::render::svg::{RenderOptions, Theme};
+///
+/// let options = RenderOptions::default().size("400px", "300px").theme(Theme::Dark);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RenderOptions {
+    width: String,
+    height: String,
+    pub(super) theme: Theme,
+    categories: Vec<usize>,
+    glyphs: BTreeMap<usize, Glyph>,
+    draw_order: DrawOrder,
+    z_index: BTreeMap<usize, i32>,
+    edge_fade: (EdgeFade, FadeStyle),
+    sizes: Vec<Float>,
+    radius: Option<Float>,
+    fill: Option<String>,
+    stroke: Option<String>,
+    background: Option<String>,
+    pub(super) edge_width: Float,
+    pub(super) labels: bool,
+    node_labels: Option<Shared<dyn Labels>>,
+    style: Option<Shared<dyn Style>>,
+    pub(super) font_size: Option<Float>,
+    pub(super) auto_zoom: bool,
+    pub(super) polar_grid: Option<(usize, usize)>,
+    pub(super) curvature: Option<Float>,
+    pub(super) orthogonal: bool,
+    pub(super) arcs: bool,
+    pub(super) clip_edges: bool,
+    pub(super) stylesheet: bool,
+    pub(super) animation: Option<Timing>,
+    #[cfg(feature = "html")]
+    pub(super) html: BTreeMap<usize, String>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            width: "800px".to_string(),
+            height: "800px".to_string(),
+            theme: Theme::default(),
+            categories: Vec::new(),
+            glyphs: BTreeMap::new(),
+            draw_order: DrawOrder::default(),
+            z_index: BTreeMap::new(),
+            edge_fade: (EdgeFade::None, FadeStyle::Opacity),
+            sizes: Vec::new(),
+            radius: None,
+            fill: None,
+            stroke: None,
+            background: None,
+            edge_width: 1.,
+            labels: true,
+            node_labels: None,
+            style: None,
+            font_size: None,
+            auto_zoom: false,
+            polar_grid: None,
+            curvature: None,
+            orthogonal: false,
+            arcs: false,
+            clip_edges: false,
+            stylesheet: false,
+            animation: None,
+            #[cfg(feature = "html")]
+            html: BTreeMap::new(),
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Set the width and height of the document, e.g. `"800px"` or `"100%"`.
+    pub fn size(mut self, width: impl Into<String>, height: impl Into<String>) -> Self {
+        self.width = width.into();
+        self.height = height.into();
+        self
+    }
+
+    /// Set the color scheme.
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Set the node radius in user units, which is scaled by [`Self::node_sizes`].
+    ///
+    /// Defaults to 30 for static layouts and 1cm for animations.
+    ///
+    /// ```
+    /// use rs_plode::render::svg::RenderOptions;
+    ///
+    /// let options = RenderOptions::default()
+    ///     .node_radius(12.)
+    ///     .fill("#ffcc00")
+    ///     .stroke("navy")
+    ///     .edge_width(2.)
+    ///     .font_size(10.);
+    /// ```
+    pub fn node_radius(mut self, radius: Float) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// Override the fill color of nodes given by the theme.
+    pub fn fill(mut self, color: impl Into<String>) -> Self {
+        self.fill = Some(color.into());
+        self
+    }
+
+    /// Override the color of edges, arrows and node outlines given by the theme.
+    pub fn stroke(mut self, color: impl Into<String>) -> Self {
+        self.stroke = Some(color.into());
+        self
+    }
+
+    /// Override the background color given by the theme.
+    pub fn background(mut self, color: impl Into<String>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    /// Set the stroke width of edges in user units, defaults to 1.
+    pub fn edge_width(mut self, width: Float) -> Self {
+        self.edge_width = width;
+        self
+    }
+
+    /// Style nodes, edges, labels and arrowheads by a `<style>` block of css rules for their classes
+    /// `node`, `edge`, `label` and `arrowhead`, instead of presentation attributes on every element.
+    /// Defaults to inline attributes.
+    ///
+    /// Downstream users may then restyle the document by appending their own rules. Styles of
+    /// individual elements, e.g. from the [style hooks](Self::style), go into their `style`
+    /// attribute, which takes precedence over the rules.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    /// use rs_plode::render::svg::{RenderOptions, RenderSVG};
+    ///
+    /// let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [100., 0.]])).unwrap();
+    /// let document = layout.to_document(&RenderOptions::default().stylesheet(true)).unwrap().to_string();
+    /// assert!(document.contains("<style") && document.contains("class=\"edge\""));
+    /// ```
+    pub fn stylesheet(mut self, enabled: bool) -> Self {
+        self.stylesheet = enabled;
+        self
+    }
+
+    /// Show or hide the default node labels. Html snippets are shown regardless.
+    pub fn labels(mut self, visible: bool) -> Self {
+        self.labels = visible;
+        self
+    }
+
+    /// Take the text of node labels from the given provider, instead of the
+    /// [`Graph::node_label`](crate::Graph::node_label) of the rendered graph. Nodes without either
+    /// label show `node {n}`.
+    ///
+    /// ```
+    /// use rs_plode::render::svg::RenderOptions;
+    ///
+    /// let options = RenderOptions::default().node_labels(vec!["root", "left", "right"]);
+    /// let options = RenderOptions::default().node_labels(|n: usize| Some(format!("#{}", n)));
+    /// ```
+    ///
+    /// Petgraph graphs label their nodes by their weights already.
+    pub fn node_labels(mut self, labels: impl Labels + 'static) -> Self {
+        self.node_labels = Some(Shared(Rc::new(labels)));
+        self
+    }
+
+    /// The label text of the given node of the graph.
+    pub(super) fn node_label(&self, graph: &impl Graph, node: usize) -> String {
+        self.node_labels
+            .as_ref()
+            .and_then(|labels| labels.0.label(node))
+            .or_else(|| graph.node_label(node))
+            .unwrap_or_else(|| format!("node {}", node))
+    }
+
+    /// Style individual nodes and edges, on top of the colors and widths set for all of them.
+    pub fn style(mut self, style: impl Style + 'static) -> Self {
+        self.style = Some(Shared(Rc::new(style)));
+        self
+    }
+
+    /// The style hooks, if any.
+    pub(super) fn custom_style(&self) -> Option<&dyn Style> {
+        self.style.as_ref().map(|s| s.0.as_ref())
+    }
+
+    /// The fill color of the given node.
+    pub(super) fn node_fill(&self, node: usize) -> String {
+        self.custom_style()
+            .and_then(|style| style.node_fill(node))
+            .unwrap_or_else(|| self.fill_color().to_string())
+    }
+
+    /// Set the font size of node labels in user units, defaults to the viewers font size.
+    pub fn font_size(mut self, size: Float) -> Self {
+        self.font_size = Some(size);
+        self
+    }
+
+    /// The unscaled node radius, `default` unless set by [`Self::node_radius`].
+    pub(super) fn base_radius(&self, default: Float) -> Float {
+        self.radius.unwrap_or(default)
+    }
+
+    /// The radius of the given node, scaled by its size.
+    pub(super) fn radius(&self, node: usize, default: Float) -> Float {
+        self.base_radius(default) * self.node_size(node)
+    }
+
+    /// Whether straight edges end at the node outlines, see [`Self::clip_edges`].
+    pub(super) fn clips_edges(&self) -> bool {
+        self.clip_edges && self.curvature.is_none() && !self.orthogonal && !self.arcs
+    }
+
+    /// How far arrowheads stay away from the end of their edge, which is the node radius unless
+    /// edges end at the node outlines already.
+    pub(super) fn arrow_offset(&self, default: Float) -> Float {
+        match self.clips_edges() {
+            true => 0.,
+            false => self.base_radius(default),
+        }
+    }
+
+    /// Whether the node radius was set explicitly.
+    pub(super) fn has_radius(&self) -> bool {
+        self.radius.is_some()
+    }
+
+    pub(super) fn fill_color(&self) -> &str {
+        self.fill.as_deref().unwrap_or(self.theme.fill())
+    }
+
+    pub(super) fn stroke_color(&self) -> &str {
+        self.stroke.as_deref().unwrap_or(self.theme.stroke())
+    }
+
+    pub(super) fn background_color(&self) -> Option<&str> {
+        self.background.as_deref().or(self.theme.background())
+    }
+
+    /// Assign a category to each node, indexed by node. Nodes without category are drawn as circles.
+    pub fn categories(mut self, categories: Vec<usize>) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    /// Draw all nodes of the given category with a glyph instead of a circle.
+    ///
+    /// ```
+    /// use rs_plode::render::svg::{Glyph, RenderOptions};
+    ///
+    /// let options = RenderOptions::default()
+    ///     .categories(vec![0, 1, 1])
+    ///     .glyph(0, Glyph::Emoji("🏠".to_string()))
+    ///     .glyph(1, Glyph::Path("M -1 -1 H 1 V 1 H -1 Z".to_string()));
+    /// ```
+    pub fn glyph(mut self, category: usize, glyph: Glyph) -> Self {
+        self.glyphs.insert(category, glyph);
+        self
+    }
+
+    /// The glyph of the given node, if its category has one.
+    pub(super) fn node_glyph(&self, node: usize) -> Option<&Glyph> {
+        self.categories.get(node).and_then(|c| self.glyphs.get(c))
+    }
+
+    /// Scale the radius of each node, indexed by node. Nodes without size use 1.
+    ///
+    /// Passing the same values as to
+    /// [`FruchtermanReingold::masses`](crate::engines::fruchterman_reingold::FruchtermanReingold::masses)
+    /// draws heavy nodes larger, matching the extra space they get.
+    pub fn node_sizes(mut self, sizes: Vec<Float>) -> Self {
+        self.sizes = sizes;
+        self
+    }
+
+    /// The radius scale of the given node.
+    pub(super) fn node_size(&self, node: usize) -> Float {
+        self.custom_style()
+            .and_then(|style| style.node_size(node))
+            .or_else(|| self.sizes.get(node).copied())
+            .unwrap_or(1.)
+    }
+
+    /// Let the view of animations follow the layout, by animating the viewBox through the bounding
+    /// boxes of the individual frames. Otherwise the view shows the bounding box of all frames,
+    /// which makes early frames of shrinking layouts tiny.
+    pub fn auto_zoom(mut self, enabled: bool) -> Self {
+        self.auto_zoom = enabled;
+        self
+    }
+
+    /// Draw a polar grid of concentric rings and radial spokes below the graph, centered on the
+    /// bounding box and reaching its corners. Matches [`ScatterLayout::to_polar`] around
+    /// `bbox().center()`, which helps to analyze the radial structure of a layout.
+    ///
+    /// [`ScatterLayout::to_polar`]: crate::layout::scatter::ScatterLayout::to_polar
+    pub fn polar_grid(mut self, rings: usize, spokes: usize) -> Self {
+        self.polar_grid = Some((rings, spokes));
+        self
+    }
+
+    /// Draw parallel edges as quadratic Bézier curves fanning out by the given curvature, e.g. 0.2,
+    /// and self loops as arcs on top of their node. Otherwise all edges are straight lines and self
+    /// loops are hidden below their node.
+    pub fn curved_edges(mut self, curvature: Float) -> Self {
+        self.curvature = Some(curvature);
+        self
+    }
+
+    /// Draw edges as axis-aligned polylines, which run along the longer axis to half way, cross over
+    /// and continue to their target, e.g. for layouts [snapped to a grid]. Takes precedence over
+    /// [curved edges](Self::curved_edges), except for self loops.
+    ///
+    /// [snapped to a grid]: crate::layout::scatter::ScatterLayout::snap_to_grid
+    pub fn orthogonal_edges(mut self, enabled: bool) -> Self {
+        self.orthogonal = enabled;
+        self
+    }
+
+    /// Draw edges as semicircles, e.g. for [arc diagrams](crate::engines::arc::ArcDiagram). Arcs
+    /// turn clockwise from source to target, i.e. run above the line from left to right. Edges of
+    /// undirected graphs always run above the line. Takes precedence over
+    /// [curved edges](Self::curved_edges), except for self loops.
+    pub fn arc_edges(mut self, enabled: bool) -> Self {
+        self.arcs = enabled;
+        self
+    }
+
+    /// End straight edges at the outlines of their node circles instead of the centers, such that
+    /// they do not show through transparent nodes and arrowheads sit right at the target outline.
+    /// Edges between overlapping nodes shrink to a point. Has no effect on curved, orthogonal, arc
+    /// or bundled edges.
+    pub fn clip_edges(mut self, enabled: bool) -> Self {
+        self.clip_edges = enabled;
+        self
+    }
+
+    /// Set the timing of animations rendered via [`RenderSVG`](super::RenderSVG), e.g. their
+    /// duration, easing and repetition.
+    pub fn animation(mut self, timing: Timing) -> Self {
+        self.animation = Some(timing);
+        self
+    }
+
+    /// Set the order in which nodes are drawn. Edges are always drawn below all nodes.
+    pub fn draw_order(mut self, order: DrawOrder) -> Self {
+        self.draw_order = order;
+        self
+    }
+
+    /// Override the layer of a single node. Nodes are drawn by ascending z-index first, which
+    /// defaults to 0, and by the [`DrawOrder`] second.
+    ///
+    /// ```
+    /// use rs_plode::render::svg::{DrawOrder, RenderOptions};
+    ///
+    /// // hubs on top, but node 3 above everything else
+    /// let options = RenderOptions::default().draw_order(DrawOrder::Degree).z_index(3, 1);
+    /// ```
+    pub fn z_index(mut self, node: usize, z: i32) -> Self {
+        self.z_index.insert(node, z);
+        self
+    }
+
+    /// The nodes of the graph in drawing order, ties are broken by node index.
+    pub(super) fn node_order(&self, graph: &impl Graph) -> Vec<usize> {
+        let nodes = graph.nodes();
+        let key: Vec<Float> = match &self.draw_order {
+            DrawOrder::Index => vec![0.; nodes],
+            DrawOrder::Degree => {
+                let mut degree = vec![0.; nodes];
+                for (u, v) in graph.edges() {
+                    degree[u] += 1.;
+                    degree[v] += 1.;
+                }
+                degree
+            }
+            DrawOrder::Key(keys) => (0..nodes).map(|n| keys.get(n).copied().unwrap_or(0.)).collect(),
+        };
+        let mut order: Vec<usize> = (0..nodes).collect();
+        order.sort_by(|&a, &b| {
+            let z = |n: usize| self.z_index.get(&n).copied().unwrap_or(0);
+            z(a).cmp(&z(b)).then(key[a].total_cmp(&key[b])).then(a.cmp(&b))
+        });
+        order
+    }
+
+    /// Fade edges by their length or weight, using either their opacity or their width.
+    ///
+    /// Factors range linearly from 1 for the most prominent edge down to 0.15 for the faintest,
+    /// widths are relative to the [`Self::edge_width`].
+    /// Animations use the edge lengths of the last frame.
+    ///
+    /// ```
+    /// use rs_plode::render::svg::{EdgeFade, FadeStyle, RenderOptions};
+    ///
+    /// let options = RenderOptions::default().edge_fade(EdgeFade::Length, FadeStyle::Opacity);
+    /// ```
+    pub fn edge_fade(mut self, fade: EdgeFade, style: FadeStyle) -> Self {
+        self.edge_fade = (fade, style);
+        self
+    }
+
+    /// The stroke attribute and its value for each edge, if edges are faded.
+    pub(super) fn edge_strokes(&self, graph: &impl Graph, coord: impl Fn(usize) -> Point) -> Option<(&'static str, Vec<Float>)> {
+        let prominence: Vec<Float> = match self.edge_fade.0 {
+            EdgeFade::None => return None,
+            EdgeFade::Length => graph
+                .edges()
+                .map(|(u, v)| {
+                    let (a, b) = (coord(u), coord(v));
+                    -((a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2)).sqrt()
+                })
+                .collect(),
+            EdgeFade::Weight => graph.edge_weights().unwrap_or_else(|| vec![1.; graph.edges().count()]),
+        };
+        let min = prominence.iter().copied().fold(Float::INFINITY, Float::min);
+        let max = prominence.iter().copied().fold(Float::NEG_INFINITY, Float::max);
+        let (attribute, scale) = match self.edge_fade.1 {
+            FadeStyle::Opacity => ("stroke-opacity", 1.),
+            FadeStyle::Width => ("stroke-width", self.edge_width),
+        };
+        let factors = prominence
+            .iter()
+            .map(|p| if max > min { MIN_FADE + (1. - MIN_FADE) * (p - min) / (max - min) } else { 1. })
+            .map(|f| f * scale)
+            .collect();
+        Some((attribute, factors))
+    }
+
+    /// List the overlapping nodes and labels when rendering the static layout with these options,
+    /// see [`metrics::collisions`]. Assumes a font size of 16 unless set, and ignores html snippets.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use rs_plode::layout::scatter::ScatterLayout;
+    /// use rs_plode::render::svg::RenderOptions;
+    ///
+    /// let layout = ScatterLayout::new(vec![(0, 1)], arr2(&[[0., 0.], [50., 0.]])).unwrap();
+    /// assert!(!RenderOptions::default().collisions(&layout).is_empty());
+    /// assert!(RenderOptions::default().node_radius(10.).labels(false).collisions(&layout).is_empty());
+    /// ```
+    pub fn collisions<G: Graph>(&self, layout: &ScatterLayout<G>) -> Vec<Collision> {
+        let nodes = layout.graph().nodes();
+        let radii: Vec<Float> = (0..nodes).map(|n| self.radius(n, 30.)).collect();
+        let labels: Vec<String> = match self.labels {
+            true => (0..nodes).map(|n| self.node_label(layout.graph(), n)).collect(),
+            false => Vec::new(),
+        };
+        metrics::collisions(layout.view(), &radii, &labels, self.font_size.unwrap_or(16.))
+    }
+
+    /// Render the given html snippet inside the node instead of its default label.
+    ///
+    /// The snippet is embedded via `foreignObject` without escaping, hence it has to be well formed
+    /// xhtml. Only browsers display such content, other svg renderers typically skip it.
+    ///
+    /// ```
+    /// use rs_plode::render::svg::RenderOptions;
+    ///
+    /// let options = RenderOptions::default().html(0, "<b>root</b><br/>3 children");
+    /// ```
+    #[cfg(feature = "html")]
+    pub fn html(mut self, node: usize, snippet: impl Into<String>) -> Self {
+        self.html.insert(node, snippet.into());
+        self
+    }
+
+    /// Create an empty document with the configured size.
+    pub fn document(&self) -> Document {
+        Document::new()
+            .set("width", self.width.as_str())
+            .set("height", self.height.as_str())
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+
+use rhai::{Dynamic, Engine, Scope, AST};
+
+use super::Style;
+use crate::{Float, Graph};
+
+/// The maximal number of operations a single script evaluation may take, which bounds the cost of
+/// user provided scripts in rendering services.
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// Style hooks given as [Rhai](https://rhai.rs) expressions, e.g. read from a config file, such
+/// that rendering services can be restyled without recompiling.
+///
+/// Node scripts see the variables `node` and `degree`, plus one variable per node attribute. Edge
+/// scripts see `index`, `source`, `target`, `weight` (1 for unweighted graphs), `source_degree`
+/// and `target_degree`. A script returning `()` keeps the default style of the node or edge, as
+/// does a script failing at runtime.
+///
+/// ```
+/// use rs_plode::render::svg::{RenderOptions, ScriptStyle};
+///
+/// let graph: Vec<(usize, usize)> = vec![(0, 1), (0, 2), (0, 3)];
+/// let style = ScriptStyle::new(&graph)
+///     .attribute("score", vec![0.5, 2., 1., 4.])
+///     .node_fill(r#"if degree > 2 { "crimson" } else { "white" }"#)?
+///     .node_size("score")?
+///     .edge_dash(r#"if target == 3 { "4 2" }"#)?;
+/// let options = RenderOptions::default().style(style);
+/// # Ok::<(), String>(())
+/// ```
+pub struct ScriptStyle {
+    engine: Engine,
+    degrees: Vec<i64>,
+    weights: Option<Vec<Float>>,
+    attributes: BTreeMap<String, Vec<f64>>,
+    node_fill: Option<AST>,
+    node_size: Option<AST>,
+    node_class: Option<AST>,
+    edge_stroke: Option<AST>,
+    edge_width: Option<AST>,
+    edge_dash: Option<AST>,
+    edge_class: Option<AST>,
+}
+
+impl ScriptStyle {
+    /// Create style hooks without scripts for the given graph, which provides degrees and weights.
+    pub fn new(graph: &impl Graph) -> Self {
+        let mut degrees = vec![0; graph.nodes()];
+        for (u, v) in graph.edges() {
+            degrees[u] += 1;
+            degrees[v] += 1;
+        }
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        Self {
+            engine,
+            degrees,
+            weights: graph.edge_weights(),
+            attributes: BTreeMap::new(),
+            node_fill: None,
+            node_size: None,
+            node_class: None,
+            edge_stroke: None,
+            edge_width: None,
+            edge_dash: None,
+            edge_class: None,
+        }
+    }
+
+    /// Provide a numeric attribute per node to the node scripts. Nodes without value see 0.
+    pub fn attribute(mut self, name: impl Into<String>, values: Vec<f64>) -> Self {
+        self.attributes.insert(name.into(), values);
+        self
+    }
+
+    /// Set the script computing the fill color of each node.
+    pub fn node_fill(mut self, script: &str) -> Result<Self, String> {
+        self.node_fill = Some(self.compile(script)?);
+        Ok(self)
+    }
+
+    /// Set the script computing the radius scale of each node.
+    pub fn node_size(mut self, script: &str) -> Result<Self, String> {
+        self.node_size = Some(self.compile(script)?);
+        Ok(self)
+    }
+
+    /// Set the script computing the css class of each node.
+    pub fn node_class(mut self, script: &str) -> Result<Self, String> {
+        self.node_class = Some(self.compile(script)?);
+        Ok(self)
+    }
+
+    /// Set the script computing the stroke color of each edge.
+    pub fn edge_stroke(mut self, script: &str) -> Result<Self, String> {
+        self.edge_stroke = Some(self.compile(script)?);
+        Ok(self)
+    }
+
+    /// Set the script computing the stroke width of each edge.
+    pub fn edge_width(mut self, script: &str) -> Result<Self, String> {
+        self.edge_width = Some(self.compile(script)?);
+        Ok(self)
+    }
+
+    /// Set the script computing the dash pattern of each edge.
+    pub fn edge_dash(mut self, script: &str) -> Result<Self, String> {
+        self.edge_dash = Some(self.compile(script)?);
+        Ok(self)
+    }
+
+    /// Set the script computing the css class of each edge.
+    pub fn edge_class(mut self, script: &str) -> Result<Self, String> {
+        self.edge_class = Some(self.compile(script)?);
+        Ok(self)
+    }
+
+    fn compile(&self, script: &str) -> Result<AST, String> {
+        self.engine
+            .compile_expression(script)
+            .map_err(|e| format!("Invalid style script '{}': {}", script, e))
+    }
+
+    fn degree(&self, node: usize) -> i64 {
+        self.degrees.get(node).copied().unwrap_or(0)
+    }
+
+    /// Evaluate a node script, if given.
+    fn eval_node(&self, script: &Option<AST>, node: usize) -> Option<Dynamic> {
+        let mut scope = Scope::new();
+        scope.push("node", node as i64);
+        scope.push("degree", self.degree(node));
+        for (name, values) in &self.attributes {
+            scope.push(name.as_str(), values.get(node).copied().unwrap_or(0.));
+        }
+        self.engine.eval_ast_with_scope(&mut scope, script.as_ref()?).ok()
+    }
+
+    /// Evaluate an edge script, if given.
+    fn eval_edge(&self, script: &Option<AST>, index: usize, (u, v): (usize, usize)) -> Option<Dynamic> {
+        let weight = self.weights.as_ref().and_then(|w| w.get(index).copied()).unwrap_or(1.);
+        let mut scope = Scope::new();
+        scope.push("index", index as i64);
+        scope.push("source", u as i64);
+        scope.push("target", v as i64);
+        scope.push("weight", weight as rhai::FLOAT);
+        scope.push("source_degree", self.degree(u));
+        scope.push("target_degree", self.degree(v));
+        self.engine.eval_ast_with_scope(&mut scope, script.as_ref()?).ok()
+    }
+}
+
+/// The script result as text, `()` gives none.
+fn text(value: Option<Dynamic>) -> Option<String> {
+    value.filter(|v| !v.is_unit()).map(|v| v.to_string())
+}
+
+/// The script result as number, either integer or float.
+fn number(value: Option<Dynamic>) -> Option<Float> {
+    let value = value?;
+    value.as_float().ok().map(|f| f as Float).or_else(|| value.as_int().ok().map(|i| i as Float))
+}
+
+impl Style for ScriptStyle {
+    fn node_fill(&self, node: usize) -> Option<String> {
+        text(self.eval_node(&self.node_fill, node))
+    }
+
+    fn node_size(&self, node: usize) -> Option<Float> {
+        number(self.eval_node(&self.node_size, node))
+    }
+
+    fn node_class(&self, node: usize) -> Option<String> {
+        text(self.eval_node(&self.node_class, node))
+    }
+
+    fn edge_stroke(&self, index: usize, edge: (usize, usize)) -> Option<String> {
+        text(self.eval_edge(&self.edge_stroke, index, edge))
+    }
+
+    fn edge_width(&self, index: usize, edge: (usize, usize)) -> Option<Float> {
+        number(self.eval_edge(&self.edge_width, index, edge))
+    }
+
+    fn edge_dash(&self, index: usize, edge: (usize, usize)) -> Option<String> {
+        text(self.eval_edge(&self.edge_dash, index, edge))
+    }
+
+    fn edge_class(&self, index: usize, edge: (usize, usize)) -> Option<String> {
+        text(self.eval_edge(&self.edge_class, index, edge))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ScriptStyle;
+    use crate::render::svg::Style;
+
+    #[test]
+    fn evaluate_scripts() {
+        let graph: Vec<(usize, usize)> = vec![(0, 1), (0, 2), (0, 3)];
+        let style = ScriptStyle::new(&graph)
+            .attribute("score", vec![0.5, 2.])
+            .node_fill(r#"if degree > 2 { "crimson" }"#)
+            .unwrap()
+            .node_size("score * 2")
+            .unwrap()
+            .edge_width("source_degree + target")
+            .unwrap();
+        assert_eq!(Style::node_fill(&style, 0), Some("crimson".to_string()));
+        assert_eq!(Style::node_fill(&style, 1), None);
+        assert_eq!((Style::node_size(&style, 1), Style::node_size(&style, 3)), (Some(4.), Some(0.)));
+        assert_eq!(Style::edge_width(&style, 2, (0, 3)), Some(6.));
+        assert_eq!(Style::edge_dash(&style, 0, (0, 1)), None);
+
+        assert!(ScriptStyle::new(&graph).node_fill("if {").is_err());
+        // runtime errors keep the default style
+        let style = ScriptStyle::new(&graph).node_class(r#"degree + "x" * 2"#).unwrap();
+        assert_eq!(Style::node_class(&style, 0), None);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::Float;
+
+/// Hooks to style individual nodes and edges, see [`RenderOptions::style`](super::RenderOptions::style).
+///
+/// Each method returns `None` by default, which keeps the style configured for all nodes or edges.
+/// Edges are identified by their index in the order of [`Graph::edges`](crate::Graph::edges) and
+/// their (source, target) pair.
+///
+/// ```
+/// use rs_plode::render::svg::{RenderOptions, Style};
+///
+/// /// Color nodes by community and dash edges between communities.
+/// struct Communities(Vec<usize>);
+///
+/// impl Style for Communities {
+///     fn node_fill(&self, node: usize) -> Option<String> {
+///         ["#e41a1c", "#377eb8", "#4daf4a"].get(self.0[node]).map(|c| c.to_string())
+///     }
+///
+///     fn edge_dash(&self, _: usize, (u, v): (usize, usize)) -> Option<String> {
+///         (self.0[u] != self.0[v]).then(|| "4 2".to_string())
+///     }
+/// }
+///
+/// let options = RenderOptions::default().style(Communities(vec![0, 0, 1, 2]));
+/// ```
+pub trait Style {
+    /// The fill color of the node.
+    fn node_fill(&self, _node: usize) -> Option<String> {
+        None
+    }
+
+    /// The radius scale of the node, overriding [`RenderOptions::node_sizes`](super::RenderOptions::node_sizes).
+    fn node_size(&self, _node: usize) -> Option<Float> {
+        None
+    }
+
+    /// The css class of the node group, e.g. to style nodes from an external stylesheet.
+    fn node_class(&self, _node: usize) -> Option<String> {
+        None
+    }
+
+    /// The stroke color of the edge.
+    fn edge_stroke(&self, _index: usize, _edge: (usize, usize)) -> Option<String> {
+        None
+    }
+
+    /// The stroke width of the edge.
+    fn edge_width(&self, _index: usize, _edge: (usize, usize)) -> Option<Float> {
+        None
+    }
+
+    /// The dash pattern of the edge, as used by the `stroke-dasharray` attribute.
+    fn edge_dash(&self, _index: usize, _edge: (usize, usize)) -> Option<String> {
+        None
+    }
+
+    /// The css class of the edge.
+    fn edge_class(&self, _index: usize, _edge: (usize, usize)) -> Option<String> {
+        None
+    }
+}
@@ -0,0 +1,134 @@
+use svg::node::element::{Definitions, Style as StyleElement};
+use svg::Node;
+
+use super::RenderOptions;
+
+/// The css classes of the rendered elements, which an external stylesheet may select.
+pub(super) const NODE_CLASS: &str = "node";
+pub(super) const EDGE_CLASS: &str = "edge";
+pub(super) const LABEL_CLASS: &str = "label";
+pub(super) const ARROWHEAD_CLASS: &str = "arrowhead";
+
+/// The presentation properties of an element as (name, value) pairs.
+pub(super) type Properties = Vec<(&'static str, String)>;
+
+/// The properties shared by all edges.
+pub(super) fn edge_properties(options: &RenderOptions) -> Properties {
+    vec![
+        ("fill", "none".to_string()),
+        ("stroke", options.stroke_color().to_string()),
+        ("stroke-width", options.edge_width.to_string()),
+    ]
+}
+
+/// The properties shared by all node circles.
+pub(super) fn node_properties(options: &RenderOptions) -> Properties {
+    vec![
+        ("stroke", options.stroke_color().to_string()),
+        ("stroke-width", "1".to_string()),
+        ("fill", options.fill_color().to_string()),
+    ]
+}
+
+/// The properties shared by all labels.
+pub(super) fn label_properties(options: &RenderOptions) -> Properties {
+    let mut properties = vec![
+        ("fill", options.theme.text().to_string()),
+        ("text-anchor", "middle".to_string()),
+        ("alignment-baseline", "central".to_string()),
+    ];
+    if let Some(size) = options.font_size {
+        properties.push(("font-size", size.to_string()));
+    }
+    properties
+}
+
+/// The properties of arrowheads.
+pub(super) fn arrowhead_properties(options: &RenderOptions) -> Properties {
+    vec![("fill", options.stroke_color().to_string())]
+}
+
+/// Apply the shared properties followed by the ones specific to the element.
+///
+/// Inline, all of them become presentation attributes. With a stylesheet the shared ones are left
+/// to it and the specific ones go into the `style` attribute, as stylesheet rules take precedence
+/// over presentation attributes.
+pub(super) fn present(element: &mut impl Node, shared: Properties, specific: Properties, options: &RenderOptions) {
+    if !options.stylesheet {
+        for (name, value) in shared.into_iter().chain(specific) {
+            element.assign(name, value);
+        }
+        return;
+    }
+    // later properties override earlier ones of the same name, like reassigned attributes
+    let mut style: Properties = Vec::new();
+    for (name, value) in specific {
+        style.retain(|(n, _)| *n != name);
+        style.push((name, value));
+    }
+    if !style.is_empty() {
+        let style: Vec<String> = style.iter().map(|(name, value)| format!("{}: {}", name, value)).collect();
+        element.assign("style", style.join("; "));
+    }
+}
+
+/// The class attribute of an element with the given base class and the class from the style
+/// hooks, if any.
+pub(super) fn classes(base: &str, custom: Option<String>) -> String {
+    match custom {
+        Some(custom) => format!("{} {}", base, custom),
+        None => base.to_string(),
+    }
+}
+
+/// The css rules styling all elements like the inline attributes would.
+fn rules(options: &RenderOptions) -> String {
+    let rule = |selector: &str, properties: Properties| {
+        let body: Vec<String> = properties.iter().map(|(name, value)| format!("{}: {};", name, value)).collect();
+        format!("{} {{ {} }}", selector, body.join(" "))
+    };
+    [
+        rule(&format!(".{}", EDGE_CLASS), edge_properties(options)),
+        rule(&format!(".{} > circle", NODE_CLASS), node_properties(options)),
+        rule(&format!(".{}", LABEL_CLASS), label_properties(options)),
+        rule(&format!(".{}", ARROWHEAD_CLASS), arrowhead_properties(options)),
+    ]
+    .join("\n")
+}
+
+/// The definitions of the document: the stylesheet if the options ask for one and the given
+/// marker, if any.
+pub(super) fn definitions(marker: Option<impl Node>, options: &RenderOptions) -> Option<Definitions> {
+    if marker.is_none() && !options.stylesheet {
+        return None;
+    }
+    let mut definitions = Definitions::new();
+    if options.stylesheet {
+        definitions.append(StyleElement::new(rules(options)).set("type", "text/css"));
+    }
+    if let Some(marker) = marker {
+        definitions.append(marker);
+    }
+    Some(definitions)
+}
+
+#[cfg(test)]
+mod test {
+    use svg::node::element::Path;
+
+    use super::{classes, present};
+    use crate::render::svg::RenderOptions;
+
+    #[test]
+    fn inline_or_style_attribute() {
+        let shared = || vec![("stroke", "black".to_string()), ("stroke-width", "1".to_string())];
+        let specific = || vec![("stroke-width", "2".to_string()), ("stroke-width", "3".to_string())];
+        let mut path = Path::new();
+        present(&mut path, shared(), specific(), &RenderOptions::default());
+        assert_eq!(path.to_string(), "<path stroke=\"black\" stroke-width=\"3\"/>");
+        let mut path = Path::new();
+        present(&mut path, shared(), specific(), &RenderOptions::default().stylesheet(true));
+        assert_eq!(path.to_string(), "<path style=\"stroke-width: 3\"/>");
+        assert_eq!(classes("node", Some("hub".to_string())), "node hub");
+    }
+}
@@ -0,0 +1,277 @@
+use crate::Float;
+
+/// The interpolation between frames.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    /// Constant speed between frames.
+    #[default]
+    Linear,
+    /// Accelerate and decelerate between frames.
+    EaseInOut,
+    /// A cubic Bézier timing function given by its control points (x1, y1, x2, y2) in [0, 1].
+    Spline(Float, Float, Float, Float),
+    /// Jump from frame to frame.
+    Discrete,
+}
+
+/// Timing shared by all animated elements of a rendered sequence.
+///
+/// Every `animate`/`animateTransform` element receives the same `begin`, `dur` and `keyTimes`,
+/// so edge and node animations stay frame-synchronized regardless of how many frames are rendered.
+///
+/// The default has no key times, which are then spaced evenly over the frames of the rendered
+/// sequence, e.g. when passed to [`RenderOptions::animation`](super::RenderOptions::animation).
+///
+/// ```
+/// use rs_plode::render::svg::{Easing, RenderOptions, Timing};
+///
+/// let timing = Timing::default().frame_dur(0.5).easing(Easing::EaseInOut).repeat_count("indefinite");
+/// let options = RenderOptions::default().animation(timing);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Timing {
+    pub(super) begin: String,
+    pub(super) dur: String,
+    key_times: Vec<Float>,
+    /// seconds per frame, overriding `dur`
+    frame_dur: Option<Float>,
+    /// seconds to show the first and the last frame
+    hold: (Float, Float),
+    /// seconds to fade in the whole drawing
+    pub(super) fade_in: Float,
+    easing: Easing,
+    repeat_count: Option<String>,
+    freeze: bool,
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self::uniform(0)
+    }
+}
+
+impl Timing {
+    /// Evenly spaced key times for the given number of frames.
+    pub fn uniform(frames: usize) -> Self {
+        let last = usize::max(frames, 2) - 1;
+        Self {
+            begin: "0s".to_string(),
+            dur: "10s".to_string(),
+            key_times: (0..frames).map(|f| f as Float / last as Float).collect(),
+            frame_dur: None,
+            hold: (0., 0.),
+            fade_in: 0.,
+            easing: Easing::default(),
+            repeat_count: None,
+            freeze: true,
+        }
+    }
+
+    /// This timing for a sequence of the given number of frames, with evenly spaced key times
+    /// unless given explicitly.
+    pub(super) fn for_frames(&self, frames: usize) -> Self {
+        match self.key_times.is_empty() {
+            true => Self { key_times: Self::uniform(frames).key_times, ..self.clone() },
+            false => self.clone(),
+        }
+    }
+
+    /// Key times proportional to the original iteration indices of the rendered frames.
+    ///
+    /// Use this after subsampling a sequence, so the animation keeps the pacing of the original run.
+    pub fn from_frame_indices(indices: &[usize]) -> Result<Self, String> {
+        if indices.windows(2).any(|w| w[0] >= w[1]) {
+            return Err("Frame indices must be strictly increasing.".to_string());
+        }
+        let (first, last) = match (indices.first(), indices.last()) {
+            (Some(&first), Some(&last)) if last > first => (first, last),
+            _ => return Ok(Self::uniform(indices.len())),
+        };
+        Ok(Self {
+            key_times: indices
+                .iter()
+                .map(|&i| (i - first) as Float / (last - first) as Float)
+                .collect(),
+            ..Self::uniform(indices.len())
+        })
+    }
+
+    /// Set the begin attribute, e.g. `"2s"` or `"click"`.
+    pub fn begin(mut self, begin: impl Into<String>) -> Self {
+        self.begin = begin.into();
+        self
+    }
+
+    /// Set the total duration of the animation, e.g. `"10s"`.
+    pub fn dur(mut self, dur: impl Into<String>) -> Self {
+        self.dur = dur.into();
+        self
+    }
+
+    /// Set the duration per frame in seconds, such that the total duration grows with the number of
+    /// frames. Overrides [`Self::dur`].
+    pub fn frame_dur(mut self, seconds: Float) -> Self {
+        self.frame_dur = Some(Float::max(seconds, 0.));
+        self
+    }
+
+    /// Set the interpolation between frames.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set how often the animation plays, e.g. `"3"` or `"indefinite"` to loop forever.
+    pub fn repeat_count(mut self, count: impl Into<String>) -> Self {
+        self.repeat_count = Some(count.into());
+        self
+    }
+
+    /// Whether to keep showing the last frame once the animation ended (the default), or to return
+    /// to the initial state.
+    pub fn freeze(mut self, freeze: bool) -> Self {
+        self.freeze = freeze;
+        self
+    }
+
+    /// The `fill`, `calcMode`, `keySplines` and `repeatCount` attributes for the given number of
+    /// key times.
+    pub(super) fn attributes(&self, key_times: usize) -> Vec<(&'static str, String)> {
+        let fill = if self.freeze { "freeze" } else { "remove" };
+        let mut attributes = vec![("fill", fill.to_string())];
+        let spline = match self.easing {
+            Easing::Linear => None,
+            Easing::EaseInOut => Some((0.42, 0., 0.58, 1.)),
+            Easing::Spline(x1, y1, x2, y2) => Some((x1, y1, x2, y2)),
+            Easing::Discrete => {
+                attributes.push(("calcMode", "discrete".to_string()));
+                None
+            }
+        };
+        if let Some((x1, y1, x2, y2)) = spline {
+            let spline = format!("{} {} {} {}", x1, y1, x2, y2);
+            attributes.push(("calcMode", "spline".to_string()));
+            attributes.push(("keySplines", vec![spline; usize::max(key_times, 2) - 1].join(";")));
+        }
+        if let Some(count) = &self.repeat_count {
+            attributes.push(("repeatCount", count.clone()));
+        }
+        attributes
+    }
+
+    /// Show the first and the last frame for the given number of seconds, before and after the
+    /// animation of duration `dur`. Requires `dur` to be given in seconds (`"10s"`) or
+    /// milliseconds (`"500ms"`).
+    pub fn hold(mut self, first: Float, last: Float) -> Self {
+        self.hold = (Float::max(first, 0.), Float::max(last, 0.));
+        self
+    }
+
+    /// Fade in the whole drawing over the given number of seconds, starting at `begin`.
+    pub fn fade_in(mut self, seconds: Float) -> Self {
+        self.fade_in = Float::max(seconds, 0.);
+        self
+    }
+
+    /// The key time of each frame in the range [0, 1].
+    pub fn key_times(&self) -> &[Float] {
+        &self.key_times
+    }
+
+    /// The frame shown at each key time, the `keyTimes` attribute and the total duration, which
+    /// include the holds of the first and last frame.
+    pub(super) fn schedule(&self) -> Result<(Vec<usize>, String, String), String> {
+        let frames: Vec<usize> = (0..self.key_times.len()).collect();
+        let attribute = |times: &[Float]| times.iter().map(Float::to_string).collect::<Vec<String>>().join(";");
+        let frame_dur = self.frame_dur.map(|f| f * (usize::max(frames.len(), 2) - 1) as Float);
+        if self.hold == (0., 0.) {
+            let dur = frame_dur.map_or_else(|| self.dur.clone(), |d| format!("{}s", d));
+            return Ok((frames, attribute(&self.key_times), dur));
+        }
+        let dur = match frame_dur {
+            Some(dur) => dur,
+            None => seconds(&self.dur)?,
+        };
+        let total = self.hold.0 + dur + self.hold.1;
+        let mut schedule = Vec::with_capacity(frames.len() + 2);
+        let mut times = Vec::with_capacity(frames.len() + 2);
+        if self.hold.0 > 0. {
+            schedule.push(0);
+            times.push(0.);
+        }
+        for (&f, &t) in frames.iter().zip(&self.key_times) {
+            schedule.push(f);
+            times.push((self.hold.0 + t * dur) / total);
+        }
+        if self.hold.1 > 0. {
+            schedule.push(frames.len() - 1);
+            times.push(1.);
+        }
+        Ok((schedule, attribute(&times), format!("{}s", total)))
+    }
+}
+
+/// Parse a SMIL clock value given in seconds or milliseconds.
+fn seconds(value: &str) -> Result<Float, String> {
+    let value = value.trim();
+    let (number, factor) = if let Some(ms) = value.strip_suffix("ms") {
+        (ms, 0.001)
+    } else {
+        (value.strip_suffix('s').unwrap_or(value), 1.)
+    };
+    number
+        .trim()
+        .parse::<Float>()
+        .map(|n| n * factor)
+        .map_err(|_| format!("Holding frames requires a duration in s or ms, got '{}'", value))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Easing, Timing};
+
+    #[test]
+    fn uniform_key_times() {
+        assert_eq!(Timing::uniform(3).key_times(), &[0., 0.5, 1.]);
+        assert_eq!(Timing::uniform(1).key_times(), &[0.]);
+    }
+
+    #[test]
+    fn key_times_follow_frame_indices() {
+        let timing = Timing::from_frame_indices(&[0, 10, 40]).unwrap();
+        assert_eq!(timing.key_times(), &[0., 0.25, 1.]);
+        assert!(Timing::from_frame_indices(&[0, 5, 5]).is_err());
+    }
+
+    #[test]
+    fn hold_first_and_last_frame() {
+        let (frames, key_times, dur) = Timing::uniform(3).hold(2., 3.).dur("5000ms").schedule().unwrap();
+        assert_eq!(frames, vec![0, 0, 1, 2, 2]);
+        assert_eq!(key_times, "0;0.2;0.45;0.7;1");
+        assert_eq!(dur, "10s");
+
+        let (frames, key_times, dur) = Timing::uniform(2).schedule().unwrap();
+        assert_eq!((frames, key_times.as_str(), dur.as_str()), (vec![0, 1], "0;1", "10s"));
+        assert!(Timing::uniform(2).dur("indefinite").hold(1., 0.).schedule().is_err());
+    }
+
+    #[test]
+    fn per_frame_duration_easing_and_repeat() {
+        let timing = Timing::default().frame_dur(0.5).for_frames(5);
+        assert_eq!(timing.key_times(), &[0., 0.25, 0.5, 0.75, 1.]);
+        assert_eq!(timing.schedule().unwrap().2, "2s");
+        assert_eq!(timing.hold(1., 0.).schedule().unwrap().2, "3s");
+
+        assert_eq!(Timing::uniform(3).attributes(3), vec![("fill", "freeze".to_string())]);
+        let timing = Timing::uniform(3).easing(Easing::EaseInOut).repeat_count("indefinite").freeze(false);
+        assert_eq!(
+            timing.attributes(3),
+            vec![
+                ("fill", "remove".to_string()),
+                ("calcMode", "spline".to_string()),
+                ("keySplines", "0.42 0 0.58 1;0.42 0 0.58 1".to_string()),
+                ("repeatCount", "indefinite".to_string()),
+            ]
+        );
+    }
+}
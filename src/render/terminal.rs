@@ -0,0 +1,190 @@
+use std::fmt;
+
+use crate::layout::scatter::ScatterLayout;
+use crate::{validate, Float, Graph};
+
+/// A character grid where every cell is a unicode braille pattern of 2 x 4 dots.
+///
+/// Small layouts can be printed to a terminal without any file transfer:
+/// ```
+/// use ndarray::arr2;
+/// use rs_plode::layout::scatter::ScatterLayout;
+/// use rs_plode::render::terminal::{BrailleCanvas, RenderTerminal};
+///
+/// let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [10., 5.]])).unwrap();
+/// let mut canvas = BrailleCanvas::new(20, 5);
+/// layout.draw(&mut canvas).unwrap();
+/// println!("{}", canvas);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BrailleCanvas {
+    columns: usize,
+    rows: usize,
+    dots: Vec<bool>,
+}
+
+impl BrailleCanvas {
+    /// Create an empty canvas with the given size in characters.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            dots: vec![false; columns * 2 * rows * 4],
+        }
+    }
+
+    /// The width in dots.
+    pub fn width(&self) -> usize {
+        self.columns * 2
+    }
+
+    /// The height in dots.
+    pub fn height(&self) -> usize {
+        self.rows * 4
+    }
+
+    /// Set a single dot, ignoring coordinates outside of the canvas.
+    pub fn set(&mut self, x: i64, y: i64) {
+        if x >= 0 && y >= 0 && (x as usize) < self.width() && (y as usize) < self.height() {
+            let width = self.width();
+            self.dots[y as usize * width + x as usize] = true;
+        }
+    }
+
+    /// Draw a straight line between two dots (Bresenham).
+    pub fn line(&mut self, (x0, y0): (i64, i64), (x1, y1): (i64, i64)) {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+        let (mut x, mut y, mut err) = (x0, y0, dx + dy);
+        loop {
+            self.set(x, y);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Get the braille character of the cell at given column and row.
+    pub fn cell(&self, column: usize, row: usize) -> char {
+        // bit of each dot within a braille pattern, indexed by [dy][dx]
+        const BITS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+        let mut pattern = 0;
+        for (dy, bits) in BITS.iter().enumerate() {
+            for (dx, bit) in bits.iter().enumerate() {
+                if self.dots[(row * 4 + dy) * self.width() + column * 2 + dx] {
+                    pattern |= bit;
+                }
+            }
+        }
+        char::from_u32(0x2800 + pattern).unwrap()
+    }
+}
+
+impl fmt::Display for BrailleCanvas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            let line: String = (0..self.columns).map(|column| self.cell(column, row)).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+pub trait RenderTerminal {
+    /// Draw self onto the canvas, scaled to fill it, returning a string indicating a failure.
+    fn draw(&self, canvas: &mut BrailleCanvas) -> Result<(), String>;
+}
+
+impl<G: Graph> RenderTerminal for ScatterLayout<G> {
+    fn draw(&self, canvas: &mut BrailleCanvas) -> Result<(), String> {
+        validate(&self.graph)?;
+        let bbox = self.bbox();
+        // keep a margin of one dot, such that node markers are not cut off.
+        let scale = |value: Float, min: Float, extent: Float, dots: usize| -> i64 {
+            let usable = dots.saturating_sub(3) as Float;
+            if extent > 0. {
+                1 + ((value - min) / extent * usable).round() as i64
+            } else {
+                (dots / 2) as i64
+            }
+        };
+        let (width, height) = (canvas.width(), canvas.height());
+        let dot = |n: usize| {
+            let p = self.coord(n);
+            (
+                scale(p.x(), bbox.lower_left().x(), bbox.width(), width),
+                scale(p.y(), bbox.lower_left().y(), bbox.height(), height),
+            )
+        };
+
+        for (u, v) in self.graph.edges() {
+            let (a, b) = (dot(u), dot(v));
+            canvas.line(a, b);
+        }
+        for n in 0..self.graph.nodes() {
+            let (x, y) = dot(n);
+            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1), (0, 0)] {
+                canvas.set(x + dx, y + dy);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A ratatui widget drawing a layout as braille characters into the widgets area.
+#[cfg(feature = "ratatui")]
+pub struct LayoutWidget<'a, G: Graph>(pub &'a ScatterLayout<G>);
+
+#[cfg(feature = "ratatui")]
+impl<G: Graph> ratatui::widgets::Widget for LayoutWidget<'_, G> {
+    fn render(self, area: ratatui::layout::Rect, buf: &mut ratatui::buffer::Buffer) {
+        let mut canvas = BrailleCanvas::new(area.width as usize, area.height as usize);
+        if self.0.draw(&mut canvas).is_err() {
+            return;
+        }
+        for row in 0..area.height {
+            for column in 0..area.width {
+                let c = canvas.cell(column as usize, row as usize);
+                if c != '\u{2800}' {
+                    buf[(area.x + column, area.y + row)].set_char(c);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::arr2;
+
+    use super::{BrailleCanvas, RenderTerminal};
+    use crate::layout::scatter::ScatterLayout;
+
+    #[test]
+    fn braille_patterns() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        canvas.set(0, 0);
+        canvas.set(3, 3);
+        assert_eq!(canvas.to_string(), "\u{2801}\u{2880}\n");
+    }
+
+    #[test]
+    fn draw_layout() {
+        let layout = ScatterLayout::new(vec![(0usize, 1usize)], arr2(&[[0., 0.], [10., 0.]])).unwrap();
+        let mut canvas = BrailleCanvas::new(10, 3);
+        layout.draw(&mut canvas).unwrap();
+        let text = canvas.to_string();
+        assert_eq!(text.lines().count(), 3);
+        // the edge spans the whole width
+        assert!(text.lines().nth(1).unwrap().chars().all(|c| c != '\u{2800}'));
+    }
+}
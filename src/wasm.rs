@@ -0,0 +1,46 @@
+//! Bindings for web visualizations, built with `wasm-pack build --no-default-features --features
+//! wasm-bindgen` for the `wasm32-unknown-unknown` target.
+//!
+//! Edges are passed as flat `Uint32Array` of (source, target) pairs and positions are returned as
+//! flat `Float32Array` of (x, y) pairs, indexed by node.
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::engines::fruchterman_reingold::FruchtermanReingold;
+use crate::engines::termination::Termination;
+use crate::{Float, Graph};
+
+/// Lay out the graph given by flat (source, target) pairs with the default Fruchterman Reingold
+/// engine. The graph has one node more than the largest node index.
+#[wasm_bindgen]
+pub fn compute_layout(edges: &[u32]) -> Result<Vec<f32>, String> {
+    compute_layout_with(edges, 0, 150., 0, 200)
+}
+
+/// Lay out the graph given by flat (source, target) pairs with a Fruchterman Reingold engine of
+/// the given parameters. The graph has at least the given number of nodes, such that isolated
+/// nodes can be laid out, too.
+#[wasm_bindgen]
+pub fn compute_layout_with(edges: &[u32], nodes: u32, k: f32, seed: u32, iterations: u32) -> Result<Vec<f32>, String> {
+    if !edges.len().is_multiple_of(2) {
+        return Err(format!("Expected pairs of node indices, got {} values", edges.len()));
+    }
+    let edges: Vec<(usize, usize)> = edges.chunks(2).map(|e| (e[0] as usize, e[1] as usize)).collect();
+    let nodes = edges.iter().map(|&(u, v)| usize::max(u, v) + 1).fold(nodes as usize, usize::max);
+    let graph = crate::graph::AdjacencyGraph::from_edges(nodes, edges)?;
+    let engine = FruchtermanReingold::new(k as Float, seed as u64).until(Termination::Iterations(iterations as usize));
+    Ok(graph.layout(engine).view().iter().map(|&p| p as f32).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compute_layout, compute_layout_with};
+
+    #[test]
+    fn flat_positions() {
+        let positions = compute_layout(&[0, 1, 1, 2, 2, 0]).unwrap();
+        assert_eq!(positions.len(), 6);
+        assert!(positions.iter().all(|p| p.is_finite()));
+        assert_eq!(compute_layout_with(&[0, 1], 4, 100., 1, 10).unwrap().len(), 8);
+        assert!(compute_layout(&[0, 1, 2]).is_err());
+    }
+}